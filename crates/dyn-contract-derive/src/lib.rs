@@ -0,0 +1,81 @@
+#![doc = include_str!("../README.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/alloy.jpg",
+    html_favicon_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/favicon.ico"
+)]
+#![warn(
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    unreachable_pub,
+    clippy::missing_const_for_fn,
+    rustdoc::all
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields};
+
+/// Derives `FromDynSolValues` and `FromDynSolValue` for a struct, mapping the
+/// values returned by a contract call to the struct's fields by position.
+///
+/// See the `alloy-dyn-contract` documentation for more details.
+#[proc_macro_derive(SolOutput)]
+pub fn derive_sol_output(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "SolOutput can only be derived for structs"));
+    };
+
+    let krate = quote!(::alloy_dyn_contract);
+    let private = quote!(#krate::private);
+
+    let len = data.fields.len();
+    let next = quote!(#krate::FromDynSolValue::from_dyn_sol_value(values.next().unwrap())?);
+    let construct = match &data.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!(Self { #(#names: #next,)* })
+        }
+        Fields::Unnamed(fields) => {
+            let nexts = fields.unnamed.iter().map(|_| &next);
+            quote!(Self(#(#nexts,)*))
+        }
+        Fields::Unit => quote!(Self),
+    };
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in &data.fields {
+        let ty = &field.ty;
+        where_clause.predicates.push(syn::parse_quote!(#ty: #krate::FromDynSolValue));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::FromDynSolValues for #name #ty_generics #where_clause {
+            #[allow(unused_mut, unused_variables)]
+            fn from_dyn_sol_values(
+                values: ::std::vec::Vec<#private::DynSolValue>,
+            ) -> #krate::Result<Self> {
+                let mut values = #private::expect_len(values, #len)?;
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+
+        impl #impl_generics #krate::FromDynSolValue for #name #ty_generics #where_clause {
+            fn from_dyn_sol_value(value: #private::DynSolValue) -> #krate::Result<Self> {
+                #krate::FromDynSolValues::from_dyn_sol_values(#private::expect_tuple(value)?)
+            }
+        }
+    })
+}
@@ -0,0 +1,127 @@
+use crate::{CallBuilder, ContractInstance, Error, Result};
+use alloy_json_abi::Function;
+use alloy_primitives::{Address, B256, U256};
+use alloy_providers::provider::TempProvider;
+use alloy_sol_types::SolCall;
+use futures_util::{stream, StreamExt, TryStreamExt};
+
+/// The maximum number of concurrent `getRoleMember` calls in
+/// [`ContractInstance::get_role_member`].
+const MAX_CONCURRENT_CALLS: usize = 16;
+
+mod interfaces {
+    #![allow(missing_docs, unreachable_pub)]
+
+    alloy_sol_types::sol! {
+        function owner() external view returns (address);
+        function getRoleMemberCount(bytes32 role) external view returns (uint256);
+        function getRoleMember(bytes32 role, uint256 index) external view returns (address);
+    }
+}
+
+impl<P: TempProvider + Clone> ContractInstance<P> {
+    /// Returns the owner of an OpenZeppelin `Ownable` contract, by calling its `owner()`
+    /// function.
+    ///
+    /// Fails with [`Error::FunctionNotFound`] if the contract's ABI has no `owner()` function.
+    pub async fn get_owner(&self) -> Result<Address> {
+        Ok(self.call_known(&interfaces::ownerCall {}).await?._0)
+    }
+
+    /// Returns all members of `role` in an OpenZeppelin `AccessControlEnumerable` contract, by
+    /// calling its `getRoleMemberCount(bytes32)` function, and then its
+    /// `getRoleMember(bytes32,uint256)` function for each member, with at most 16 calls in
+    /// flight at a time.
+    ///
+    /// Fails with [`Error::FunctionNotFound`] if the contract's ABI has either function missing.
+    /// A plain `AccessControl` contract cannot enumerate the members of a role.
+    pub async fn get_role_member(&self, role: B256) -> Result<Vec<Address>> {
+        // Check both functions before sending any request.
+        self.known_function::<interfaces::getRoleMemberCall>()?;
+        let count = self.call_known(&interfaces::getRoleMemberCountCall { role }).await?._0;
+        stream::iter(0..count.saturating_to::<usize>())
+            .map(|index| async move {
+                let call = interfaces::getRoleMemberCall { role, index: U256::from(index) };
+                Ok::<_, Error>(self.call_known(&call).await?._0)
+            })
+            .buffered(MAX_CONCURRENT_CALLS)
+            .try_collect()
+            .await
+    }
+
+    /// Returns the function of `C` in the contract's ABI, which must have the same signature.
+    fn known_function<C: SolCall>(&self) -> Result<&Function> {
+        self.get_from_selector(&C::SELECTOR.into())
+            .map_err(|_| Error::FunctionNotFound(C::SIGNATURE.to_string()))
+    }
+
+    /// Calls the function of `C` via `eth_call`, decoding the output as `C`'s return type.
+    async fn call_known<C: SolCall>(&self, call: &C) -> Result<C::Return> {
+        let function = self.known_function::<C>()?.clone();
+        let input = call.abi_encode().into();
+        let data =
+            CallBuilder::new(self.provider(), function, self.address(), input).call_raw().await?;
+        Ok(C::abi_decode_returns(&data, true)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::Bytes;
+    use alloy_sol_types::SolValue;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn access_control() {
+        let role = B256::with_last_byte(1);
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "eth_call");
+            let input: Bytes = serde_json::from_value(params[0]["input"].clone()).unwrap();
+            let output = match input[..4].try_into().unwrap() {
+                interfaces::ownerCall::SELECTOR => Address::with_last_byte(9).abi_encode(),
+                interfaces::getRoleMemberCountCall::SELECTOR => {
+                    assert_eq!(
+                        interfaces::getRoleMemberCountCall::abi_decode(&input, true).unwrap().role,
+                        role
+                    );
+                    U256::from(2).abi_encode()
+                }
+                interfaces::getRoleMemberCall::SELECTOR => {
+                    let index =
+                        interfaces::getRoleMemberCall::abi_decode(&input, true).unwrap().index;
+                    Address::with_last_byte(10 + index.to::<u8>()).abi_encode()
+                }
+                selector => unreachable!("{selector:?}"),
+            };
+            Ok(json!(Bytes::from(output)))
+        });
+        let abi = JsonAbi::parse([
+            "function owner() returns (address)",
+            "function getRoleMemberCount(bytes32 role) returns (uint256)",
+            "function getRoleMember(bytes32 role, uint256 index) returns (address)",
+        ])
+        .unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        assert_eq!(contract.get_owner().await.unwrap(), Address::with_last_byte(9));
+        assert_eq!(
+            contract.get_role_member(role).await.unwrap(),
+            [Address::with_last_byte(10), Address::with_last_byte(11)]
+        );
+
+        let contract = contract.apply_upgrade(
+            JsonAbi::parse(["function owner(uint256 id) returns (address)"]).unwrap(),
+        );
+        assert!(matches!(
+            contract.get_owner().await,
+            Err(Error::FunctionNotFound(signature)) if signature == "owner()"
+        ));
+        assert!(matches!(
+            contract.get_role_member(role).await,
+            Err(Error::FunctionNotFound(signature)) if signature == "getRoleMember(bytes32,uint256)"
+        ));
+    }
+}
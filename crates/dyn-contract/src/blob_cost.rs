@@ -0,0 +1,81 @@
+use crate::{ContractInstance, Result};
+use alloy_dyn_abi::DynSolValue;
+use alloy_eips::eip4844::calc_blob_fee_for_transaction;
+use alloy_providers::provider::TempProvider;
+use alloy_rpc_types::kzg::Blob;
+
+/// The estimated cost of a blob transaction, as returned by
+/// [`ContractInstance::estimate_blob_tx_cost`]. All costs are in wei.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlobTxCostBreakdown {
+    /// The cost of the transaction's execution, i.e. the estimated gas times the gas price.
+    pub execution_cost: u128,
+    /// The fee paid for the transaction's blobs.
+    pub blob_cost: u128,
+    /// The sum of the execution and blob costs.
+    pub total: u128,
+}
+
+impl<P: TempProvider + Clone> ContractInstance<P> {
+    /// Estimates the cost of calling the given function in a transaction carrying `blobs`,
+    /// included in a block with the given `excess_blob_gas`, e.g. to post data to an L2.
+    ///
+    /// The execution cost is the gas estimated by `eth_estimateGas` times the current gas price
+    /// returned by `eth_gasPrice`. The blob cost is computed as in
+    /// [`calc_blob_fee_for_transaction`], and only depends on the number of blobs. The costs
+    /// saturate at `u128::MAX`.
+    pub async fn estimate_blob_tx_cost(
+        &self,
+        function: &str,
+        args: &[DynSolValue],
+        blobs: &[Blob],
+        excess_blob_gas: u64,
+    ) -> Result<BlobTxCostBreakdown> {
+        let gas = self.function(function, args)?.estimate_gas().await?;
+        let gas_price = self.provider_ref().get_gas_price().await?;
+        let execution_cost = gas.saturating_mul(gas_price).saturating_to::<u128>();
+        let blob_cost = calc_blob_fee_for_transaction(blobs.len(), excess_blob_gas);
+        Ok(BlobTxCostBreakdown {
+            execution_cost,
+            blob_cost,
+            total: execution_cost.saturating_add(blob_cost),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_eips::eip4844::{calc_blob_gasprice, DATA_GAS_PER_BLOB};
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::{Address, U256};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn estimate_blob_tx_cost() {
+        let provider = MockTransport::provider(|method, _| match method {
+            "eth_estimateGas" => Ok(json!(U256::from(50_000))),
+            "eth_gasPrice" => Ok(json!(U256::from(20))),
+            _ => unreachable!("{method}"),
+        });
+        let abi = JsonAbi::parse(["function postBatch(bytes32 root)"]).unwrap();
+        let inbox = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let blobs = vec![Blob::ZERO; 2];
+        let excess_blob_gas = 10 * DATA_GAS_PER_BLOB;
+        let args = [DynSolValue::FixedBytes(Default::default(), 32)];
+        let cost =
+            inbox.estimate_blob_tx_cost("postBatch", &args, &blobs, excess_blob_gas).await.unwrap();
+
+        let blob_cost = 2 * DATA_GAS_PER_BLOB as u128 * calc_blob_gasprice(excess_blob_gas);
+        assert_eq!(
+            cost,
+            BlobTxCostBreakdown {
+                execution_cost: 1_000_000,
+                blob_cost,
+                total: 1_000_000 + blob_cost
+            }
+        );
+    }
+}
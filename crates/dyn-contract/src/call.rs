@@ -1,13 +1,22 @@
-use crate::Result;
-use alloy_dyn_abi::{DynSolValue, FunctionExt};
+use crate::{
+    CallDecoder, Error, FeeOracle, FromDynSolValues, PendingTransaction, Result, RetryPolicy,
+};
+use alloy_dyn_abi::DynSolValue;
 use alloy_json_abi::Function;
-use alloy_primitives::{Address, Bytes, U256, U64};
+use alloy_json_rpc::{Id, Request, RequestMeta, SerializedRequest};
+use alloy_primitives::{Address, Bytes, TxHash, U256, U64, U8};
 use alloy_providers::provider::TempProvider;
-use alloy_rpc_types::{state::StateOverride, BlockId, CallInput, CallRequest};
+use alloy_rpc_types::{
+    state::StateOverride, BlockId, BlockNumberOrTag, CallInput, CallRequest, SignedAuthorization,
+};
+use alloy_transport::TransportError;
 use std::{
     future::{Future, IntoFuture},
     pin::Pin,
+    sync::Arc,
+    time::Duration,
 };
+use tokio::sync::OnceCell;
 
 /// A builder for sending a transaction via. `eth_sendTransaction`, or calling a function via
 /// `eth_call`.
@@ -27,12 +36,37 @@ pub struct CallBuilder<P> {
     provider: P,
     // todo: only used to decode - should it be some type D to dedupe with `sol!` contracts?
     function: Function,
+    oracle: Option<Arc<dyn FeeOracle<P>>>,
+    decoder: Option<Arc<dyn CallDecoder>>,
+    retry: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    /// The chain ID used if none is set on the request, fetched on the first send and shared with
+    /// the [`ContractInstance`](crate::ContractInstance) that created the builder.
+    chain_id: Arc<OnceCell<u64>>,
 }
 
 impl<P> CallBuilder<P> {
-    pub(crate) fn new(provider: P, function: Function, input: Bytes) -> Self {
-        let request = CallRequest { input: CallInput::new(input), ..Default::default() };
-        Self { request, function, provider, block: None, state: None }
+    pub(crate) fn new(provider: P, function: Function, to: Address, input: Bytes) -> Self {
+        let request =
+            CallRequest { to: Some(to), input: CallInput::new(input), ..Default::default() };
+        Self {
+            request,
+            function,
+            provider,
+            block: None,
+            state: None,
+            oracle: None,
+            decoder: None,
+            retry: None,
+            timeout: None,
+            chain_id: Arc::default(),
+        }
+    }
+
+    /// Shares the cached chain ID of a [`ContractInstance`](crate::ContractInstance).
+    pub(crate) fn with_chain_id_cache(mut self, chain_id: Arc<OnceCell<u64>>) -> Self {
+        self.chain_id = chain_id;
+        self
     }
 
     /// Sets the `from` field in the transaction to the provided value
@@ -41,6 +75,18 @@ impl<P> CallBuilder<P> {
         self
     }
 
+    /// Sets the `to` field in the transaction to the provided value, overriding the address of
+    /// the contract the builder was created for, e.g. to call a library with the same interface.
+    ///
+    /// # Note
+    ///
+    /// The output is still decoded as the output of the builder's function, which is intentional
+    /// but only meaningful if the contract at `to` implements that function.
+    pub const fn to(mut self, to: Address) -> Self {
+        self.request.to = Some(to);
+        self
+    }
+
     /// Uses a Legacy transaction instead of an EIP-1559 one to execute the call
     pub fn legacy(self) -> Self {
         todo!()
@@ -72,12 +118,41 @@ impl<P> CallBuilder<P> {
         self
     }
 
-    /// Sets the `block` field for sending the tx to the chain
-    pub const fn block(mut self, block: BlockId) -> Self {
-        self.block = Some(block);
+    /// Sets the `chain_id` field in the transaction to the provided value, for EIP-155 replay
+    /// protection.
+    ///
+    /// If not set, the chain ID is fetched via `eth_chainId` when the transaction is
+    /// [sent](Self::send).
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.request.chain_id = Some(U64::from(chain_id));
+        self
+    }
+
+    /// Sets the block at which calls and gas estimates are executed, either by number or tag, or
+    /// by hash as specified in [EIP-1898](https://eips.ethereum.org/EIPS/eip-1898).
+    ///
+    /// Defaults to the `latest` block.
+    pub const fn block_id(mut self, id: BlockId) -> Self {
+        self.block = Some(id);
         self
     }
 
+    /// Sets the `block` field for sending the tx to the chain. Alias for
+    /// [`block_id`](Self::block_id).
+    pub const fn block(self, block: BlockId) -> Self {
+        self.block_id(block)
+    }
+
+    /// Sets the `block` field to the `pending` block, so that calls and gas estimates account for
+    /// the transactions in the node's mempool that are not yet mined.
+    ///
+    /// # Note
+    ///
+    /// The pending state is specific to each node, and may change between two requests.
+    pub const fn pending(self) -> Self {
+        self.block_id(BlockId::Number(BlockNumberOrTag::Pending))
+    }
+
     /// Sets the [state override set](https://geth.ethereum.org/docs/rpc/ns-eth#3-object---state-override-set).
     ///
     /// # Note
@@ -88,10 +163,116 @@ impl<P> CallBuilder<P> {
         self
     }
 
+    /// Sets the [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorization list, making the
+    /// transaction a type 4 transaction. The list is also passed to `eth_call` and
+    /// `eth_estimateGas`, but not all clients support it there.
+    ///
+    /// Calling or sending fails with [`Error::IncompatibleTransaction`] if the transaction is also
+    /// configured as a legacy or EIP-4844 transaction.
+    pub fn authorization_list(mut self, auths: Vec<SignedAuthorization>) -> Self {
+        self.request.authorization_list = Some(auths);
+        self.request.transaction_type = Some(U8::from(4));
+        self
+    }
+
+    /// Sets the [`FeeOracle`] used to fill in the EIP-1559 fee fields when the transaction is
+    /// [sent](Self::send).
+    ///
+    /// Fees that were set explicitly, e.g. with [`Self::gas_price`], take precedence over the
+    /// oracle's estimate.
+    pub fn with_oracle(mut self, oracle: Arc<dyn FeeOracle<P>>) -> Self {
+        self.oracle = Some(oracle);
+        self
+    }
+
+    /// Sets the [`CallDecoder`] used to decode the output of [`Self::call`], instead of the
+    /// function's output types.
+    pub fn with_decoder(mut self, decoder: impl CallDecoder + 'static) -> Self {
+        self.decoder = Some(Arc::new(decoder));
+        self
+    }
+
+    /// Sets the [`RetryPolicy`] for `eth_call` requests that fail with a transient error. The
+    /// identical request is sent again, and the last error is returned if all retries fail.
+    ///
+    /// Without a policy, calls are not retried. Transactions are never retried.
+    pub const fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Sets a timeout for each RPC request made by this call, independent of the provider's
+    /// configuration. A request that does not complete in time fails with [`Error::Timeout`].
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Transforms the ABI encoded calldata, including the selector, with `f`, e.g. to append the
+    /// sender address for an [ERC-2771](https://eips.ethereum.org/EIPS/eip-2771) trusted
+    /// forwarder.
+    ///
+    /// Only the request is changed: the output of [`call`](Self::call) is still decoded as the
+    /// outputs of the original function.
+    pub fn map_calldata(mut self, f: impl FnOnce(Bytes) -> Bytes) -> Self {
+        let input = std::mem::take(&mut self.request.input).into_input().unwrap_or_default();
+        self.request.input = CallInput::new(f(input));
+        self
+    }
+
     /// Returns the underlying transaction's ABI encoded data
     pub fn calldata(&self) -> Option<&Bytes> {
         self.request.input.input()
     }
+
+    /// Returns the transaction request, checking that its settings are compatible.
+    pub(crate) fn request(&self) -> Result<CallRequest> {
+        let request = &self.request;
+        if request.authorization_list.is_some() {
+            if request.transaction_type != Some(U8::from(4)) {
+                return Err(Error::IncompatibleTransaction(
+                    "an authorization list requires a type 4 transaction",
+                ));
+            }
+            if request.gas_price.is_some() {
+                return Err(Error::IncompatibleTransaction(
+                    "an authorization list cannot be combined with a legacy gas price",
+                ));
+            }
+            if request.max_fee_per_blob_gas.is_some() || request.blob_versioned_hashes.is_some() {
+                return Err(Error::IncompatibleTransaction(
+                    "an authorization list cannot be combined with blobs",
+                ));
+            }
+        }
+        Ok(request.clone())
+    }
+
+    /// Returns the `eth_call` request that [`CallBuilder::call`] would send, serialized as it
+    /// would be sent over the wire, e.g. to log or reproduce it.
+    ///
+    /// The request ID is always `0`, since the actual ID is assigned by the client when the
+    /// request is sent.
+    pub fn serialize_request(&self) -> Result<SerializedRequest> {
+        let meta = RequestMeta { method: "eth_call", id: Id::Number(0) };
+        let tx = self.request()?;
+        let block = self.block.unwrap_or_default();
+        match &self.state {
+            Some(state) => Request { meta, params: (tx, block, state) }.serialize(),
+            None => Request { meta, params: (tx, block) }.serialize(),
+        }
+        .map_err(|err| TransportError::ser_err(err).into())
+    }
+
+    /// Awaits the given RPC request, failing if it does not complete within the timeout.
+    async fn dispatch<T>(&self, request: impl Future<Output = Result<T>>) -> Result<T> {
+        match self.timeout {
+            Some(timeout) => {
+                tokio::time::timeout(timeout, request).await.map_err(|_| Error::Timeout(timeout))?
+            }
+            None => request.await,
+        }
+    }
 }
 
 impl<P> CallBuilder<P>
@@ -100,7 +281,8 @@ where
 {
     /// Returns the estimated gas cost for the underlying transaction to be executed
     pub async fn estimate_gas(&self) -> Result<U256> {
-        self.provider.estimate_gas(self.request.clone(), self.block).await.map_err(Into::into)
+        let request = self.request()?;
+        self.dispatch(async { Ok(self.provider.estimate_gas(request, self.block).await?) }).await
     }
 
     /// Queries the blockchain via an `eth_call` for the provided transaction.
@@ -115,28 +297,93 @@ where
     ///
     /// This function _does not_ send a transaction from your account.
     pub async fn call(&self) -> Result<Vec<DynSolValue>> {
-        let bytes = self.call_raw().await?;
-
-        // decode output
-        let data = self.function.abi_decode_output(&bytes, true)?;
+        self.call_with(&self.provider).await
+    }
 
-        Ok(data)
+    /// Queries the blockchain via an `eth_call` for the provided transaction, converting the
+    /// decoded output into `T`.
+    ///
+    /// `T` is usually a struct deriving [`SolOutput`](crate::SolOutput), whose fields are
+    /// mapped to the function's outputs by position.
+    pub async fn call_into<T: FromDynSolValues>(&self) -> Result<T> {
+        T::from_dyn_sol_values(self.call().await?)
     }
 
     /// Queries the blockchain via an `eth_call` for the provided transaction without decoding
     /// the output.
+    ///
+    /// Transient failures are retried according to the [retry policy](Self::with_retry).
     pub async fn call_raw(&self) -> Result<Bytes> {
-        if let Some(state) = &self.state {
-            self.provider.call_with_overrides(self.request.clone(), self.block, state.clone()).await
-        } else {
-            self.provider.call(self.request.clone(), self.block).await
+        self.call_raw_with(&self.provider).await
+    }
+
+    /// Broadcasts the provided transaction via `eth_sendTransaction`, returning a
+    /// [`PendingTransaction`] that can be awaited for its receipt.
+    ///
+    /// The transaction is signed by the node, so the `from` account must be unlocked on it. If no
+    /// [chain ID](Self::chain_id) was set, it is fetched via `eth_chainId` first, and cached for
+    /// all transactions sent with the same [`ContractInstance`](crate::ContractInstance).
+    pub async fn send(self) -> Result<PendingTransaction<P>> {
+        let mut request = self.request()?;
+        if request.chain_id.is_none() {
+            let chain_id = self
+                .chain_id
+                .get_or_try_init(|| {
+                    self.dispatch(async { Ok(self.provider.get_chain_id().await?.to::<u64>()) })
+                })
+                .await?;
+            request.chain_id = Some(U64::from(*chain_id));
         }
-        .map_err(Into::into)
+        if let Some(oracle) = &self.oracle {
+            if request.gas_price.is_none() && request.max_fee_per_gas.is_none() {
+                let fees = self.dispatch(oracle.estimate(&self.provider)).await?;
+                request.max_fee_per_gas = Some(fees.max_fee_per_gas);
+                request.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas);
+            }
+        }
+        let tx_hash: TxHash = self
+            .dispatch(async {
+                Ok(self.provider.raw_request("eth_sendTransaction", (request,)).await?)
+            })
+            .await?;
+        Ok(PendingTransaction::new(self.provider, tx_hash))
     }
+}
 
-    /// Signs and broadcasts the provided transaction
-    pub async fn send(&self) -> Result<()> {
-        todo!()
+impl<P> CallBuilder<P> {
+    /// Queries the blockchain via an `eth_call` for the provided transaction, like
+    /// [`CallBuilder::call`], but using the given provider instead of the bound one, e.g. to fail
+    /// over to another endpoint.
+    ///
+    /// All other settings of the builder, including the [retry policy](Self::with_retry) and
+    /// [timeout](Self::timeout), apply as usual.
+    pub async fn call_with<Q: TempProvider>(&self, provider: &Q) -> Result<Vec<DynSolValue>> {
+        let bytes = self.call_raw_with(provider).await?;
+
+        // decode output
+        match &self.decoder {
+            Some(decoder) => decoder.decode_output(&bytes),
+            None => self.function.decode_output(&bytes),
+        }
+    }
+
+    async fn call_raw_with<Q: TempProvider>(&self, provider: &Q) -> Result<Bytes> {
+        let request = self.request()?;
+        let attempt = || {
+            self.dispatch(async {
+                if let Some(state) = &self.state {
+                    let state = state.clone();
+                    provider.call_with_overrides(request.clone(), self.block, state).await
+                } else {
+                    provider.call(request.clone(), self.block).await
+                }
+                .map_err(Into::into)
+            })
+        };
+        match &self.retry {
+            Some(policy) => policy.run(attempt, Error::is_transient).await,
+            None => attempt().await,
+        }
     }
 }
 
@@ -167,6 +414,258 @@ impl<P> std::fmt::Debug for CallBuilder<P> {
             .field("function", &self.function)
             .field("block", &self.block)
             .field("state", &self.state)
+            .field("timeout", &self.timeout)
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, FeeEstimate, Interface};
+    use alloy_json_abi::JsonAbi;
+    use alloy_json_rpc::{RequestPacket, ResponsePacket};
+    use alloy_primitives::B256;
+    use alloy_providers::provider::Provider;
+    use alloy_rpc_types::Authorization;
+    use alloy_transport::TransportFut;
+    use serde_json::{json, Value};
+    use std::{
+        sync::Mutex,
+        task::{Context, Poll},
+    };
+
+    #[tokio::test]
+    async fn authorization_list() {
+        let sent = Arc::new(Mutex::new(Value::Null));
+        let provider = MockTransport::provider({
+            let sent = sent.clone();
+            move |method, params| match method {
+                "eth_sendTransaction" => {
+                    *sent.lock().unwrap() = params[0].clone();
+                    Ok(json!(B256::with_last_byte(1)))
+                }
+                _ => unreachable!("{method}"),
+            }
+        });
+        let abi = JsonAbi::parse(["function bump()"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let auth = SignedAuthorization {
+            inner: Authorization {
+                chain_id: U256::from(1),
+                address: Address::with_last_byte(2),
+                nonce: U64::ZERO,
+            },
+            ..Default::default()
+        };
+        let call =
+            contract.function("bump", &[]).unwrap().chain_id(1).authorization_list(vec![auth]);
+        call.clone().send().await.unwrap();
+        {
+            let sent = sent.lock().unwrap();
+            assert_eq!(sent["type"], json!("0x4"));
+            assert_eq!(sent["authorizationList"], json!([auth]));
+        }
+
+        let mut blob_call = call.clone();
+        blob_call.request.blob_versioned_hashes = Some(vec![B256::ZERO]);
+        assert!(matches!(blob_call.send().await, Err(Error::IncompatibleTransaction(_))));
+
+        let mut legacy_call = call;
+        legacy_call.request.gas_price = Some(U256::from(1));
+        assert!(matches!(legacy_call.call_raw().await, Err(Error::IncompatibleTransaction(_))));
+    }
+
+    #[tokio::test]
+    async fn to() {
+        let library = Address::with_last_byte(2);
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "eth_call");
+            assert_eq!(params[0]["to"], json!(library));
+            Ok(json!(Bytes::from(U256::from(7).to_be_bytes_vec())))
+        });
+        let abi = JsonAbi::parse(["function get() returns (uint256)"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let call = contract.function("get", &[]).unwrap().to(library);
+        assert_eq!(call.call().await.unwrap(), [DynSolValue::Uint(U256::from(7), 256)]);
+    }
+
+    #[tokio::test]
+    async fn call_with() {
+        let abi = JsonAbi::parse(["function get() returns (uint256)"]).unwrap();
+        let down = MockTransport::provider(|_, _| {
+            Err(alloy_transport::TransportErrorKind::custom_str("connection refused"))
+        });
+        let up = MockTransport::provider(|method, _| {
+            assert_eq!(method, "eth_call");
+            Ok(json!(Bytes::from(U256::from(7).to_be_bytes_vec())))
+        });
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), down);
+
+        let call = contract.function("get", &[]).unwrap();
+        assert!(matches!(call.call().await, Err(Error::TransportError(_))));
+        assert_eq!(call.call_with(&up).await.unwrap(), [DynSolValue::Uint(U256::from(7), 256)]);
+    }
+
+    /// A transport whose requests never complete.
+    #[derive(Clone, Debug)]
+    struct HangingTransport;
+
+    impl tower::Service<RequestPacket> for HangingTransport {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = TransportFut<'static>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: RequestPacket) -> Self::Future {
+            Box::pin(std::future::pending())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn timeout() {
+        let abi = JsonAbi::parse(["function bump()"]).unwrap();
+        let provider = Arc::new(Provider::new(HangingTransport));
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+        let call = contract.function("bump", &[]).unwrap().timeout(Duration::from_secs(5));
+
+        let err = call.call_raw().await.unwrap_err();
+        assert!(matches!(err, Error::Timeout(timeout) if timeout == Duration::from_secs(5)));
+        assert!(matches!(call.estimate_gas().await, Err(Error::Timeout(_))));
+        assert!(matches!(call.clone().send().await, Err(Error::Timeout(_))));
+
+        // Fee estimation is subject to the timeout too.
+        struct StalledOracle;
+
+        #[async_trait::async_trait]
+        impl<P: Send + Sync> FeeOracle<P> for StalledOracle {
+            async fn estimate(&self, _provider: &P) -> Result<FeeEstimate> {
+                std::future::pending().await
+            }
+        }
+
+        let call = call.chain_id(1).with_oracle(Arc::new(StalledOracle));
+        assert!(matches!(call.send().await, Err(Error::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn chain_id() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let provider = MockTransport::provider({
+            let sent = sent.clone();
+            move |method, params| match method {
+                "eth_chainId" => Ok(json!(U64::from(10))),
+                "eth_sendTransaction" => {
+                    sent.lock().unwrap().push(params[0].clone());
+                    Ok(json!(B256::with_last_byte(1)))
+                }
+                _ => unreachable!("{method}"),
+            }
+        });
+        let abi = JsonAbi::parse(["function bump()"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        contract.function("bump", &[]).unwrap().send().await.unwrap();
+        contract.function("bump", &[]).unwrap().chain_id(5).send().await.unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[0]["chainId"], json!("0xa"));
+        assert_eq!(sent[1]["chainId"], json!("0x5"));
+    }
+
+    #[tokio::test]
+    async fn pending() {
+        let blocks = Arc::new(Mutex::new(Vec::new()));
+        let provider = MockTransport::provider({
+            let blocks = blocks.clone();
+            move |method, params| {
+                assert_eq!(method, "eth_call");
+                blocks.lock().unwrap().push(params[1].clone());
+                Ok(json!(Bytes::new()))
+            }
+        });
+        let abi = JsonAbi::parse(["function bump()"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let call = contract.function("bump", &[]).unwrap();
+        call.call_raw().await.unwrap();
+        call.pending().call_raw().await.unwrap();
+        assert_eq!(*blocks.lock().unwrap(), [json!("latest"), json!("pending")]);
+    }
+
+    #[tokio::test]
+    async fn map_calldata() {
+        let sender = Address::with_last_byte(9);
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "eth_call");
+            let input: Bytes = serde_json::from_value(params[0]["input"].clone()).unwrap();
+            assert_eq!(input[4..], sender[..]);
+            Ok(json!(Bytes::from(U256::from(7).to_be_bytes_vec())))
+        });
+        let abi = JsonAbi::parse(["function count() returns (uint256)"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let call = contract
+            .function("count", &[])
+            .unwrap()
+            .map_calldata(|input| [&input[..], &sender[..]].concat().into());
+        assert_eq!(call.calldata().unwrap().len(), 24);
+        assert_eq!(call.call().await.unwrap(), [DynSolValue::Uint(U256::from(7), 256)]);
+    }
+
+    #[tokio::test]
+    async fn block_id() {
+        let blocks = Arc::new(Mutex::new(Vec::new()));
+        let provider = MockTransport::provider({
+            let blocks = blocks.clone();
+            move |_, params| {
+                blocks.lock().unwrap().push(params[1].clone());
+                Ok(json!(Bytes::new()))
+            }
+        });
+        let abi = JsonAbi::parse(["function bump()"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let call = contract.function("bump", &[]).unwrap();
+        let hash = B256::with_last_byte(7);
+        call.clone().block_id(BlockId::from(5u64)).call_raw().await.unwrap();
+        call.block_id(BlockId::from(hash)).call_raw().await.unwrap();
+        assert_eq!(*blocks.lock().unwrap(), [json!("0x5"), json!({ "blockHash": hash })]);
+    }
+
+    #[tokio::test]
+    async fn serialize_request() {
+        let sent = Arc::new(Mutex::new(Value::Null));
+        let provider = MockTransport::provider({
+            let sent = sent.clone();
+            move |method, params| {
+                assert_eq!(method, "eth_call");
+                *sent.lock().unwrap() = params.clone();
+                Ok(json!(Bytes::from(U256::from(7).to_be_bytes_vec())))
+            }
+        });
+        let abi = JsonAbi::parse(["function get() returns (uint256)"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let call =
+            contract.function("get", &[]).unwrap().from(Address::with_last_byte(2)).pending();
+        let request = call.serialize_request().unwrap();
+        assert_eq!(request.method(), "eth_call");
+        call.call().await.unwrap();
+        let params: Value = serde_json::from_str(request.params().unwrap().get()).unwrap();
+        assert_eq!(params, *sent.lock().unwrap());
+        assert_eq!(params[1], "pending");
+
+        let call = call.state(StateOverride::default());
+        let request = call.serialize_request().unwrap();
+        call.call().await.unwrap();
+        let params: Value = serde_json::from_str(request.params().unwrap().get()).unwrap();
+        assert_eq!(params, *sent.lock().unwrap());
+        assert_eq!(params.as_array().unwrap().len(), 3);
+    }
+}
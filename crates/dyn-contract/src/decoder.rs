@@ -0,0 +1,49 @@
+use crate::Result;
+use alloy_dyn_abi::{DynSolValue, FunctionExt};
+use alloy_json_abi::Function;
+
+/// A strategy for decoding the output of an `eth_call`.
+///
+/// By default, [`CallBuilder::call`](crate::CallBuilder::call) decodes the output against the
+/// called function's output types. A custom decoder can be set with
+/// [`CallBuilder::with_decoder`](crate::CallBuilder::with_decoder), e.g. to decode against a
+/// different ABI when calling through a proxy.
+pub trait CallDecoder: Send + Sync {
+    /// Decodes the raw output of a call.
+    fn decode_output(&self, data: &[u8]) -> Result<Vec<DynSolValue>>;
+}
+
+/// Decodes the output against the function's output types.
+impl CallDecoder for Function {
+    fn decode_output(&self, data: &[u8]) -> Result<Vec<DynSolValue>> {
+        self.abi_decode_output(data, true).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock::MockTransport, Interface};
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_json_abi::{Function, JsonAbi};
+    use alloy_primitives::{Address, Bytes};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn with_decoder() {
+        let owner = Address::with_last_byte(2);
+        let provider = MockTransport::provider(move |method, _| {
+            assert_eq!(method, "eth_call");
+            Ok(json!(Bytes::from(owner.into_word().to_vec())))
+        });
+        // The proxy's ABI declares a different output type than the implementation.
+        let abi = JsonAbi::parse(["function implementation() returns (uint256)"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let call = contract.function("implementation", &[]).unwrap();
+        assert!(matches!(call.call().await.unwrap()[..], [DynSolValue::Uint(..)]));
+
+        let decoder = Function::parse("function implementation() returns (address)").unwrap();
+        let call = call.with_decoder(decoder);
+        assert_eq!(call.call().await.unwrap(), [DynSolValue::Address(owner)]);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{
+    ContractInstance, Error, Interface, PendingTransaction, Result, DEFAULT_POLL_INTERVAL,
+};
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::{Constructor, StateMutability};
+use alloy_primitives::{Address, Bytes, TxHash, U256, U64};
+use alloy_providers::provider::TempProvider;
+use alloy_rpc_types::{CallInput, CallRequest};
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+    time::Duration,
+};
+
+/// A builder for deploying a contract via `eth_sendTransaction`, created with
+/// [`Interface::deploy`].
+///
+/// The builder can be `.await`ed directly which is equivalent to invoking
+/// [`DeployBuilder::deploy`].
+#[derive(Clone)]
+pub struct DeployBuilder<P> {
+    provider: P,
+    interface: Interface,
+    request: CallRequest,
+    confirmations: u64,
+    timeout: Option<Duration>,
+    poll_interval: Duration,
+}
+
+impl Interface {
+    /// Returns a builder for deploying a contract with this interface, from the given creation
+    /// bytecode and constructor arguments.
+    ///
+    /// If the ABI has no constructor, `args` must be empty.
+    pub fn deploy<P>(
+        self,
+        provider: P,
+        bytecode: Bytes,
+        args: &[DynSolValue],
+    ) -> Result<DeployBuilder<P>> {
+        let constructor = self.constructor().cloned().unwrap_or(Constructor {
+            inputs: Vec::new(),
+            state_mutability: StateMutability::NonPayable,
+        });
+        let mut input = bytecode.to_vec();
+        input.extend(constructor.abi_encode_input(args)?);
+
+        let request = CallRequest { input: CallInput::new(input.into()), ..Default::default() };
+        Ok(DeployBuilder {
+            provider,
+            interface: self,
+            request,
+            confirmations: 1,
+            timeout: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        })
+    }
+}
+
+impl<P> DeployBuilder<P> {
+    /// Sets the `from` field in the transaction to the provided value
+    pub fn from(mut self, from: Address) -> Self {
+        self.request = self.request.from(from);
+        self
+    }
+
+    /// Sets the `gas` field in the transaction to the provided value
+    pub fn gas(mut self, gas: U256) -> Self {
+        self.request = self.request.gas(gas);
+        self
+    }
+
+    /// Sets the `value` field in the transaction to the provided value
+    pub fn value(mut self, value: U256) -> Self {
+        self.request = self.request.value(value);
+        self
+    }
+
+    /// Sets the `nonce` field in the transaction to the provided value
+    pub fn nonce(mut self, nonce: U64) -> Self {
+        self.request = self.request.nonce(nonce);
+        self
+    }
+
+    /// Sets how long to wait for the contract creation transaction to be mined and confirmed
+    /// before failing with [`Error::DeployTimeout`]. There is no timeout by default.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of confirmations to wait for before returning the deployed contract.
+    /// Defaults to `1`.
+    ///
+    /// See [`PendingTransaction::with_confirmations`].
+    pub const fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets the interval at which the receipt of the contract creation transaction is polled.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Returns the contract creation transaction's data: the bytecode followed by the ABI
+    /// encoded constructor arguments.
+    pub fn calldata(&self) -> Option<&Bytes> {
+        self.request.input.input()
+    }
+}
+
+impl<P: TempProvider + Clone> DeployBuilder<P> {
+    /// Broadcasts the contract creation transaction via `eth_sendTransaction`, and waits for it
+    /// to be mined and confirmed, returning an instance of the deployed contract.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::DeployTimeout`], carrying the transaction hash, if the transaction is
+    /// not confirmed within the [timeout](Self::with_timeout). Fails with
+    /// [`Error::TransactionReverted`] or [`Error::ContractNotDeployed`] if the transaction did
+    /// not create a contract.
+    pub async fn deploy(self) -> Result<ContractInstance<P>> {
+        let tx_hash: TxHash =
+            self.provider.raw_request("eth_sendTransaction", (&self.request,)).await?;
+        let receipt = PendingTransaction::new(self.provider.clone(), tx_hash)
+            .with_confirmations(self.confirmations)
+            .with_poll_interval(self.poll_interval)
+            .get_receipt();
+        let receipt = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, receipt)
+                .await
+                .map_err(|_| Error::DeployTimeout(tx_hash))??,
+            None => receipt.await?,
+        };
+
+        if receipt.status_code == Some(U64::ZERO) {
+            return Err(Error::TransactionReverted { revert_data: None });
+        }
+        let address = receipt
+            .contract_address
+            .ok_or(Error::ContractNotDeployed { address: None, tx_hash: Some(tx_hash) })?;
+        Ok(ContractInstance::new(address, self.provider, self.interface))
+    }
+}
+
+/// [`DeployBuilder`] can be turned into a [`Future`] automatically with `.await`.
+///
+/// Defaults to calling [`DeployBuilder::deploy`].
+impl<P> IntoFuture for DeployBuilder<P>
+where
+    P: TempProvider + Clone + 'static,
+{
+    type Output = Result<ContractInstance<P>>;
+
+    #[cfg(target_arch = "wasm32")]
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output>>>;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.deploy())
+    }
+}
+
+impl<P> std::fmt::Debug for DeployBuilder<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeployBuilder")
+            .field("confirmations", &self.confirmations)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::B256;
+    use alloy_rpc_types::{Transaction, TransactionReceipt};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    fn abi() -> JsonAbi {
+        JsonAbi::parse(["constructor(uint256 supply)"]).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn deploy() {
+        let tx_hash = B256::with_last_byte(1);
+        let address = Address::with_last_byte(2);
+        let sent = Arc::new(Mutex::new(Value::Null));
+        let provider = MockTransport::provider({
+            let sent = sent.clone();
+            move |method, params| match method {
+                "eth_sendTransaction" => {
+                    *sent.lock().unwrap() = params[0].clone();
+                    Ok(json!(tx_hash))
+                }
+                "eth_getTransactionReceipt" => Ok(serde_json::to_value(TransactionReceipt {
+                    block_number: Some(U256::from(1)),
+                    contract_address: Some(address),
+                    status_code: Some(U64::from(1)),
+                    ..Default::default()
+                })
+                .unwrap()),
+                "eth_blockNumber" => Ok(json!(U64::from(2))),
+                _ => unreachable!("{method}"),
+            }
+        });
+
+        let args = [DynSolValue::Uint(U256::from(1000), 256)];
+        let builder =
+            Interface::from(abi()).deploy(provider, Bytes::from_static(&[0xfe]), &args).unwrap();
+        let mut calldata = vec![0xfe];
+        calldata.extend(U256::from(1000).to_be_bytes::<32>());
+        assert_eq!(builder.calldata().unwrap()[..], calldata);
+
+        let contract = builder.with_confirmations(2).await.unwrap();
+        assert_eq!(contract.address(), address);
+        assert_eq!(sent.lock().unwrap()["to"], Value::Null);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn deploy_timeout() {
+        let tx_hash = B256::with_last_byte(1);
+        let provider = MockTransport::provider(move |method, _| match method {
+            "eth_sendTransaction" => Ok(json!(tx_hash)),
+            "eth_getTransactionReceipt" => Ok(Value::Null),
+            "eth_getTransactionByHash" => {
+                Ok(serde_json::to_value(Transaction { hash: tx_hash, ..Default::default() })
+                    .unwrap())
+            }
+            _ => unreachable!("{method}"),
+        });
+
+        let err = Interface::from(abi())
+            .deploy(provider, Bytes::new(), &[DynSolValue::Uint(U256::from(1), 256)])
+            .unwrap()
+            .with_timeout(Duration::from_secs(60))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::DeployTimeout(hash) if hash == tx_hash));
+    }
+
+    #[test]
+    fn deploy_without_constructor() {
+        let interface = Interface::from(JsonAbi::new());
+        assert!(interface.clone().deploy((), Bytes::new(), &[]).is_ok());
+        assert!(interface.deploy((), Bytes::new(), &[DynSolValue::Bool(true)]).is_err());
+    }
+}
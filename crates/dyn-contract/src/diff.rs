@@ -0,0 +1,283 @@
+use crate::Interface;
+use alloy_json_abi::{Event, Function};
+use alloy_primitives::{Selector, B256};
+use std::collections::BTreeMap;
+
+/// The differences between the items of one kind, e.g. functions, of two [`Interface`]s, keyed by
+/// selector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemDiff<S, T> {
+    /// Items that only exist in the new interface.
+    pub added: BTreeMap<S, T>,
+    /// Items that only exist in the old interface.
+    pub removed: BTreeMap<S, T>,
+    /// Items with the same selector in both interfaces whose definition changed in an ABI-relevant
+    /// way, as `(old, new)` pairs.
+    pub changed: BTreeMap<S, (T, T)>,
+}
+
+impl<S, T> Default for ItemDiff<S, T> {
+    fn default() -> Self {
+        Self { added: BTreeMap::new(), removed: BTreeMap::new(), changed: BTreeMap::new() }
+    }
+}
+
+impl<S, T> ItemDiff<S, T> {
+    /// Returns `true` if no items were added, removed or changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// The differences between two [`Interface`]s, as computed by [`Interface::diff`].
+///
+/// Items are matched by selector, so changing the name or any input type of an item shows up as
+/// one removal and one addition. Renaming parameters is not considered a change.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InterfaceDiff {
+    /// The differences between the functions. A function is changed if its output types or state
+    /// mutability changed.
+    pub functions: ItemDiff<Selector, Function>,
+    /// The differences between the events. An event is changed if it became (non-)anonymous or
+    /// any of its parameters became (non-)indexed.
+    pub events: ItemDiff<B256, Event>,
+    /// The differences between the errors. Errors are fully identified by their selector, so they
+    /// are never changed.
+    pub errors: ItemDiff<Selector, alloy_json_abi::Error>,
+}
+
+impl InterfaceDiff {
+    /// Returns `true` if the two interfaces are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.events.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// The differences between two [`Interface`]s as item signatures, as computed by
+/// [`Interface::compare_with`].
+///
+/// Added and removed items are listed by their signature, e.g. `transfer(address,uint256)`.
+/// Changed items are listed as `(name, old, new)` with their full human-readable signatures.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AbiDiff {
+    /// Functions that only exist in the new interface.
+    pub added_functions: Vec<String>,
+    /// Functions that only exist in the old interface.
+    pub removed_functions: Vec<String>,
+    /// Functions whose output types or state mutability changed.
+    pub changed_functions: Vec<(String, String, String)>,
+    /// Events that only exist in the new interface.
+    pub added_events: Vec<String>,
+    /// Events that only exist in the old interface.
+    pub removed_events: Vec<String>,
+    /// Events that became (non-)anonymous or whose parameters became (non-)indexed.
+    pub changed_events: Vec<(String, String, String)>,
+    /// Errors that only exist in the new interface.
+    pub added_errors: Vec<String>,
+    /// Errors that only exist in the old interface.
+    pub removed_errors: Vec<String>,
+    /// Always empty, since errors are fully identified by their selector.
+    pub changed_errors: Vec<(String, String, String)>,
+}
+
+impl AbiDiff {
+    /// Returns `true` if callers of the old interface can use the new one, i.e. no function was
+    /// removed or changed.
+    ///
+    /// Changes to events and errors are not considered breaking.
+    pub fn is_backward_compatible(&self) -> bool {
+        self.removed_functions.is_empty() && self.changed_functions.is_empty()
+    }
+}
+
+impl From<InterfaceDiff> for AbiDiff {
+    fn from(diff: InterfaceDiff) -> Self {
+        fn changed<T>(
+            changed: BTreeMap<impl Ord, (T, T)>,
+            name: impl Fn(&T) -> &str,
+            signature: impl Fn(&T) -> String,
+        ) -> Vec<(String, String, String)> {
+            changed
+                .into_values()
+                .map(|(old, new)| (name(&old).to_string(), signature(&old), signature(&new)))
+                .collect()
+        }
+        let InterfaceDiff { functions, events, errors } = diff;
+        Self {
+            added_functions: functions.added.values().map(Function::signature).collect(),
+            removed_functions: functions.removed.values().map(Function::signature).collect(),
+            changed_functions: changed(functions.changed, |f| &f.name, Function::full_signature),
+            added_events: events.added.values().map(Event::signature).collect(),
+            removed_events: events.removed.values().map(Event::signature).collect(),
+            changed_events: changed(events.changed, |e| &e.name, Event::full_signature),
+            added_errors: errors.added.values().map(alloy_json_abi::Error::signature).collect(),
+            removed_errors: errors.removed.values().map(alloy_json_abi::Error::signature).collect(),
+            changed_errors: changed(errors.changed, |e| &e.name, alloy_json_abi::Error::signature),
+        }
+    }
+}
+
+impl Interface {
+    /// Computes the differences between this interface and `other`, treating `self` as the old
+    /// interface and `other` as the new one.
+    ///
+    /// The constructor, fallback and receive functions are not compared.
+    pub fn diff(&self, other: &Interface) -> InterfaceDiff {
+        let (old, new) = (self.abi(), other.abi());
+        InterfaceDiff {
+            functions: diff_items(&old.functions, &new.functions, Function::selector, |a, b| {
+                a.state_mutability == b.state_mutability
+                    && a.signature_with_outputs() == b.signature_with_outputs()
+            }),
+            events: diff_items(&old.events, &new.events, Event::selector, |a, b| {
+                a.anonymous == b.anonymous
+                    && a.inputs.iter().map(|p| p.indexed).eq(b.inputs.iter().map(|p| p.indexed))
+            }),
+            errors: diff_items(
+                &old.errors,
+                &new.errors,
+                alloy_json_abi::Error::selector,
+                |_, _| true,
+            ),
+        }
+    }
+
+    /// Computes the differences between this interface and `other` as item signatures, treating
+    /// `self` as the old interface and `other` as the new one.
+    ///
+    /// See [`Interface::diff`] for how items are compared.
+    pub fn compare_with(&self, other: &Interface) -> AbiDiff {
+        self.diff(other).into()
+    }
+}
+
+fn diff_items<S, T, F, C>(
+    old: &BTreeMap<String, Vec<T>>,
+    new: &BTreeMap<String, Vec<T>>,
+    selector: F,
+    compatible: C,
+) -> ItemDiff<S, T>
+where
+    S: Ord,
+    T: Clone,
+    F: Fn(&T) -> S + Copy,
+    C: Fn(&T, &T) -> bool,
+{
+    fn by_selector<S: Ord, T>(
+        items: &BTreeMap<String, Vec<T>>,
+        selector: impl Fn(&T) -> S,
+    ) -> BTreeMap<S, &T> {
+        items.values().flatten().map(|item| (selector(item), item)).collect()
+    }
+    let (mut old, new) = (by_selector(old, selector), by_selector(new, selector));
+
+    let mut diff = ItemDiff::default();
+    for (selector, new_item) in new {
+        match old.remove(&selector) {
+            Some(old_item) if !compatible(old_item, new_item) => {
+                diff.changed.insert(selector, (old_item.clone(), new_item.clone()));
+            }
+            Some(_) => {}
+            None => {
+                diff.added.insert(selector, new_item.clone());
+            }
+        }
+    }
+    diff.removed = old.into_iter().map(|(selector, item)| (selector, item.clone())).collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_abi::JsonAbi;
+
+    fn interface(items: &[&str]) -> Interface {
+        Interface::from(JsonAbi::parse(items.iter().copied()).unwrap())
+    }
+
+    #[test]
+    fn diff() {
+        let old = interface(&[
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function balanceOf(address owner) returns (uint256)",
+            "function burn(uint256 amount)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "error Unauthorized()",
+        ]);
+        let new = interface(&[
+            // Renamed parameters are compatible.
+            "function transfer(address recipient, uint256 value) returns (bool)",
+            "function balanceOf(address owner) returns (uint128)",
+            "function mint(address to, uint256 amount)",
+            "event Transfer(address indexed from, address to, uint256 value)",
+            "error Unauthorized()",
+            "error Paused()",
+        ]);
+
+        let diff = old.diff(&new);
+        let function = |name: &str, abi: &Interface| abi.abi().function(name).unwrap()[0].clone();
+        assert_eq!(
+            diff.functions.added.into_values().collect::<Vec<_>>(),
+            [function("mint", &new)]
+        );
+        assert_eq!(
+            diff.functions.removed.into_values().collect::<Vec<_>>(),
+            [function("burn", &old)]
+        );
+        assert_eq!(
+            diff.functions.changed.into_values().collect::<Vec<_>>(),
+            [(function("balanceOf", &old), function("balanceOf", &new))]
+        );
+        assert_eq!(diff.events.changed.len(), 1);
+        assert!(diff.events.added.is_empty() && diff.events.removed.is_empty());
+        assert_eq!(diff.errors.added.len(), 1);
+        assert!(diff.errors.removed.is_empty() && diff.errors.changed.is_empty());
+
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn compare_with() {
+        let old = interface(&[
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function balanceOf(address owner) returns (uint256)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        ]);
+        let new = interface(&[
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function balanceOf(address owner) returns (uint256)",
+            "function mint(address to, uint256 amount)",
+            "event Transfer(address indexed from, address to, uint256 value)",
+            "error Paused()",
+        ]);
+
+        // Additions and event changes are backward compatible.
+        let diff = old.compare_with(&new);
+        assert_eq!(diff.added_functions, ["mint(address,uint256)"]);
+        assert_eq!(diff.added_errors, ["Paused()"]);
+        assert_eq!(diff.changed_events.len(), 1);
+        assert_eq!(diff.changed_events[0].0, "Transfer");
+        assert!(diff.is_backward_compatible());
+
+        // Removing a function is not.
+        let diff = new.compare_with(&old);
+        assert_eq!(diff.removed_functions, ["mint(address,uint256)"]);
+        assert!(!diff.is_backward_compatible());
+
+        let changed = interface(&[
+            "function transfer(address to, uint256 amount)",
+            "function balanceOf(address owner) returns (uint256)",
+        ]);
+        let diff = old.compare_with(&changed);
+        assert_eq!(
+            diff.changed_functions,
+            [(
+                "transfer".to_string(),
+                "function transfer(address to, uint256 amount) returns (bool)".to_string(),
+                "function transfer(address to, uint256 amount)".to_string(),
+            )]
+        );
+        assert!(!diff.is_backward_compatible());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{ContractInstance, Error, Result};
+use alloy_dyn_abi::DynSolValue;
+use alloy_primitives::{Address, U256};
+use alloy_providers::provider::TempProvider;
+use alloy_rpc_types::BlockId;
+
+/// Transaction parameters used to simulate a call with [`ContractInstance::dry_run`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TxParams {
+    /// The sender to simulate the transaction as.
+    pub from: Option<Address>,
+    /// The value to send with the transaction.
+    pub value: Option<U256>,
+    /// The gas limit of the transaction.
+    pub gas: Option<U256>,
+    /// The block to simulate the transaction at. Defaults to the latest block.
+    pub block: Option<BlockId>,
+}
+
+/// The outcome of a transaction simulated with [`ContractInstance::dry_run`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DryRunResult {
+    /// The decoded return values of the function. Empty if the transaction reverted.
+    pub return_values: Vec<DynSolValue>,
+    /// The gas the transaction would use, as estimated by `eth_estimateGas`. Zero if the
+    /// transaction reverted.
+    pub gas_used: u64,
+    /// Whether the transaction would revert.
+    pub reverted: bool,
+}
+
+impl<P: TempProvider + Clone> ContractInstance<P> {
+    /// Simulates a transaction calling the given function via `eth_call`, previewing its outcome
+    /// as sent by `tx_params.from` without spending any gas.
+    ///
+    /// Unless the simulation reverts, the gas used is estimated with a separate
+    /// `eth_estimateGas` request. A revert is reported in the result rather than as an error.
+    pub async fn dry_run(
+        &self,
+        function: &str,
+        args: &[DynSolValue],
+        tx_params: TxParams,
+    ) -> Result<DryRunResult> {
+        let mut call = self.function(function, args)?;
+        if let Some(from) = tx_params.from {
+            call = call.from(from);
+        }
+        if let Some(value) = tx_params.value {
+            call = call.value(value);
+        }
+        if let Some(gas) = tx_params.gas {
+            call = call.gas(gas);
+        }
+        if let Some(block) = tx_params.block {
+            call = call.block_id(block);
+        }
+
+        let return_values = match call.call().await {
+            Ok(return_values) => return_values,
+            Err(err @ Error::TransportError(_)) if err.is_revert() => {
+                return Ok(DryRunResult { reverted: true, ..Default::default() });
+            }
+            Err(err) => return Err(err),
+        };
+        let gas_used = call.estimate_gas().await?.saturating_to();
+        Ok(DryRunResult { return_values, gas_used, reverted: false })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_json_abi::JsonAbi;
+    use alloy_json_rpc::{ErrorPayload, RpcError};
+    use alloy_primitives::{Bytes, U64};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn dry_run_transfer() {
+        let abi = JsonAbi::parse(["function transfer(address to, uint256 amount) returns (bool)"])
+            .unwrap();
+        let (alice, bob) = (Address::with_last_byte(2), Address::with_last_byte(3));
+        let provider = MockTransport::provider(move |method, params| {
+            // Only alice has a balance.
+            let from: Address = serde_json::from_value(params[0]["from"].clone()).unwrap();
+            match (method, from == alice) {
+                ("eth_call", true) => Ok(json!(Bytes::from(U256::from(1).to_be_bytes_vec()))),
+                ("eth_estimateGas", true) => Ok(json!(U64::from(51_000))),
+                ("eth_call", false) => Err(RpcError::ErrorResp(ErrorPayload {
+                    code: 3,
+                    message: "execution reverted: ERC20: transfer amount exceeds balance".into(),
+                    data: None,
+                })),
+                _ => unreachable!("{method}"),
+            }
+        });
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+        let args = [DynSolValue::Address(bob), DynSolValue::Uint(U256::from(100), 256)];
+
+        let result = contract
+            .dry_run("transfer", &args, TxParams { from: Some(alice), ..Default::default() })
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            DryRunResult {
+                return_values: vec![DynSolValue::Bool(true)],
+                gas_used: 51_000,
+                reverted: false,
+            }
+        );
+
+        let result = contract
+            .dry_run("transfer", &args, TxParams { from: Some(bob), ..Default::default() })
+            .await
+            .unwrap();
+        assert!(result.reverted);
+        assert!(result.return_values.is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 use alloy_dyn_abi::Error as AbiError;
-use alloy_primitives::Selector;
+use alloy_primitives::{Address, Bytes, Selector, B256, U256};
+use alloy_sol_types::{Panic, Revert, SolError};
 use alloy_transport::TransportError;
-use std::fmt;
+use std::{fmt, time::Duration};
 
 /// Dynamic contract result type.
 pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -11,20 +12,142 @@ pub type Result<T, E = Error> = core::result::Result<T, E>;
 pub enum Error {
     /// Unknown function referenced.
     UnknownFunction(String),
+    /// The contract's ABI does not have a function with the given signature, which a standard
+    /// pattern like OpenZeppelin's `Ownable` requires.
+    FunctionNotFound(String),
     /// Unknown function selector referenced.
     UnknownSelector(Selector),
+    /// Unknown event selector referenced.
+    UnknownEventSelector(B256),
+    /// Unknown event referenced.
+    UnknownEvent(String),
+    /// Unknown custom error referenced.
+    UnknownError(String),
+    /// The transaction receipt could not be found, e.g. because the transaction is not yet mined.
+    TransactionNotFound(B256),
+    /// The transaction with the given hash is not known to the node anymore, e.g. because it
+    /// was replaced or evicted from the mempool.
+    TransactionDropped(B256),
+    /// The transaction with the given hash was not mined and confirmed within the
+    /// [timeout](crate::PendingTransaction::with_timeout).
+    ReceiptTimeout(B256),
+    /// The transaction was mined but reverted.
+    TransactionReverted {
+        /// The revert data, if known. Transaction receipts do not include it.
+        revert_data: Option<Bytes>,
+    },
+    /// The contract creation transaction with the given hash was not mined in time.
+    DeployTimeout(B256),
+    /// There is no contract code at an address, or a contract creation transaction did not
+    /// create a contract.
+    ContractNotDeployed {
+        /// The address without code, if known.
+        address: Option<Address>,
+        /// The hash of the contract creation transaction, if any.
+        tx_hash: Option<B256>,
+    },
+    /// The ABI of the contract at the given address is not known, see
+    /// [`ContractInstance::apply_upgrade_from_address`](crate::ContractInstance::apply_upgrade_from_address).
+    AbiNotFound(Address),
+    /// A request did not complete within the [timeout](crate::CallBuilder::timeout) of the call.
+    Timeout(Duration),
+    /// The transaction combines settings of incompatible transaction types, e.g. an EIP-7702
+    /// authorization list and EIP-4844 blobs.
+    IncompatibleTransaction(&'static str),
+    /// Unknown storage variable referenced.
+    UnknownStorageVariable(String),
+    /// The storage variable with the given name does not have the expected type, e.g. it is not
+    /// a mapping.
+    StorageTypeMismatch {
+        /// The name of the variable.
+        variable: String,
+        /// The expected kind of type, e.g. `mapping`.
+        expected: &'static str,
+    },
+    /// The storage layout JSON could not be parsed.
+    InvalidStorageLayout(serde_json::Error),
     /// An error occurred ABI encoding or decoding.
     AbiError(AbiError),
     /// An error occurred interacting with a contract over RPC.
     TransportError(TransportError),
 }
 
+impl Error {
+    /// Returns `true` if the error reports a reverted call or transaction, either as a node's
+    /// response to a call that reverted, or as [`Error::TransactionReverted`].
+    pub fn is_revert(&self) -> bool {
+        match self {
+            Self::TransactionReverted { .. } => true,
+            // Geth and most other clients use code 3 for reverts with data.
+            Self::TransportError(err) => err
+                .as_error_resp()
+                .map_or(false, |payload| payload.code == 3 || payload.message.contains("revert")),
+            _ => false,
+        }
+    }
+
+    /// Returns the reason of a revert, if known.
+    ///
+    /// This is the message of an `Error(string)` revert, or the part of a node's error message
+    /// following `execution reverted: `.
+    pub fn as_revert_reason(&self) -> Option<&str> {
+        match self {
+            Self::TransactionReverted { revert_data: Some(data) } => {
+                let data = data.strip_prefix(&Revert::SELECTOR)?;
+                // A single `string` parameter: its offset, length and contents.
+                if data.len() < 64 || U256::try_from_be_slice(&data[..32])? != U256::from(32) {
+                    return None;
+                }
+                let len: usize = U256::try_from_be_slice(&data[32..64])?.try_into().ok()?;
+                std::str::from_utf8(data[64..].get(..len)?).ok()
+            }
+            Self::TransportError(err) if self.is_revert() => {
+                err.as_error_resp()?.message.strip_prefix("execution reverted: ")
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the revert data of a reverted call or transaction, if known.
+    ///
+    /// This is the data of [`Error::TransactionReverted`], or the `data` of a node's response to
+    /// a call that reverted.
+    pub fn revert_data(&self) -> Option<Bytes> {
+        match self {
+            Self::TransactionReverted { revert_data } => revert_data.clone(),
+            Self::TransportError(err) if self.is_revert() => {
+                serde_json::from_str(err.as_error_resp()?.data.as_ref()?.get()).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the selector and ABI encoded parameters of the custom error a call or transaction
+    /// reverted with, if any.
+    ///
+    /// This inspects the same [`revert_data`](Self::revert_data), including the `data` of a node's
+    /// response to a call that reverted; `Error(string)` and `Panic(uint256)` reverts are not
+    /// custom errors.
+    pub fn as_custom_error(&self) -> Option<(Selector, Bytes)> {
+        let data = self.revert_data()?;
+        let selector = Selector::try_from(data.get(..4)?).ok()?;
+        (*selector != Revert::SELECTOR && *selector != Panic::SELECTOR)
+            .then(|| (selector, data.slice(4..)))
+    }
+}
+
 impl From<AbiError> for Error {
     fn from(error: AbiError) -> Self {
         Self::AbiError(error)
     }
 }
 
+impl From<alloy_sol_types::Error> for Error {
+    fn from(error: alloy_sol_types::Error) -> Self {
+        Self::AbiError(error.into())
+    }
+}
+
 impl From<TransportError> for Error {
     fn from(error: TransportError) -> Self {
         Self::TransportError(error)
@@ -36,6 +159,7 @@ impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::AbiError(e) => Some(e),
+            Self::InvalidStorageLayout(e) => Some(e),
             _ => None,
         }
     }
@@ -47,12 +171,115 @@ impl fmt::Display for Error {
             Self::UnknownFunction(name) => {
                 write!(f, "unknown function: function {name} does not exist",)
             }
+            Self::FunctionNotFound(signature) => {
+                write!(f, "function not found: the ABI has no function {signature}")
+            }
             Self::UnknownSelector(selector) => {
                 write!(f, "unknown function: function with selector {selector} does not exist")
             }
+            Self::UnknownEventSelector(selector) => {
+                write!(f, "unknown event: event with selector {selector} does not exist")
+            }
+            Self::UnknownEvent(name) => write!(f, "unknown event: event {name} does not exist"),
+            Self::UnknownError(name) => write!(f, "unknown error: error {name} does not exist"),
+            Self::TransactionNotFound(hash) => {
+                write!(f, "transaction not found: no receipt for transaction {hash}")
+            }
+            Self::TransactionDropped(hash) => {
+                write!(f, "transaction dropped: transaction {hash} is not known to the node")
+            }
+            Self::ReceiptTimeout(hash) => {
+                write!(f, "receipt timed out: transaction {hash} was not mined in time")
+            }
+            Self::TransactionReverted { revert_data } => match revert_data {
+                Some(data) => write!(f, "transaction reverted with data {data}"),
+                None => f.write_str("transaction reverted"),
+            },
+            Self::DeployTimeout(hash) => {
+                write!(f, "deployment timed out: transaction {hash} was not mined in time")
+            }
+            Self::ContractNotDeployed { address, tx_hash } => {
+                f.write_str("contract not deployed")?;
+                if let Some(address) = address {
+                    write!(f, ": no code at address {address}")?;
+                }
+                if let Some(hash) = tx_hash {
+                    write!(f, ": transaction {hash} did not create a contract")?;
+                }
+                Ok(())
+            }
+            Self::AbiNotFound(address) => {
+                write!(f, "ABI not found: no ABI for the contract at address {address}")
+            }
+            Self::Timeout(timeout) => write!(f, "request timed out after {timeout:?}"),
+            Self::IncompatibleTransaction(reason) => {
+                write!(f, "incompatible transaction settings: {reason}")
+            }
+            Self::UnknownStorageVariable(name) => {
+                write!(f, "unknown storage variable: variable {name} does not exist")
+            }
+            Self::StorageTypeMismatch { variable, expected } => {
+                write!(f, "storage type mismatch: variable {variable} is not a {expected}")
+            }
+            Self::InvalidStorageLayout(e) => write!(f, "invalid storage layout: {e}"),
 
             Self::AbiError(e) => e.fmt(f),
             Self::TransportError(e) => e.fmt(f),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{ErrorPayload, RpcError};
+
+    fn reverted(data: Vec<u8>) -> Error {
+        Error::TransactionReverted { revert_data: Some(data.into()) }
+    }
+
+    #[test]
+    fn revert_accessors() {
+        let err = reverted(Revert::from("insufficient balance").abi_encode());
+        assert!(err.is_revert());
+        assert_eq!(err.as_revert_reason(), Some("insufficient balance"));
+        assert_eq!(err.as_custom_error(), None);
+
+        let err = reverted([[0xde, 0xad, 0xbe, 0xef].as_slice(), &[1; 32]].concat());
+        assert!(err.is_revert());
+        assert_eq!(err.as_revert_reason(), None);
+        let custom = Some((Selector::new([0xde, 0xad, 0xbe, 0xef]), Bytes::from([1; 32])));
+        assert_eq!(err.as_custom_error(), custom);
+
+        let err = reverted(Panic::from(U256::from(0x11)).abi_encode());
+        assert_eq!(err.as_custom_error(), None);
+
+        let err = Error::TransportError(RpcError::ErrorResp(ErrorPayload {
+            code: 3,
+            message: "execution reverted: paused".into(),
+            data: None,
+        }));
+        assert!(err.is_revert());
+        assert_eq!(err.as_revert_reason(), Some("paused"));
+        assert_eq!(err.as_custom_error(), None);
+
+        let err = Error::TransportError(RpcError::ErrorResp(ErrorPayload {
+            code: 3,
+            message: "execution reverted".into(),
+            data: Some(
+                serde_json::value::to_raw_value(&format!("0xdeadbeef{}", "01".repeat(32))).unwrap(),
+            ),
+        }));
+        assert!(err.is_revert());
+        assert_eq!(err.as_custom_error(), custom);
+
+        let err = Error::TransportError(RpcError::ErrorResp(ErrorPayload {
+            code: -32000,
+            message: "nonce too low".into(),
+            data: None,
+        }));
+        assert!(!err.is_revert());
+        assert_eq!(err.as_revert_reason(), None);
+        assert!(!Error::UnknownFunction("transfer".into()).is_revert());
+    }
+}
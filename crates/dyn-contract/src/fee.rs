@@ -0,0 +1,139 @@
+use crate::Result;
+use alloy_primitives::U256;
+use alloy_providers::{
+    provider::TempProvider,
+    utils::{
+        eip1559_default_estimator, EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+        EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE,
+    },
+};
+use alloy_rpc_types::BlockNumberOrTag;
+
+/// EIP-1559 fees for a transaction, as estimated by a [`FeeOracle`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// The maximum total fee per gas the sender is willing to pay.
+    pub max_fee_per_gas: U256,
+    /// The maximum priority fee per gas paid to the block author.
+    pub max_priority_fee_per_gas: U256,
+}
+
+/// A strategy for estimating the fees of a transaction at send time.
+///
+/// See [`CallBuilder::with_oracle`](crate::CallBuilder::with_oracle).
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait FeeOracle<P>: Send + Sync {
+    /// Estimates the fees for a transaction sent through the given provider.
+    async fn estimate(&self, provider: &P) -> Result<FeeEstimate>;
+}
+
+/// A [`FeeOracle`] that estimates fees from the `eth_feeHistory` priority fee rewards at the given
+/// percentile, using the provider's default EIP-1559 estimator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PercentileFeeOracle {
+    /// The number of past blocks to fetch the fee history for.
+    pub block_count: u64,
+    /// The percentile of priority fee rewards to fetch.
+    pub percentile: f64,
+}
+
+impl Default for PercentileFeeOracle {
+    fn default() -> Self {
+        Self {
+            block_count: EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+            percentile: EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P: TempProvider> FeeOracle<P> for PercentileFeeOracle {
+    async fn estimate(&self, provider: &P) -> Result<FeeEstimate> {
+        let fee_history = provider
+            .get_fee_history(
+                U256::from(self.block_count),
+                BlockNumberOrTag::Latest,
+                &[self.percentile],
+            )
+            .await?;
+
+        // The last entry is the base fee of the next block.
+        let base_fee_per_gas = fee_history.base_fee_per_gas.last().copied().unwrap_or_default();
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            eip1559_default_estimator(base_fee_per_gas, fee_history.reward.unwrap_or_default());
+        Ok(FeeEstimate { max_fee_per_gas, max_priority_fee_per_gas })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::{Address, B256};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    struct StubOracle;
+
+    #[async_trait::async_trait]
+    impl<P: Send + Sync> FeeOracle<P> for StubOracle {
+        async fn estimate(&self, _provider: &P) -> Result<FeeEstimate> {
+            Ok(FeeEstimate {
+                max_fee_per_gas: U256::from(100),
+                max_priority_fee_per_gas: U256::from(2),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn send_uses_oracle() {
+        let sent = Arc::new(Mutex::new(Vec::<Value>::new()));
+        let provider = MockTransport::provider({
+            let sent = sent.clone();
+            move |method, params| match method {
+                "eth_chainId" => Ok(json!("0x1")),
+                "eth_sendTransaction" => {
+                    sent.lock().unwrap().push(params[0].clone());
+                    Ok(json!(B256::with_last_byte(1)))
+                }
+                _ => unreachable!("{method}"),
+            }
+        });
+        let abi = JsonAbi::parse(["function bump()"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let call = contract.function("bump", &[]).unwrap().with_oracle(Arc::new(StubOracle));
+        assert_eq!(call.clone().send().await.unwrap().tx_hash(), B256::with_last_byte(1));
+        // Explicit fees take precedence.
+        call.gas_price(U256::from(7)).send().await.unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[0]["to"], json!(Address::with_last_byte(1)));
+        assert_eq!(sent[0]["maxFeePerGas"], json!("0x64"));
+        assert_eq!(sent[0]["maxPriorityFeePerGas"], json!("0x2"));
+        assert_eq!(sent[1]["maxFeePerGas"], json!("0x7"));
+        assert_eq!(sent[1]["maxPriorityFeePerGas"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn percentile_oracle() {
+        let provider = MockTransport::provider(|method, params| {
+            assert_eq!(method, "eth_feeHistory");
+            assert_eq!(params[2], json!([EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE]));
+            Ok(json!({
+                "oldestBlock": "0x1",
+                "baseFeePerGas": ["0x1", "0x2540be400"],
+                "gasUsedRatio": [0.5],
+                "reward": [["0x1"]],
+            }))
+        });
+
+        let fees = PercentileFeeOracle::default().estimate(&provider).await.unwrap();
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            eip1559_default_estimator(U256::from(10_000_000_000u64), vec![vec![U256::from(1)]]);
+        assert_eq!(fees, FeeEstimate { max_fee_per_gas, max_priority_fee_per_gas });
+    }
+}
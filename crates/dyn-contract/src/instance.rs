@@ -1,8 +1,13 @@
-use crate::{CallBuilder, Interface, Result};
-use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
-use alloy_json_abi::JsonAbi;
-use alloy_primitives::{Address, Selector};
+use crate::{AbiRegistry, CallBuilder, Error, Interface, Result};
+use alloy_dyn_abi::{DecodedEvent, DynSolValue, JsonAbiExt, ResolveSolType};
+use alloy_json_abi::{Function, JsonAbi};
+use alloy_primitives::{keccak256, Address, LogData, Selector, B256, U256, U64};
 use alloy_providers::provider::TempProvider;
+use alloy_rpc_types::{BlockId, BlockNumberOrTag};
+use alloy_sol_types::{abi::TokenSeq, SolType, SolValue};
+use futures_util::future::join_all;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
 
 /// A handle to an Ethereum contract at a specific address.
 ///
@@ -12,12 +17,45 @@ pub struct ContractInstance<P> {
     address: Address,
     provider: P,
     interface: Interface,
+    /// The chain ID cache shared with clones and [`CallBuilder`]s, created on first use so that
+    /// [`Self::new`] can be `const`.
+    chain_id: OnceCell<Arc<OnceCell<u64>>>,
+    registry: Option<Arc<dyn AbiRegistry>>,
 }
 
 impl<P> ContractInstance<P> {
     /// Creates a new contract from the provided address, provider, and interface.
     pub const fn new(address: Address, provider: P, interface: Interface) -> Self {
-        Self { address, provider, interface }
+        Self { address, provider, interface, chain_id: OnceCell::const_new(), registry: None }
+    }
+
+    /// Sets the registry used to look up the ABI of a new implementation in
+    /// [`ContractInstance::apply_upgrade_from_address`].
+    pub fn with_abi_registry(mut self, registry: impl AbiRegistry + 'static) -> Self {
+        self.registry = Some(Arc::new(registry));
+        self
+    }
+
+    /// Sets the chain ID used for EIP-155 replay protection by all transactions sent via this
+    /// contract's [`CallBuilder`]s, unless overridden with [`CallBuilder::chain_id`].
+    ///
+    /// If not set, the chain ID is fetched via `eth_chainId` when the first transaction is sent,
+    /// and cached afterwards.
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = OnceCell::new_with(Some(Arc::new(OnceCell::new_with(Some(chain_id)))));
+        self
+    }
+
+    /// Returns the chain ID used for transactions, if it was set or has already been fetched.
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id.get().and_then(|cache| cache.get()).copied()
+    }
+
+    /// Returns the chain ID cache, creating it if needed.
+    fn chain_id_cache(&self) -> Arc<OnceCell<u64>> {
+        // Fails if another thread created it first, which is fine.
+        let _ = self.chain_id.set(Arc::default());
+        self.chain_id.get().expect("chain ID cache was just created").clone()
     }
 
     /// Returns the contract's address.
@@ -36,6 +74,31 @@ impl<P> ContractInstance<P> {
     }
 }
 
+impl<P> ContractInstance<P> {
+    /// Computes the storage slot of the value for `key` in a mapping stored at `mapping_slot`,
+    /// following Solidity's storage layout: `keccak256(h(key) . mapping_slot)`.
+    ///
+    /// `h(key)` is the key padded to 32 bytes for value types, or the unpadded key for `string`
+    /// and `bytes` keys.
+    pub fn storage_slot_for_mapping(mapping_slot: U256, key: DynSolValue) -> U256 {
+        let mut preimage = match key {
+            DynSolValue::String(key) => key.into_bytes(),
+            DynSolValue::Bytes(key) => key,
+            key => key.abi_encode(),
+        };
+        preimage.extend_from_slice(&mapping_slot.to_be_bytes::<32>());
+        keccak256(preimage).into()
+    }
+
+    /// Computes the storage slot of the element at `index` in a dynamic array stored at
+    /// `array_slot`, following Solidity's storage layout: `keccak256(array_slot) + index`.
+    ///
+    /// This assumes that each element takes up a whole slot.
+    pub fn storage_slot_for_dynamic_array(array_slot: U256, index: U256) -> U256 {
+        U256::from_be_bytes(keccak256(array_slot.to_be_bytes::<32>()).0).wrapping_add(index)
+    }
+}
+
 impl<P> ContractInstance<P>
 where
     P: Clone,
@@ -54,6 +117,30 @@ where
         this.address = address;
         this
     }
+
+    /// Returns a new contract instance at the same address with the given ABI, e.g. after the
+    /// implementation of a proxy contract was upgraded.
+    ///
+    /// Clones `self` internally
+    #[must_use]
+    pub fn apply_upgrade(&self, new_abi: JsonAbi) -> ContractInstance<P> {
+        let mut this = self.clone();
+        this.interface = Interface::from(new_abi);
+        this
+    }
+
+    /// Returns a new contract instance at the same address with the ABI of the implementation
+    /// contract at `impl_address`, as looked up in the [registry](Self::with_abi_registry).
+    ///
+    /// Fails with [`Error::AbiNotFound`] if no registry is set or it does not know the ABI.
+    pub async fn apply_upgrade_from_address(
+        &self,
+        impl_address: Address,
+    ) -> Result<ContractInstance<P>> {
+        let registry = self.registry.as_ref().ok_or(Error::AbiNotFound(impl_address))?;
+        let abi = registry.abi(impl_address).await?.ok_or(Error::AbiNotFound(impl_address))?;
+        Ok(self.apply_upgrade(abi))
+    }
 }
 
 impl<P: TempProvider + Clone> ContractInstance<P> {
@@ -64,7 +151,8 @@ impl<P: TempProvider + Clone> ContractInstance<P> {
     pub fn function(&self, name: &str, args: &[DynSolValue]) -> Result<CallBuilder<P>> {
         let func = self.interface.get_from_name(name)?;
         let data = func.abi_encode_input(args)?;
-        Ok(CallBuilder::new(self.provider.clone(), func.clone(), data.into()))
+        Ok(CallBuilder::new(self.provider.clone(), func.clone(), self.address, data.into())
+            .with_chain_id_cache(self.chain_id_cache()))
     }
 
     /// Returns a transaction builder for the provided function selector.
@@ -75,7 +163,125 @@ impl<P: TempProvider + Clone> ContractInstance<P> {
     ) -> Result<CallBuilder<P>> {
         let func = self.interface.get_from_selector(selector)?;
         let data = func.abi_encode_input(args)?;
-        Ok(CallBuilder::new(self.provider.clone(), func.clone(), data.into()))
+        Ok(CallBuilder::new(self.provider.clone(), func.clone(), self.address, data.into())
+            .with_chain_id_cache(self.chain_id_cache()))
+    }
+
+    /// Calls the given functions concurrently via one `eth_call` request each, returning their
+    /// decoded outputs in the same order as `calls`.
+    ///
+    /// Each call succeeds or fails independently. The whole batch only fails, before any request
+    /// is sent, if one of the functions does not exist or cannot be encoded with its arguments.
+    ///
+    /// If there are multiple functions with the same name due to overloading, the first match is
+    /// used, as in [`ContractInstance::function`].
+    pub async fn call_batch_parallel(
+        &self,
+        calls: Vec<(&str, Vec<DynSolValue>)>,
+    ) -> Result<Vec<Result<Vec<DynSolValue>>>> {
+        let builders = calls
+            .iter()
+            .map(|(name, args)| self.function(name, args))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(join_all(builders.iter().map(CallBuilder::call)).await)
+    }
+
+    /// Calls every `view` and `pure` function of the contract via `eth_call` with zero-value
+    /// arguments, e.g. `0`, the zero address, `false`, or empty bytes, strings and arrays,
+    /// returning the signature of each function along with its decoded output.
+    ///
+    /// This is useful to check which functions revert with default inputs. The calls are sent
+    /// concurrently, and succeed or fail independently.
+    pub async fn call_all_view_functions(&self) -> Vec<(String, Result<Vec<DynSolValue>>)> {
+        let calls = self.view_functions().map(|function| async move {
+            let result = async {
+                let args = zero_args(function)?;
+                self.function_from_selector(&function.selector(), &args)?.call().await
+            };
+            (function.signature(), result.await)
+        });
+        join_all(calls).await
+    }
+
+    /// Calls the given function via `eth_call`, decoding its whole return tuple into `T`, e.g. a
+    /// struct generated by [`sol!`](alloy_sol_types::sol), whose fields are the function's
+    /// outputs in order.
+    ///
+    /// The output is decoded against `T` only, not the function's declared output types.
+    pub async fn call_returning_struct<T>(&self, function: &str, args: &[DynSolValue]) -> Result<T>
+    where
+        T: SolValue + From<<T::SolType as SolType>::RustType>,
+        for<'a> <T::SolType as SolType>::Token<'a>: TokenSeq<'a>,
+    {
+        let data = self.function(function, args)?.call_raw().await?;
+        Ok(T::abi_decode_params(&data, true)?)
+    }
+
+    /// Calls the given function via `eth_call`, decoding its first return value into `T`.
+    ///
+    /// Any further return values are ignored. The output is decoded against `T` only, not the
+    /// function's declared output types.
+    pub async fn call_returning_single<T>(&self, function: &str, args: &[DynSolValue]) -> Result<T>
+    where
+        T: SolValue + From<<T::SolType as SolType>::RustType>,
+    {
+        let data = self.function(function, args)?.call_raw().await?;
+        Ok(T::abi_decode(&data, false)?)
+    }
+
+    /// Returns `true` if there is contract code at this contract's address at the given block,
+    /// via `eth_getCode`.
+    pub async fn is_deployed(&self, block: BlockId) -> Result<bool> {
+        Ok(!self.provider.get_code_at(self.address, block).await?.is_empty())
+    }
+
+    /// Calls the given function via `eth_call`, like [`CallBuilder::call`], after checking that
+    /// the contract is deployed at the latest block.
+    ///
+    /// Calling an address without code succeeds with empty output, which usually fails to decode
+    /// with a confusing error. This fails with [`Error::ContractNotDeployed`] instead, at the cost
+    /// of an extra `eth_getCode` request.
+    pub async fn safe_call(
+        &self,
+        function: &str,
+        args: &[DynSolValue],
+    ) -> Result<Vec<DynSolValue>> {
+        let call = self.function(function, args)?;
+        if !self.is_deployed(BlockId::Number(BlockNumberOrTag::Latest)).await? {
+            return Err(Error::ContractNotDeployed { address: Some(self.address), tx_hash: None });
+        }
+        call.call().await
+    }
+
+    /// Reads the raw value of the given storage slot of this contract at the latest block, via
+    /// `eth_getStorageAt`.
+    pub async fn read_storage_raw(&self, slot: U256) -> Result<U256> {
+        self.provider.get_storage_at(self.address, slot, None).await.map_err(Into::into)
+    }
+
+    /// Returns the events emitted by this contract in the transaction with the given hash,
+    /// decoded using the contract's ABI.
+    ///
+    /// Logs emitted by other contracts during the transaction are ignored.
+    pub async fn logs_for_tx(&self, tx_hash: B256) -> Result<Vec<DecodedEvent>> {
+        let receipt = self
+            .provider
+            .get_transaction_receipt(tx_hash)
+            .await?
+            .ok_or(Error::TransactionNotFound(tx_hash))?;
+        if receipt.status_code == Some(U64::ZERO) {
+            return Err(Error::TransactionReverted { revert_data: None });
+        }
+
+        receipt
+            .logs
+            .into_iter()
+            .filter(|log| log.address == self.address)
+            .map(|log| {
+                let log = LogData::new_unchecked(log.topics, log.data);
+                self.interface.decode_log(&log, true)
+            })
+            .collect()
     }
 }
 
@@ -88,6 +294,8 @@ where
             address: self.address,
             provider: self.provider.clone(),
             interface: self.interface.clone(),
+            chain_id: OnceCell::new_with(Some(self.chain_id_cache())),
+            registry: self.registry.clone(),
         }
     }
 }
@@ -105,3 +313,402 @@ impl<P> std::fmt::Debug for ContractInstance<P> {
         f.debug_struct("ContractInstance").field("address", &self.address).finish()
     }
 }
+
+/// Returns the zero value of each of the function's parameters.
+///
+/// All-zero calldata decodes to exactly these: static values are zero, and dynamic values are
+/// empty, since their offsets and lengths are zero.
+fn zero_args(function: &Function) -> Result<Vec<DynSolValue>> {
+    let words = function
+        .inputs
+        .iter()
+        .map(|param| Ok(param.resolve()?.minimum_words()))
+        .sum::<Result<usize>>()?;
+    Ok(function.abi_decode_input(&vec![0; 32 * words], false)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use alloy_json_rpc::{ErrorPayload, RpcError};
+    use alloy_primitives::{b256, Bytes};
+    use alloy_rpc_types::{Log, TransactionReceipt};
+    use alloy_transport::TransportResult;
+    use serde_json::{json, Value};
+
+    fn erc20_abi() -> JsonAbi {
+        JsonAbi::parse([
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Approval(address indexed owner, address indexed spender, uint256 value)",
+        ])
+        .unwrap()
+    }
+
+    fn log(address: Address, topics: Vec<B256>, value: u64) -> Log {
+        let data = Bytes::from(U256::from(value).to_be_bytes_vec());
+        Log { address, topics, data, ..Default::default() }
+    }
+
+    fn receipt_provider(
+        receipt: Option<TransactionReceipt>,
+    ) -> impl Fn(&str, &Value) -> TransportResult<Value> {
+        move |method, _| {
+            assert_eq!(method, "eth_getTransactionReceipt");
+            Ok(serde_json::to_value(&receipt).unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn logs_for_tx_decodes_contract_logs() {
+        let abi = erc20_abi();
+        let address = Address::with_last_byte(1);
+        let (alice, bob) = (Address::with_last_byte(2), Address::with_last_byte(3));
+        let transfer = abi.event("Transfer").unwrap()[0].selector();
+        let approval = abi.event("Approval").unwrap()[0].selector();
+
+        let receipt = TransactionReceipt {
+            status_code: Some(U64::from(1)),
+            logs: vec![
+                log(address, vec![transfer, alice.into_word(), bob.into_word()], 100),
+                log(Address::with_last_byte(4), vec![transfer, alice.into_word()], 1),
+                log(address, vec![approval, alice.into_word(), bob.into_word()], 200),
+            ],
+            ..Default::default()
+        };
+        let provider = MockTransport::provider(receipt_provider(Some(receipt)));
+        let contract = Interface::from(abi).connect(address, provider);
+
+        let events = contract.logs_for_tx(B256::ZERO).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].indexed, vec![DynSolValue::Address(alice), DynSolValue::Address(bob)]);
+        assert_eq!(events[0].body, vec![DynSolValue::Uint(U256::from(100), 256)]);
+        assert_eq!(events[1].body, vec![DynSolValue::Uint(U256::from(200), 256)]);
+    }
+
+    type Contract = ContractInstance<()>;
+
+    #[test]
+    fn storage_slots() {
+        // `mapping(uint256 => uint256)` at slot 0.
+        assert_eq!(
+            Contract::storage_slot_for_mapping(U256::ZERO, DynSolValue::Uint(U256::ZERO, 256)),
+            U256::from_be_bytes(
+                b256!("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5").0
+            )
+        );
+        // `mapping(address => uint256)` at slot 1: the key is left-padded.
+        let owner = Address::with_last_byte(0xaa);
+        assert_eq!(
+            Contract::storage_slot_for_mapping(U256::from(1), DynSolValue::Address(owner)),
+            U256::from_be_bytes(
+                keccak256([owner.into_word().0, U256::from(1).to_be_bytes()].concat()).0
+            )
+        );
+        // `mapping(string => uint256)` at slot 2: the key is not padded.
+        assert_eq!(
+            Contract::storage_slot_for_mapping(U256::from(2), DynSolValue::String("abc".into())),
+            U256::from_be_bytes(keccak256([b"abc".as_slice(), &[0; 31], &[2]].concat()).0)
+        );
+
+        // `uint256[]` at slots 0 and 1.
+        let first = U256::from_be_bytes(
+            b256!("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563").0,
+        );
+        assert_eq!(Contract::storage_slot_for_dynamic_array(U256::ZERO, U256::ZERO), first);
+        assert_eq!(
+            Contract::storage_slot_for_dynamic_array(U256::ZERO, U256::from(2)),
+            first + U256::from(2)
+        );
+        assert_eq!(
+            Contract::storage_slot_for_dynamic_array(U256::from(1), U256::ZERO),
+            U256::from_be_bytes(
+                b256!("b10e2d527612073b26eecdfd717e6a320cf44b4afac2b0732d9fcbe2b7fa0cf6").0
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn read_storage_raw() {
+        let address = Address::with_last_byte(1);
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "eth_getStorageAt");
+            assert_eq!(params[0], json!(address));
+            assert_eq!(params[1], json!(U256::from(7)));
+            Ok(json!(U256::from(42)))
+        });
+        let contract = Interface::from(erc20_abi()).connect(address, provider);
+        assert_eq!(contract.read_storage_raw(U256::from(7)).await.unwrap(), U256::from(42));
+    }
+
+    #[tokio::test]
+    async fn apply_upgrade() {
+        let proxy = Address::with_last_byte(1);
+        let implementation = Address::with_last_byte(2);
+        let v1 = JsonAbi::parse(["function version() returns (uint256)"]).unwrap();
+        let v2 = JsonAbi::parse([
+            "function version() returns (uint256)",
+            "function paused() returns (bool)",
+        ])
+        .unwrap();
+        let paused = v2.function("paused").unwrap()[0].selector();
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "eth_call");
+            assert_eq!(params[0]["to"], json!(proxy));
+            let input: Bytes = serde_json::from_value(params[0]["input"].clone()).unwrap();
+            assert_eq!(input[..], paused[..]);
+            Ok(json!(Bytes::from(U256::from(1).to_be_bytes_vec())))
+        });
+        let contract = Interface::from(v1).connect(proxy, provider);
+        assert!(contract.function("paused", &[]).is_err());
+
+        let upgraded = contract.apply_upgrade(v2.clone());
+        assert_eq!(upgraded.address(), proxy);
+        assert_eq!(
+            upgraded.function("paused", &[]).unwrap().call().await.unwrap(),
+            [DynSolValue::Bool(true)]
+        );
+
+        let err = contract.apply_upgrade_from_address(implementation).await.unwrap_err();
+        assert!(matches!(err, Error::AbiNotFound(address) if address == implementation));
+
+        let registry = std::collections::HashMap::from([(implementation, v2)]);
+        let contract = contract.with_abi_registry(registry);
+        let upgraded = contract.apply_upgrade_from_address(implementation).await.unwrap();
+        assert_eq!(upgraded.address(), proxy);
+        assert!(
+            upgraded.function("paused", &[]).unwrap().call().await.unwrap()[0]
+                == DynSolValue::Bool(true)
+        );
+
+        let err = contract.apply_upgrade_from_address(proxy).await.unwrap_err();
+        assert!(matches!(err, Error::AbiNotFound(address) if address == proxy));
+    }
+
+    #[tokio::test]
+    async fn safe_call() {
+        let deployed = Address::with_last_byte(1);
+        let provider = MockTransport::provider(move |method, params| match method {
+            "eth_getCode" => {
+                assert_eq!(params[1], json!("latest"));
+                let code = if params[0] == json!(deployed) { &[0xfe][..] } else { &[] };
+                Ok(json!(Bytes::copy_from_slice(code)))
+            }
+            "eth_call" => Ok(json!(Bytes::from(U256::from(1000).to_be_bytes_vec()))),
+            _ => unreachable!("{method}"),
+        });
+        let abi = JsonAbi::parse(["function totalSupply() returns (uint256)"]).unwrap();
+        let contract = Interface::from(abi).connect(deployed, provider);
+
+        assert!(contract.is_deployed(BlockId::Number(BlockNumberOrTag::Latest)).await.unwrap());
+        assert_eq!(
+            contract.safe_call("totalSupply", &[]).await.unwrap(),
+            [DynSolValue::Uint(U256::from(1000), 256)]
+        );
+
+        let contract = contract.at(Address::ZERO);
+        assert!(!contract.is_deployed(BlockId::Number(BlockNumberOrTag::Latest)).await.unwrap());
+        let err = contract.safe_call("totalSupply", &[]).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ContractNotDeployed { address: Some(address), tx_hash: None }
+                if address == Address::ZERO
+        ));
+    }
+
+    #[tokio::test]
+    async fn call_batch_parallel() {
+        let abi = JsonAbi::parse([
+            "function totalSupply() returns (uint256)",
+            "function balanceOf(address owner) returns (uint256)",
+        ])
+        .unwrap();
+        let total_supply = abi.function("totalSupply").unwrap()[0].selector();
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "eth_call");
+            let input: Bytes = serde_json::from_value(params[0]["input"].clone()).unwrap();
+            if input.starts_with(total_supply.as_slice()) {
+                Ok(json!(Bytes::from(U256::from(1000).to_be_bytes_vec())))
+            } else {
+                Err(RpcError::ErrorResp(ErrorPayload {
+                    code: 3,
+                    message: "execution reverted".into(),
+                    data: None,
+                }))
+            }
+        });
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let alice = DynSolValue::Address(Address::with_last_byte(2));
+        let results = contract
+            .call_batch_parallel(vec![
+                ("balanceOf", vec![alice.clone()]),
+                ("totalSupply", vec![]),
+                ("totalSupply", vec![]),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], Err(Error::TransportError(_))));
+        assert_eq!(results[1].as_ref().unwrap(), &[DynSolValue::Uint(U256::from(1000), 256)]);
+        assert_eq!(results[2].as_ref().unwrap(), &[DynSolValue::Uint(U256::from(1000), 256)]);
+
+        let err = contract
+            .call_batch_parallel(vec![("totalSupply", vec![]), ("approve", vec![alice])])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UnknownFunction(name) if name == "approve"));
+    }
+
+    #[tokio::test]
+    async fn logs_for_tx_errors() {
+        let address = Address::with_last_byte(1);
+
+        let provider = MockTransport::provider(receipt_provider(None));
+        let contract = Interface::from(erc20_abi()).connect(address, provider);
+        let err = contract.logs_for_tx(B256::ZERO).await.unwrap_err();
+        assert!(matches!(err, Error::TransactionNotFound(hash) if hash == B256::ZERO));
+
+        let receipt = TransactionReceipt { status_code: Some(U64::ZERO), ..Default::default() };
+        let provider = MockTransport::provider(receipt_provider(Some(receipt)));
+        let contract = Interface::from(erc20_abi()).connect(address, provider);
+        let err = contract.logs_for_tx(B256::ZERO).await.unwrap_err();
+        assert!(matches!(err, Error::TransactionReverted { revert_data: None }));
+    }
+
+    #[tokio::test]
+    async fn chain_id() {
+        let chain_id_requests = Arc::new(std::sync::Mutex::new(0));
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let provider = MockTransport::provider({
+            let (chain_id_requests, sent) = (chain_id_requests.clone(), sent.clone());
+            move |method, params| match method {
+                "eth_chainId" => {
+                    *chain_id_requests.lock().unwrap() += 1;
+                    Ok(json!(U64::from(10)))
+                }
+                "eth_sendTransaction" => {
+                    sent.lock().unwrap().push(params[0]["chainId"].clone());
+                    Ok(json!(B256::ZERO))
+                }
+                _ => unreachable!("{method}"),
+            }
+        });
+        let abi = JsonAbi::parse(["function bump()"]).unwrap();
+        let interface = Interface::from(abi);
+
+        // Fetched lazily, once.
+        let contract = interface.clone().connect(Address::with_last_byte(1), provider.clone());
+        assert_eq!(contract.chain_id(), None);
+        contract.function("bump", &[]).unwrap().send().await.unwrap();
+        contract
+            .at(Address::with_last_byte(2))
+            .function("bump", &[])
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(contract.chain_id(), Some(10));
+        assert_eq!(*chain_id_requests.lock().unwrap(), 1);
+
+        // Never fetched if set.
+        let contract = interface.connect(Address::with_last_byte(1), provider).with_chain_id(5);
+        assert_eq!(contract.chain_id(), Some(5));
+        contract.function("bump", &[]).unwrap().send().await.unwrap();
+        assert_eq!(*chain_id_requests.lock().unwrap(), 1);
+
+        assert_eq!(*sent.lock().unwrap(), [json!("0xa"), json!("0xa"), json!("0x5")]);
+    }
+
+    #[tokio::test]
+    async fn call_returning_struct() {
+        alloy_sol_types::sol! {
+            struct Position {
+                uint256 liquidity;
+                address owner;
+            }
+
+            struct Pool {
+                Position position;
+                uint64[] ticks;
+                string name;
+            }
+        }
+
+        let pool = Pool {
+            position: Position { liquidity: U256::from(1000), owner: Address::with_last_byte(2) },
+            ticks: vec![1, 2, 3],
+            name: "ETH/USDC".into(),
+        };
+        let output = Bytes::from(pool.abi_encode_params());
+        let provider = MockTransport::provider(move |method, _| {
+            assert_eq!(method, "eth_call");
+            Ok(json!(output))
+        });
+        let abi = JsonAbi::parse([
+            "function pool() returns ((uint256,address) position, uint64[] ticks, string name)",
+        ])
+        .unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let decoded: Pool = contract.call_returning_struct("pool", &[]).await.unwrap();
+        assert_eq!(decoded.position.liquidity, pool.position.liquidity);
+        assert_eq!(decoded.position.owner, pool.position.owner);
+        assert_eq!(decoded.ticks, pool.ticks);
+        assert_eq!(decoded.name, pool.name);
+
+        let position: Position = contract.call_returning_single("pool", &[]).await.unwrap();
+        assert_eq!(position.owner, pool.position.owner);
+
+        let result = contract.call_returning_struct::<Position>("pool", &[]).await;
+        assert!(matches!(result, Err(Error::AbiError(_))));
+    }
+
+    #[tokio::test]
+    async fn call_all_view_functions() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[
+                {"type": "function", "name": "totalSupply", "inputs": [], "outputs": [{"name": "", "type": "uint256"}], "stateMutability": "view"},
+                {"type": "function", "name": "balanceOf", "inputs": [{"name": "owner", "type": "address"}], "outputs": [{"name": "", "type": "uint256"}], "stateMutability": "view"},
+                {"type": "function", "name": "check", "inputs": [{"name": "flag", "type": "bool"}, {"name": "data", "type": "bytes"}, {"name": "ids", "type": "uint256[]"}, {"name": "pair", "type": "tuple[2]", "components": [{"name": "name", "type": "string"}, {"name": "id", "type": "int8"}]}], "outputs": [{"name": "", "type": "uint256"}], "stateMutability": "pure"},
+                {"type": "function", "name": "transfer", "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}], "outputs": [{"name": "", "type": "bool"}], "stateMutability": "nonpayable"}
+            ]"#,
+        )
+        .unwrap();
+        let check = abi.function("check").unwrap()[0].clone();
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "eth_call");
+            let input: Bytes = serde_json::from_value(params[0]["input"].clone()).unwrap();
+            if input.starts_with(check.selector().as_slice()) {
+                let args = check.abi_decode_input(&input[4..], true).unwrap();
+                assert_eq!(args[0], DynSolValue::Bool(false));
+                assert_eq!(args[1], DynSolValue::Bytes(vec![]));
+                assert_eq!(args[2], DynSolValue::Array(vec![]));
+                let zero = DynSolValue::Tuple(vec![
+                    DynSolValue::String(String::new()),
+                    DynSolValue::Int(Default::default(), 8),
+                ]);
+                assert_eq!(args[3], DynSolValue::FixedArray(vec![zero.clone(), zero]));
+                return Err(RpcError::ErrorResp(ErrorPayload {
+                    code: 3,
+                    message: "execution reverted".into(),
+                    data: None,
+                }));
+            }
+            assert_eq!(input[4..], [0; 32][..input.len() - 4]);
+            Ok(json!(Bytes::from(U256::from(1).to_be_bytes_vec())))
+        });
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let mut results = contract.call_all_view_functions().await;
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(results.len(), 3);
+        let one = [DynSolValue::Uint(U256::from(1), 256)];
+        assert_eq!(results[0].0, "balanceOf(address)");
+        assert_eq!(results[0].1.as_ref().unwrap(), &one);
+        assert_eq!(results[1].0, "check(bool,bytes,uint256[],(string,int8)[2])");
+        assert!(matches!(results[1].1, Err(Error::TransportError(_))));
+        assert_eq!(results[2].0, "totalSupply()");
+        assert_eq!(results[2].1.as_ref().unwrap(), &one);
+    }
+}
@@ -1,19 +1,27 @@
 use crate::{ContractInstance, Error, Result};
-use alloy_dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt};
-use alloy_json_abi::{Function, JsonAbi};
-use alloy_primitives::{Address, Selector};
-use std::collections::HashMap;
+use alloy_dyn_abi::{DecodedEvent, DynSolValue, EventExt, FunctionExt, JsonAbiExt};
+use alloy_json_abi::{Constructor, Event, Function, JsonAbi, StateMutability};
+use alloy_primitives::{Address, LogData, Selector, B256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+};
 
 /// A smart contract interface.
 #[derive(Debug, Clone)]
 pub struct Interface {
     abi: JsonAbi,
     functions: HashMap<Selector, (String, usize)>,
+    events: HashMap<B256, (String, usize)>,
 }
 
 impl From<JsonAbi> for Interface {
     fn from(abi: JsonAbi) -> Self {
-        Self { abi, functions: Default::default() }
+        Self {
+            functions: create_mapping(&abi.functions, Function::selector),
+            events: create_mapping(&abi.events, Event::selector),
+            abi,
+        }
     }
 }
 
@@ -91,6 +99,17 @@ impl Interface {
         self.get_from_selector(selector)?.abi_decode_output(data, validate).map_err(Into::into)
     }
 
+    /// Decode the provided log according to the event matching its first topic.
+    ///
+    /// # Note
+    ///
+    /// Anonymous events cannot be identified by their first topic and will fail with
+    /// [`Error::UnknownEventSelector`].
+    pub fn decode_log(&self, log: &LogData, validate: bool) -> Result<DecodedEvent> {
+        let selector = log.topics().first().copied().unwrap_or_default();
+        self.get_event_from_selector(&selector)?.decode_log(log, validate).map_err(Into::into)
+    }
+
     /// Returns a reference to the contract's ABI.
     pub const fn abi(&self) -> &JsonAbi {
         &self.abi
@@ -101,6 +120,108 @@ impl Interface {
         self.abi
     }
 
+    /// Returns the contract's ABI as a Solidity `interface` declaration with the given name.
+    ///
+    /// The declaration includes the contract's functions, events, errors, and any structs and
+    /// user-defined value types they use. The constructor is omitted, since interfaces cannot
+    /// declare one.
+    pub fn to_sol_interface(&self, name: &str) -> String {
+        self.abi.to_sol(name)
+    }
+
+    /// Returns the names of the contract's functions, in alphabetical order.
+    ///
+    /// Overloaded functions appear once.
+    pub fn function_names(&self) -> Vec<&str> {
+        self.abi.functions.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the names of the contract's events, in alphabetical order.
+    ///
+    /// Overloaded events appear once.
+    pub fn event_names(&self) -> Vec<&str> {
+        self.abi.events.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the names of the contract's custom errors, in alphabetical order.
+    ///
+    /// Overloaded errors appear once.
+    pub fn error_names(&self) -> Vec<&str> {
+        self.abi.errors.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the number of functions in the contract, counting each overload separately.
+    pub fn function_count(&self) -> usize {
+        self.abi.functions.values().map(Vec::len).sum()
+    }
+
+    /// Returns the number of overloads of the function with the given name, or `0` if there is
+    /// no such function.
+    pub fn overload_count(&self, name: &str) -> usize {
+        self.abi.function(name).map_or(0, Vec::len)
+    }
+
+    /// Returns the contract's `view` and `pure` functions, which can be called via `eth_call`
+    /// without sending a transaction, in alphabetical order.
+    pub fn view_functions(&self) -> impl Iterator<Item = &Function> {
+        self.abi.functions().filter(|function| is_view(function))
+    }
+
+    /// Returns the contract's `nonpayable` and `payable` functions, which may modify its state
+    /// and so are usually sent in a transaction, in alphabetical order.
+    pub fn mutating_functions(&self) -> impl Iterator<Item = &Function> {
+        self.abi.functions().filter(|function| !is_view(function))
+    }
+
+    /// Returns the contract's constructor, if the ABI declares one.
+    pub const fn constructor(&self) -> Option<&Constructor> {
+        self.abi.constructor.as_ref()
+    }
+
+    /// Returns `true` if the contract has a fallback function, which is executed for calls that
+    /// match no other function.
+    pub const fn has_fallback(&self) -> bool {
+        self.abi.fallback.is_some()
+    }
+
+    /// Returns `true` if the contract has a receive function, which is executed for plain value
+    /// transfers with empty calldata.
+    ///
+    /// Such transfers also succeed if the contract only has a payable fallback function.
+    pub const fn has_receive(&self) -> bool {
+        self.abi.receive.is_some()
+    }
+
+    /// Returns the first event with the given name.
+    ///
+    /// If there are multiple events with the same name, use [`Self::abi`] to access all
+    /// overloads.
+    pub fn get_event(&self, name: &str) -> Result<&Event> {
+        self.abi
+            .event(name)
+            .and_then(|r| r.first())
+            .ok_or_else(|| Error::UnknownEvent(name.to_string()))
+    }
+
+    /// Returns the first custom error with the given name.
+    ///
+    /// If there are multiple errors with the same name, use [`Self::abi`] to access all
+    /// overloads.
+    pub fn get_error(&self, name: &str) -> Result<&alloy_json_abi::Error> {
+        self.abi
+            .error(name)
+            .and_then(|r| r.first())
+            .ok_or_else(|| Error::UnknownError(name.to_string()))
+    }
+
+    /// Returns the contract's constructor, if the ABI declares one.
+    ///
+    /// This is the same as [`Self::constructor`], named for symmetry with [`Self::get_event`] and
+    /// [`Self::get_error`].
+    pub const fn get_constructor(&self) -> Option<&Constructor> {
+        self.constructor()
+    }
+
     pub(crate) fn get_from_name(&self, name: &str) -> Result<&Function> {
         self.abi
             .function(name)
@@ -115,8 +236,160 @@ impl Interface {
             .ok_or_else(|| Error::UnknownSelector(*selector))
     }
 
+    pub(crate) fn get_event_from_selector(&self, selector: &B256) -> Result<&Event> {
+        self.events
+            .get(selector)
+            .map(|(name, index)| &self.abi.events[name][*index])
+            .ok_or_else(|| Error::UnknownEventSelector(*selector))
+    }
+
     /// Create a [`ContractInstance`] from this ABI for a contract at the given address.
     pub const fn connect<P>(self, address: Address, provider: P) -> ContractInstance<P> {
         ContractInstance::new(address, provider, self)
     }
 }
+
+/// Utility function for creating a mapping between a unique signature and a
+/// name-index pair for accessing contract ABI items.
+fn create_mapping<T, S, F>(
+    elements: &BTreeMap<String, Vec<T>>,
+    signature: F,
+) -> HashMap<S, (String, usize)>
+where
+    S: Hash + Eq,
+    F: Fn(&T) -> S + Copy,
+{
+    elements
+        .iter()
+        .flat_map(|(name, sub_elements)| {
+            sub_elements
+                .iter()
+                .enumerate()
+                .map(move |(index, element)| (signature(element), (name.to_owned(), index)))
+        })
+        .collect()
+}
+
+const fn is_view(function: &Function) -> bool {
+    matches!(function.state_mutability, StateMutability::View | StateMutability::Pure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_and_counts() {
+        let interface = Interface::from(
+            JsonAbi::parse([
+                "function transfer(address to, uint256 amount) returns (bool)",
+                "function balanceOf(address owner) returns (uint256)",
+                "function safeTransferFrom(address from, address to, uint256 id)",
+                "function safeTransferFrom(address from, address to, uint256 id, bytes data)",
+                "event Transfer(address indexed from, address indexed to, uint256 value)",
+                "error InsufficientBalance(uint256 available, uint256 required)",
+                "error InsufficientBalance(uint256 available)",
+            ])
+            .unwrap(),
+        );
+        assert_eq!(interface.function_names(), ["balanceOf", "safeTransferFrom", "transfer"]);
+        assert_eq!(interface.event_names(), ["Transfer"]);
+        assert_eq!(interface.error_names(), ["InsufficientBalance"]);
+        assert_eq!(interface.function_count(), 4);
+        assert_eq!(interface.overload_count("safeTransferFrom"), 2);
+        assert_eq!(interface.overload_count("transfer"), 1);
+        assert_eq!(interface.overload_count("approve"), 0);
+
+        let empty = Interface::from(JsonAbi::new());
+        assert!(empty.function_names().is_empty());
+        assert_eq!(empty.function_count(), 0);
+    }
+
+    #[test]
+    fn special_functions() {
+        let abi = r#"[
+            {"type": "constructor", "inputs": [{"name": "supply", "type": "uint256"}], "stateMutability": "nonpayable"},
+            {"type": "fallback", "stateMutability": "nonpayable"},
+            {"type": "receive", "stateMutability": "payable"}
+        ]"#;
+        let interface = Interface::from(serde_json::from_str::<JsonAbi>(abi).unwrap());
+        assert_eq!(interface.constructor().unwrap().inputs.len(), 1);
+        assert!(interface.has_fallback());
+        assert!(interface.has_receive());
+
+        let empty = Interface::from(JsonAbi::new());
+        assert!(empty.constructor().is_none());
+        assert!(!empty.has_fallback() && !empty.has_receive());
+    }
+
+    #[test]
+    fn get_event_and_error() {
+        let interface = Interface::from(
+            JsonAbi::parse([
+                "event Transfer(address indexed from, address indexed to, uint256 value)",
+                "error InsufficientBalance(uint256 available, uint256 required)",
+            ])
+            .unwrap(),
+        );
+        assert_eq!(interface.get_event("Transfer").unwrap().inputs.len(), 3);
+        assert_eq!(
+            interface.get_error("InsufficientBalance").unwrap().signature(),
+            "InsufficientBalance(uint256,uint256)"
+        );
+
+        let err = interface.get_event("Approval").unwrap_err();
+        assert!(matches!(err, Error::UnknownEvent(name) if name == "Approval"));
+        let err = interface.get_error("Transfer").unwrap_err();
+        assert!(matches!(err, Error::UnknownError(name) if name == "Transfer"));
+        assert!(interface.get_constructor().is_none());
+
+        let abi = r#"[{"type": "constructor", "inputs": [{"name": "supply", "type": "uint256"}], "stateMutability": "nonpayable"}]"#;
+        let interface = Interface::from(serde_json::from_str::<JsonAbi>(abi).unwrap());
+        assert_eq!(interface.get_constructor().unwrap().inputs[0].name, "supply");
+    }
+
+    #[test]
+    fn functions_by_state_mutability() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[
+                {"type": "function", "name": "balanceOf", "stateMutability": "view", "inputs": [{"name": "owner", "type": "address"}], "outputs": [{"name": "", "type": "uint256"}]},
+                {"type": "function", "name": "decimals", "stateMutability": "pure", "inputs": [], "outputs": [{"name": "", "type": "uint8"}]},
+                {"type": "function", "name": "deposit", "stateMutability": "payable", "inputs": [], "outputs": []},
+                {"type": "function", "name": "transfer", "stateMutability": "nonpayable", "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}], "outputs": [{"name": "", "type": "bool"}]}
+            ]"#,
+        )
+        .unwrap();
+        let interface = Interface::from(abi);
+        let view: Vec<_> = interface.view_functions().map(|function| &function.name).collect();
+        assert_eq!(view, ["balanceOf", "decimals"]);
+        let mutating: Vec<_> =
+            interface.mutating_functions().map(|function| &function.name).collect();
+        assert_eq!(mutating, ["deposit", "transfer"]);
+    }
+
+    #[test]
+    fn to_sol_interface() {
+        let interface = Interface::from(
+            JsonAbi::parse([
+                "function transfer(address to, uint256 amount) returns (bool)",
+                "event Transfer(address indexed from, address indexed to, uint256 value)",
+                "error InsufficientBalance(uint256 available, uint256 required)",
+            ])
+            .unwrap(),
+        );
+        assert_eq!(
+            interface.to_sol_interface("IToken"),
+            "interface IToken {
+    error InsufficientBalance(uint256 available, uint256 required);
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    function transfer(address to, uint256 amount) external returns (bool);
+}"
+        );
+        assert_eq!(
+            Interface::from(JsonAbi::new()).to_sol_interface("IEmpty"),
+            "interface IEmpty {}"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{CallBuilder, ContractInstance, Result};
+use alloy_dyn_abi::{DynSolType, DynSolValue, Error as AbiError};
+use alloy_json_abi::Param;
+use alloy_providers::provider::TempProvider;
+use serde_json::Value;
+
+impl<P: TempProvider + Clone> ContractInstance<P> {
+    /// Returns a transaction builder for the provided function name, coercing the JSON arguments
+    /// to the function's parameter types.
+    ///
+    /// Tuple, i.e. struct, arguments can be JSON arrays of their components in order, or JSON
+    /// objects keyed by component name. Arrays and tuples are coerced recursively, and all other
+    /// values are coerced from their string representation as in [`DynSolType::coerce_str`], e.g.
+    /// `"1 ether"` for a `uint256`.
+    pub fn function_json(&self, name: &str, args: &[Value]) -> Result<CallBuilder<P>> {
+        let function = self.get_from_name(name)?;
+        if function.inputs.len() != args.len() {
+            return Err(AbiError::EncodeLengthMismatch {
+                expected: function.inputs.len(),
+                actual: args.len(),
+            }
+            .into());
+        }
+        let args = function
+            .inputs
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| coerce_json(&param.ty, &param.components, arg))
+            .collect::<Result<Vec<_>>>()?;
+        self.function(name, &args)
+    }
+
+    /// Calls the provided function via `eth_call` with JSON arguments, returning its decoded
+    /// output.
+    ///
+    /// See [`Self::function_json`] for how the arguments are coerced.
+    pub async fn call_json(&self, name: &str, args: &[Value]) -> Result<Vec<DynSolValue>> {
+        self.function_json(name, args)?.call().await
+    }
+}
+
+/// Coerces a JSON value to the type `ty`, whose tuple components, if any, are `components`.
+fn coerce_json(ty: &str, components: &[Param], value: &Value) -> Result<DynSolValue> {
+    let mismatch =
+        || AbiError::TypeMismatch { expected: ty.to_string(), actual: value.to_string() };
+
+    // Arrays, e.g. `tuple[2][]`.
+    if let Some(inner) = ty.strip_suffix(']') {
+        let (element, size) = inner.rsplit_once('[').ok_or_else(mismatch)?;
+        let values = value.as_array().ok_or_else(mismatch)?;
+        let values = values
+            .iter()
+            .map(|value| coerce_json(element, components, value))
+            .collect::<Result<Vec<_>>>()?;
+        return match size {
+            "" => Ok(DynSolValue::Array(values)),
+            size => {
+                let size: usize = size.parse().map_err(|_| mismatch())?;
+                if values.len() != size {
+                    return Err(AbiError::EncodeLengthMismatch {
+                        expected: size,
+                        actual: values.len(),
+                    }
+                    .into());
+                }
+                Ok(DynSolValue::FixedArray(values))
+            }
+        };
+    }
+
+    if ty == "tuple" {
+        let values = match value {
+            Value::Array(values) if values.len() == components.len() => {
+                components.iter().zip(values).collect::<Vec<_>>()
+            }
+            Value::Array(values) => {
+                return Err(AbiError::EncodeLengthMismatch {
+                    expected: components.len(),
+                    actual: values.len(),
+                }
+                .into())
+            }
+            Value::Object(fields) => components
+                .iter()
+                .map(|param| {
+                    let field = fields.get(&param.name).ok_or_else(|| AbiError::TypeMismatch {
+                        expected: format!("tuple with component `{}`", param.name),
+                        actual: value.to_string(),
+                    })?;
+                    Ok((param, field))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => return Err(mismatch().into()),
+        };
+        return values
+            .into_iter()
+            .map(|(param, value)| coerce_json(&param.ty, &param.components, value))
+            .collect::<Result<Vec<_>>>()
+            .map(DynSolValue::Tuple);
+    }
+
+    let value = match value {
+        Value::String(value) => value.clone(),
+        Value::Number(_) | Value::Bool(_) => value.to_string(),
+        _ => return Err(mismatch().into()),
+    };
+    Ok(DynSolType::parse(ty)?.coerce_str(&value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock::MockTransport, Error, Interface};
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::{Address, U256};
+    use serde_json::json;
+
+    #[test]
+    fn function_json() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "function",
+                "name": "submit",
+                "inputs": [
+                    {
+                        "name": "order",
+                        "type": "tuple",
+                        "components": [
+                            { "name": "maker", "type": "address" },
+                            { "name": "amount", "type": "uint256" }
+                        ]
+                    },
+                    {
+                        "name": "hooks",
+                        "type": "tuple[]",
+                        "components": [
+                            { "name": "name", "type": "string" },
+                            { "name": "enabled", "type": "bool" }
+                        ]
+                    },
+                    { "name": "deadline", "type": "uint64" }
+                ],
+                "outputs": [],
+                "stateMutability": "nonpayable"
+            }]"#,
+        )
+        .unwrap();
+        let contract = Interface::from(abi)
+            .connect(Address::with_last_byte(1), MockTransport::provider(|_, _| unreachable!()));
+        let maker = Address::with_last_byte(2);
+
+        let args = [
+            json!({ "maker": maker, "amount": "1 gwei" }),
+            json!([["first", true], { "name": "second", "enabled": false }]),
+            json!(1700000000),
+        ];
+        let call = contract.function_json("submit", &args).unwrap();
+        let expected = contract
+            .function(
+                "submit",
+                &[
+                    DynSolValue::Tuple(vec![
+                        DynSolValue::Address(maker),
+                        DynSolValue::Uint(U256::from(1_000_000_000), 256),
+                    ]),
+                    DynSolValue::Array(vec![
+                        DynSolValue::Tuple(vec!["first".to_string().into(), true.into()]),
+                        DynSolValue::Tuple(vec!["second".to_string().into(), false.into()]),
+                    ]),
+                    DynSolValue::Uint(U256::from(1700000000), 64),
+                ],
+            )
+            .unwrap();
+        assert_eq!(call.calldata(), expected.calldata());
+
+        let missing = [json!({ "maker": maker }), json!([]), json!(0)];
+        let err = contract.function_json("submit", &missing).unwrap_err();
+        assert!(matches!(err, Error::AbiError(_)), "{err}");
+        let wrong_arity = [json!([maker]), json!([]), json!(0)];
+        assert!(contract.function_json("submit", &wrong_arity).is_err());
+        assert!(contract.function_json("submit", &args[..2]).is_err());
+    }
+}
@@ -15,14 +15,71 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+// Allows the derive macros to refer to `::alloy_dyn_contract` in this crate's tests.
+#[cfg(test)]
+extern crate self as alloy_dyn_contract;
+
+mod access;
+
 mod error;
 pub use error::*;
 
 mod interface;
 pub use interface::*;
 
+mod diff;
+pub use diff::*;
+
 mod instance;
 pub use instance::*;
 
 mod call;
 pub use call::*;
+
+mod decoder;
+pub use decoder::*;
+
+mod deploy;
+pub use deploy::*;
+
+mod blob_cost;
+pub use blob_cost::*;
+
+mod dry_run;
+pub use dry_run::*;
+
+mod json;
+
+mod logs;
+
+mod multicall;
+pub use multicall::*;
+
+mod fee;
+pub use fee::*;
+
+mod registry;
+pub use registry::*;
+
+mod retry;
+pub use retry::*;
+
+mod revert;
+pub use revert::*;
+
+mod storage;
+pub use storage::*;
+
+mod trace;
+pub use trace::*;
+
+mod typescript;
+
+mod pending;
+pub use pending::*;
+
+mod output;
+pub use output::*;
+
+#[cfg(test)]
+mod mock;
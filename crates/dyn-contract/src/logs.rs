@@ -0,0 +1,169 @@
+use crate::{ContractInstance, Error, Result, RetryPolicy};
+use alloy_dyn_abi::{DecodedEvent, EventExt};
+use alloy_primitives::LogData;
+use alloy_providers::provider::TempProvider;
+use alloy_rpc_types::{Filter, Log};
+use futures_util::{stream, Stream, TryStreamExt};
+
+impl<P: TempProvider + Clone> ContractInstance<P> {
+    /// Returns a [`Stream`] of the given event emitted by this contract between `from_block` and
+    /// `to_block`, inclusive, decoded using the contract's ABI.
+    ///
+    /// The block range is split into chunks of `page_size` blocks, which are fetched sequentially
+    /// via one `eth_getLogs` request each, so that no single request exceeds the node's result
+    /// limit. A request that failed with an RPC error, i.e. a transport failure or an error
+    /// response from the node such as "query returned more than 10000 results", is retried up to
+    /// 3 times, with the delays of the default [`RetryPolicy`], before the error is yielded and
+    /// the stream ends.
+    ///
+    /// If there are multiple events with the same name due to overloading, the first match is
+    /// used.
+    pub fn logs_paginated<'a>(
+        &'a self,
+        event_name: &str,
+        from_block: u64,
+        to_block: u64,
+        page_size: u64,
+    ) -> impl Stream<Item = Result<DecodedEvent>> + 'a {
+        let event = self.get_event(event_name).cloned();
+        let page_size = page_size.max(1);
+        stream::try_unfold((event, Some(from_block)), move |(event, from)| async move {
+            let event = event?;
+            let Some(from) = from.filter(|from| *from <= to_block) else {
+                return Ok::<_, Error>(None);
+            };
+            let to = from.saturating_add(page_size - 1).min(to_block);
+
+            let mut filter = Filter::new().address(self.address()).from_block(from).to_block(to);
+            if !event.anonymous {
+                filter = filter.event_signature(event.selector());
+            }
+            let events = self
+                .get_logs_with_retry(filter)
+                .await?
+                .into_iter()
+                .map(|log| event.decode_log(&LogData::new_unchecked(log.topics, log.data), true))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Some((events, (Ok(event), to.checked_add(1)))))
+        })
+        .map_ok(|events| stream::iter(events.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    async fn get_logs_with_retry(&self, filter: Filter) -> Result<Vec<Log>> {
+        let policy = RetryPolicy { max_retries: 3, ..Default::default() };
+        policy
+            .run(
+                || async { Ok(self.provider_ref().get_logs(filter.clone()).await?) },
+                |err| matches!(err, Error::TransportError(_)),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock::MockTransport, Error, Interface};
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_json_abi::JsonAbi;
+    use alloy_json_rpc::{ErrorPayload, RpcError};
+    use alloy_primitives::{Address, Bytes, U256, U64};
+    use alloy_rpc_types::Log;
+    use alloy_transport::TransportErrorKind;
+    use futures_util::TryStreamExt;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test(start_paused = true)]
+    async fn logs_paginated() {
+        let abi = JsonAbi::parse(["event Ping(uint256 block)"]).unwrap();
+        let topic = abi.event("Ping").unwrap()[0].selector();
+        let address = Address::with_last_byte(1);
+        let pages = Arc::new(Mutex::new(Vec::new()));
+        let provider = MockTransport::provider({
+            let pages = pages.clone();
+            move |method, params| {
+                assert_eq!(method, "eth_getLogs");
+                assert_eq!(params[0]["address"], json!(address));
+                assert_eq!(params[0]["topics"][0], json!(topic));
+                let block = |key: &str| {
+                    serde_json::from_value::<U64>(params[0][key].clone()).unwrap().to::<u64>()
+                };
+                let (from, to) = (block("fromBlock"), block("toBlock"));
+
+                // The first attempt of the third page fails.
+                let mut pages = pages.lock().unwrap();
+                pages.push((from, to));
+                if from == 200 && pages.iter().filter(|page| page.0 == 200).count() == 1 {
+                    return Err(TransportErrorKind::custom_str("connection reset"));
+                }
+
+                // One log every other block.
+                let logs = (from..=to)
+                    .step_by(2)
+                    .map(|block| Log {
+                        address,
+                        topics: vec![topic],
+                        data: Bytes::from(U256::from(block).to_be_bytes_vec()),
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>();
+                Ok(json!(logs))
+            }
+        });
+        let contract = Interface::from(abi).connect(address, provider);
+
+        let events: Vec<_> =
+            contract.logs_paginated("Ping", 0, 999, 200).try_collect().await.unwrap();
+        assert_eq!(events.len(), 500);
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(event.body, [DynSolValue::Uint(U256::from(i * 2), 256)]);
+        }
+        assert_eq!(
+            *pages.lock().unwrap(),
+            [(0, 199), (200, 399), (200, 399), (400, 599), (600, 799), (800, 999)]
+        );
+
+        let err = contract.logs_paginated("Pong", 0, 999, 200).try_collect::<Vec<_>>().await;
+        assert!(matches!(err, Err(Error::UnknownEvent(name)) if name == "Pong"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn logs_paginated_retries() {
+        let attempts = Arc::new(Mutex::new(0));
+        let provider = MockTransport::provider({
+            let attempts = attempts.clone();
+            move |_, _| {
+                *attempts.lock().unwrap() += 1;
+                Err(TransportErrorKind::custom_str("connection reset"))
+            }
+        });
+        let abi = JsonAbi::parse(["event Ping(uint256 block)"]).unwrap();
+        let contract = Interface::from(abi.clone()).connect(Address::with_last_byte(1), provider);
+
+        let result = contract.logs_paginated("Ping", 0, 999, 200).try_collect::<Vec<_>>().await;
+        assert!(matches!(result, Err(Error::TransportError(_))));
+        assert_eq!(*attempts.lock().unwrap(), 4);
+
+        // Error responses are retried as well.
+        *attempts.lock().unwrap() = 0;
+        let provider = MockTransport::provider({
+            let attempts = attempts.clone();
+            move |_, _| {
+                *attempts.lock().unwrap() += 1;
+                Err(RpcError::ErrorResp(ErrorPayload {
+                    code: -32005,
+                    message: "query returned more than 10000 results".into(),
+                    data: None,
+                }))
+            }
+        });
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+        let result = contract.logs_paginated("Ping", 0, 999, 200).try_collect::<Vec<_>>().await;
+        assert!(matches!(
+            result,
+            Err(Error::TransportError(RpcError::ErrorResp(payload))) if payload.code == -32005
+        ));
+        assert_eq!(*attempts.lock().unwrap(), 4);
+    }
+}
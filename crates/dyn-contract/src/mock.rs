@@ -0,0 +1,87 @@
+//! Mock transport for testing contract interactions without a node.
+
+use alloy_json_rpc::{
+    RequestPacket, Response, ResponsePacket, ResponsePayload, RpcError, SerializedRequest,
+};
+use alloy_providers::provider::Provider;
+use alloy_transport::{TransportError, TransportFut, TransportResult};
+use serde_json::Value;
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::Service;
+
+type Handler = dyn Fn(&str, &Value) -> TransportResult<Value> + Send + Sync;
+
+/// A transport that answers every request by invoking a handler with the
+/// request's method and params.
+///
+/// Returning [`RpcError::ErrorResp`] from the handler produces a JSON-RPC error
+/// response, any other error is returned by the transport itself.
+#[derive(Clone)]
+pub(crate) struct MockTransport {
+    handler: Arc<Handler>,
+}
+
+impl MockTransport {
+    /// Create a new mock transport from the given handler.
+    pub(crate) fn new(
+        handler: impl Fn(&str, &Value) -> TransportResult<Value> + Send + Sync + 'static,
+    ) -> Self {
+        Self { handler: Arc::new(handler) }
+    }
+
+    /// Create a provider backed by a mock transport with the given handler.
+    pub(crate) fn provider(
+        handler: impl Fn(&str, &Value) -> TransportResult<Value> + Send + Sync + 'static,
+    ) -> Arc<Provider<Self>> {
+        Arc::new(Provider::new(Self::new(handler)))
+    }
+
+    fn respond(&self, req: &SerializedRequest) -> TransportResult<Response> {
+        let params = match req.params() {
+            Some(params) => serde_json::from_str(params.get())
+                .map_err(|e| TransportError::deser_err(e, params.get()))?,
+            None => Value::Null,
+        };
+        let payload = match (self.handler)(req.method(), &params) {
+            Ok(value) => ResponsePayload::Success(
+                serde_json::value::to_raw_value(&value).map_err(TransportError::ser_err)?,
+            ),
+            Err(RpcError::ErrorResp(err)) => ResponsePayload::Failure(err),
+            Err(err) => return Err(err),
+        };
+        Ok(Response { id: req.id().clone(), payload })
+    }
+}
+
+impl std::fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockTransport").finish_non_exhaustive()
+    }
+}
+
+impl Service<RequestPacket> for MockTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move {
+            match req {
+                RequestPacket::Single(req) => this.respond(&req).map(ResponsePacket::Single),
+                RequestPacket::Batch(reqs) => reqs
+                    .iter()
+                    .map(|req| this.respond(req))
+                    .collect::<TransportResult<_>>()
+                    .map(ResponsePacket::Batch),
+            }
+        })
+    }
+}
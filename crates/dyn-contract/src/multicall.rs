@@ -0,0 +1,182 @@
+use crate::{ContractInstance, Error, Result};
+use alloy_dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt};
+use alloy_json_abi::Function;
+use alloy_primitives::{address, Address, Bytes};
+use alloy_providers::provider::TempProvider;
+use alloy_rpc_types::{CallInput, CallRequest};
+use alloy_sol_types::SolCall;
+
+/// The address of the [Multicall3](https://github.com/mds1/multicall) contract, which is deployed
+/// at the same address on most chains.
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+mod multicall3 {
+    #![allow(missing_docs, unreachable_pub)]
+
+    alloy_sol_types::sol! {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Result3 {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calls) external payable returns (Result3[] returnData);
+    }
+}
+
+/// A function call with encoded arguments, to be batched with others through Multicall3, see
+/// [`ContractInstance::try_calls`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreparedCall {
+    target: Address,
+    function: Function,
+    calldata: Bytes,
+}
+
+impl PreparedCall {
+    /// Prepares a call of `function` on the contract at `target` with the given arguments.
+    pub fn new(target: Address, function: Function, args: &[DynSolValue]) -> Result<Self> {
+        let calldata = function.abi_encode_input(args)?.into();
+        Ok(Self { target, function, calldata })
+    }
+
+    /// Returns the address of the called contract.
+    pub const fn target(&self) -> Address {
+        self.target
+    }
+
+    /// Returns the called function.
+    pub const fn function(&self) -> &Function {
+        &self.function
+    }
+
+    /// Returns the ABI encoded calldata, including the selector.
+    pub const fn calldata(&self) -> &Bytes {
+        &self.calldata
+    }
+}
+
+impl<P> ContractInstance<P> {
+    /// Prepares a call of the provided function on this contract, to be batched with
+    /// [`ContractInstance::try_calls`].
+    ///
+    /// If there are multiple functions with the same name due to overloading, the first match is
+    /// used, as in [`ContractInstance::function`].
+    pub fn prepare_call(&self, name: &str, args: &[DynSolValue]) -> Result<PreparedCall> {
+        PreparedCall::new(self.address(), self.get_from_name(name)?.clone(), args)
+    }
+}
+
+impl<P: TempProvider> ContractInstance<P> {
+    /// Calls the given functions in a single `eth_call` through Multicall3's `aggregate3`,
+    /// returning their decoded outputs in the same order as `calls`.
+    ///
+    /// Each call succeeds or fails independently: a reverting call fails with
+    /// [`Error::TransactionReverted`] holding its revert data, see
+    /// [`Error::as_revert_reason`]. The whole batch only fails if the `eth_call` itself does,
+    /// e.g. because Multicall3 is not deployed at [`MULTICALL3_ADDRESS`] on this chain.
+    ///
+    /// The calls may target any contract; the address of this instance is not used.
+    pub async fn try_calls(
+        &self,
+        calls: Vec<PreparedCall>,
+    ) -> Result<Vec<Result<Vec<DynSolValue>>>> {
+        let call3s = calls
+            .iter()
+            .map(|call| multicall3::Call3 {
+                target: call.target,
+                allowFailure: true,
+                callData: call.calldata.to_vec(),
+            })
+            .collect();
+        let input = multicall3::aggregate3Call { calls: call3s }.abi_encode();
+        let request = CallRequest {
+            to: Some(MULTICALL3_ADDRESS),
+            input: CallInput::new(input.into()),
+            ..Default::default()
+        };
+        let output = self.provider_ref().call(request, None).await?;
+        let results = multicall3::aggregate3Call::abi_decode_returns(&output, true)?.returnData;
+        if results.len() != calls.len() {
+            return Err(alloy_dyn_abi::Error::EncodeLengthMismatch {
+                expected: calls.len(),
+                actual: results.len(),
+            }
+            .into());
+        }
+
+        Ok(calls
+            .iter()
+            .zip(results)
+            .map(|(call, result)| {
+                if result.success {
+                    call.function.abi_decode_output(&result.returnData, true).map_err(Into::into)
+                } else {
+                    Err(Error::TransactionReverted { revert_data: Some(result.returnData.into()) })
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::U256;
+    use alloy_sol_types::{Revert, SolError};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn try_calls() {
+        let abi = JsonAbi::parse(["function balanceOf(address owner) returns (uint256)"]).unwrap();
+        let owner = [DynSolValue::Address(Address::with_last_byte(9))];
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "eth_call");
+            assert_eq!(params[0]["to"], json!(MULTICALL3_ADDRESS));
+            let input: Bytes = serde_json::from_value(params[0]["input"].clone()).unwrap();
+            let calls = multicall3::aggregate3Call::abi_decode(&input, true).unwrap().calls;
+            let results = calls
+                .into_iter()
+                .map(|call| {
+                    assert!(call.allowFailure);
+                    // Only the token at address 1 implements `balanceOf`.
+                    if call.target == Address::with_last_byte(1) {
+                        multicall3::Result3 {
+                            success: true,
+                            returnData: U256::from(100).to_be_bytes_vec(),
+                        }
+                    } else {
+                        multicall3::Result3 {
+                            success: false,
+                            returnData: Revert::from("not implemented").abi_encode(),
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            let output = multicall3::aggregate3Call::abi_encode_returns(&(results,));
+            Ok(json!(Bytes::from(output)))
+        });
+        let token = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let calls = vec![
+            token.prepare_call("balanceOf", &owner).unwrap(),
+            token.at(Address::with_last_byte(2)).prepare_call("balanceOf", &owner).unwrap(),
+        ];
+        assert_eq!(calls[1].target(), Address::with_last_byte(2));
+        let results = token.try_calls(calls).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), &[DynSolValue::Uint(U256::from(100), 256)]);
+        let err = results[1].as_ref().unwrap_err();
+        assert_eq!(err.as_revert_reason(), Some("not implemented"));
+
+        assert!(token.try_calls(vec![]).await.unwrap().is_empty());
+        assert!(matches!(token.prepare_call("approve", &[]), Err(Error::UnknownFunction(_))));
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{Error, Result};
+use alloy_dyn_abi::{DynSolValue, Error as AbiError};
+use alloy_primitives::{Address, Bytes, FixedBytes, Function, I256, U256};
+
+/// Derives [`FromDynSolValues`] and [`FromDynSolValue`] for a struct.
+///
+/// The values returned by a contract call are mapped to the struct's fields by position, with
+/// each field converted using its [`FromDynSolValue`] implementation. Both structs with named
+/// fields and tuple structs are supported.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy_dyn_contract::SolOutput;
+/// use alloy_primitives::U256;
+///
+/// #[derive(SolOutput)]
+/// struct Reserves {
+///     reserve0: U256,
+///     reserve1: U256,
+///     timestamp: u32,
+/// }
+/// ```
+pub use alloy_dyn_contract_derive::SolOutput;
+
+/// A type that can be converted from a single [`DynSolValue`].
+pub trait FromDynSolValue: Sized {
+    /// Converts the value into `Self`, failing if the value's type does not match.
+    fn from_dyn_sol_value(value: DynSolValue) -> Result<Self>;
+}
+
+/// A type that can be converted from the values returned by a contract call.
+///
+/// This is usually derived with [`SolOutput`].
+pub trait FromDynSolValues: Sized {
+    /// Converts the values into `Self`, failing if their number or types do not match.
+    fn from_dyn_sol_values(values: Vec<DynSolValue>) -> Result<Self>;
+}
+
+fn type_mismatch(expected: impl Into<String>, actual: &DynSolValue) -> Error {
+    let actual = actual.sol_type_name().unwrap_or_default().into_owned();
+    Error::AbiError(AbiError::TypeMismatch { expected: expected.into(), actual })
+}
+
+impl FromDynSolValue for DynSolValue {
+    fn from_dyn_sol_value(value: DynSolValue) -> Result<Self> {
+        Ok(value)
+    }
+}
+
+macro_rules! impl_from_dyn_sol_value {
+    ($($ty:ty => $expected:literal, $pat:pat => $out:expr;)*) => {$(
+        impl FromDynSolValue for $ty {
+            fn from_dyn_sol_value(value: DynSolValue) -> Result<Self> {
+                match value {
+                    $pat => Ok($out),
+                    value => Err(type_mismatch($expected, &value)),
+                }
+            }
+        }
+    )*};
+}
+
+impl_from_dyn_sol_value! {
+    bool => "bool", DynSolValue::Bool(b) => b;
+    Address => "address", DynSolValue::Address(address) => address;
+    Function => "function", DynSolValue::Function(function) => function;
+    U256 => "uint256", DynSolValue::Uint(u, _) => u;
+    I256 => "int256", DynSolValue::Int(i, _) => i;
+    String => "string", DynSolValue::String(s) => s;
+    Bytes => "bytes", DynSolValue::Bytes(bytes) => bytes.into();
+}
+
+macro_rules! impl_from_dyn_sol_value_int {
+    ($($variant:ident => $($ty:ty),+;)*) => {$($(
+        impl FromDynSolValue for $ty {
+            fn from_dyn_sol_value(value: DynSolValue) -> Result<Self> {
+                let expected = std::any::type_name::<$ty>();
+                match value {
+                    DynSolValue::$variant(n, _) => {
+                        n.try_into().map_err(|_| type_mismatch(expected, &value))
+                    }
+                    value => Err(type_mismatch(expected, &value)),
+                }
+            }
+        }
+    )+)*};
+}
+
+impl_from_dyn_sol_value_int! {
+    Uint => u8, u16, u32, u64, u128;
+    Int => i8, i16, i32, i64, i128;
+}
+
+impl<const N: usize> FromDynSolValue for FixedBytes<N> {
+    fn from_dyn_sol_value(value: DynSolValue) -> Result<Self> {
+        match value {
+            DynSolValue::FixedBytes(word, size) if size == N => Ok(Self::from_slice(&word[..N])),
+            value => Err(type_mismatch(format!("bytes{N}"), &value)),
+        }
+    }
+}
+
+impl<T: FromDynSolValue> FromDynSolValue for Vec<T> {
+    fn from_dyn_sol_value(value: DynSolValue) -> Result<Self> {
+        match value {
+            DynSolValue::Array(values) => values.into_iter().map(T::from_dyn_sol_value).collect(),
+            value => Err(type_mismatch("array", &value)),
+        }
+    }
+}
+
+impl<T: FromDynSolValue, const N: usize> FromDynSolValue for [T; N] {
+    fn from_dyn_sol_value(value: DynSolValue) -> Result<Self> {
+        match value {
+            DynSolValue::FixedArray(values) if values.len() == N => {
+                let values =
+                    values.into_iter().map(T::from_dyn_sol_value).collect::<Result<Vec<_>>>()?;
+                Ok(values.try_into().unwrap_or_else(|_| unreachable!("checked length")))
+            }
+            value => Err(type_mismatch(format!("fixed array of length {N}"), &value)),
+        }
+    }
+}
+
+/// Implementation details of the [`SolOutput`] derive macro.
+#[doc(hidden)]
+pub mod private {
+    use super::*;
+
+    pub use alloy_dyn_abi::DynSolValue;
+
+    /// Checks that exactly `len` values were returned.
+    pub fn expect_len(
+        values: Vec<DynSolValue>,
+        len: usize,
+    ) -> Result<std::vec::IntoIter<DynSolValue>> {
+        if values.len() != len {
+            return Err(Error::AbiError(AbiError::TypeMismatch {
+                expected: format!("{len} values"),
+                actual: format!("{} values", values.len()),
+            }));
+        }
+        Ok(values.into_iter())
+    }
+
+    /// Unwraps the values of a tuple.
+    pub fn expect_tuple(value: DynSolValue) -> Result<Vec<DynSolValue>> {
+        match value {
+            DynSolValue::Tuple(values) => Ok(values),
+            value => Err(type_mismatch("tuple", &value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_dyn_abi::FunctionExt;
+    use alloy_json_abi::JsonAbi;
+
+    #[derive(Debug, PartialEq, SolOutput)]
+    struct Reserves {
+        reserve0: U256,
+        reserve1: u128,
+        timestamp: u32,
+    }
+
+    #[derive(Debug, PartialEq, SolOutput)]
+    struct Position(Address, Reserves, Vec<bool>);
+
+    #[test]
+    fn derive_named_and_tuple_structs() {
+        let reserves = vec![
+            DynSolValue::Uint(U256::from(1), 112),
+            DynSolValue::Uint(U256::from(2), 112),
+            DynSolValue::Uint(U256::from(3), 32),
+        ];
+        let position = Position::from_dyn_sol_values(vec![
+            DynSolValue::Address(Address::with_last_byte(1)),
+            DynSolValue::Tuple(reserves),
+            DynSolValue::Array(vec![DynSolValue::Bool(true), DynSolValue::Bool(false)]),
+        ])
+        .unwrap();
+        assert_eq!(
+            position,
+            Position(
+                Address::with_last_byte(1),
+                Reserves { reserve0: U256::from(1), reserve1: 2, timestamp: 3 },
+                vec![true, false],
+            )
+        );
+    }
+
+    #[test]
+    fn derive_mismatches() {
+        let err = Reserves::from_dyn_sol_values(vec![DynSolValue::Bool(true)]).unwrap_err();
+        assert!(matches!(err, Error::AbiError(AbiError::TypeMismatch { .. })));
+
+        let err = Reserves::from_dyn_sol_values(vec![
+            DynSolValue::Uint(U256::from(1), 112),
+            DynSolValue::Uint(U256::from(2), 112),
+            DynSolValue::Uint(U256::MAX, 256),
+        ])
+        .unwrap_err();
+        assert!(matches!(err, Error::AbiError(AbiError::TypeMismatch { .. })));
+    }
+
+    #[tokio::test]
+    async fn call_into() {
+        let abi = JsonAbi::parse([
+            "function getReserves() returns (uint112 reserve0, uint112 reserve1, uint32 timestamp)",
+        ])
+        .unwrap();
+        let function = abi.function("getReserves").unwrap()[0].clone();
+        let provider = MockTransport::provider(move |method, _| {
+            assert_eq!(method, "eth_call");
+            let output = function
+                .abi_encode_output(&[
+                    DynSolValue::Uint(U256::from(10), 112),
+                    DynSolValue::Uint(U256::from(20), 112),
+                    DynSolValue::Uint(U256::from(30), 32),
+                ])
+                .unwrap();
+            Ok(Bytes::from(output).to_string().into())
+        });
+        let contract = Interface::from(abi).connect(Address::ZERO, provider);
+
+        let reserves: Reserves =
+            contract.function("getReserves", &[]).unwrap().call_into().await.unwrap();
+        assert_eq!(reserves, Reserves { reserve0: U256::from(10), reserve1: 20, timestamp: 30 });
+    }
+}
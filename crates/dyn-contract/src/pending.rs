@@ -0,0 +1,219 @@
+use crate::{Error, Result};
+use alloy_primitives::{TxHash, U256};
+use alloy_providers::provider::TempProvider;
+use alloy_rpc_types::{Transaction, TransactionReceipt};
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+    time::Duration,
+};
+
+/// The default interval at which a [`PendingTransaction`] polls for its receipt.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(7);
+
+/// A transaction that was broadcast to the network, returned by
+/// [`CallBuilder::send`](crate::CallBuilder::send).
+///
+/// The pending transaction can be `.await`ed directly which is equivalent to invoking
+/// [`PendingTransaction::get_receipt`].
+#[derive(Clone, Debug)]
+pub struct PendingTransaction<P> {
+    provider: P,
+    tx_hash: TxHash,
+    confirmations: u64,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl<P> PendingTransaction<P> {
+    /// Creates a new pending transaction with the given hash, waiting for a single confirmation.
+    pub const fn new(provider: P, tx_hash: TxHash) -> Self {
+        Self {
+            provider,
+            tx_hash,
+            confirmations: 1,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: None,
+        }
+    }
+
+    /// Returns the hash of the transaction.
+    pub const fn tx_hash(&self) -> TxHash {
+        self.tx_hash
+    }
+
+    /// Returns the number of confirmations to wait for.
+    pub const fn confirmations(&self) -> u64 {
+        self.confirmations
+    }
+
+    /// Sets the number of confirmations to wait for, i.e. the number of blocks, including the
+    /// one the transaction was included in, that must be mined.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub const fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets the interval at which the receipt and the latest block number are polled.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets how long to wait for the transaction to be mined and confirmed before failing with
+    /// [`Error::ReceiptTimeout`]. There is no timeout by default.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+impl<P: TempProvider> PendingTransaction<P> {
+    /// Waits for the transaction to be included and reach the configured number of
+    /// confirmations, returning its receipt.
+    ///
+    /// # Note
+    ///
+    /// The receipt of a reverted transaction is returned as well, check its `status_code`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::TransactionDropped`] if the node does not know the transaction while
+    /// it has no receipt, and with [`Error::ReceiptTimeout`] if the transaction is not confirmed
+    /// within the [timeout](Self::with_timeout).
+    pub async fn get_receipt(self) -> Result<TransactionReceipt> {
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.poll_receipt())
+                .await
+                .map_err(|_| Error::ReceiptTimeout(self.tx_hash))?,
+            None => self.poll_receipt().await,
+        }
+    }
+
+    async fn poll_receipt(&self) -> Result<TransactionReceipt> {
+        loop {
+            match self.provider.get_transaction_receipt(self.tx_hash).await? {
+                Some(receipt) => {
+                    if let Some(included) = receipt.block_number {
+                        let latest = U256::from(self.provider.get_block_number().await?);
+                        let confirmations = latest.saturating_sub(included) + U256::from(1);
+                        if confirmations >= U256::from(self.confirmations) {
+                            return Ok(receipt);
+                        }
+                    }
+                }
+                None => {
+                    let tx: Option<Transaction> = self
+                        .provider
+                        .raw_request("eth_getTransactionByHash", (self.tx_hash,))
+                        .await?;
+                    if tx.is_none() {
+                        return Err(Error::TransactionDropped(self.tx_hash));
+                    }
+                }
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+/// [`PendingTransaction`] can be turned into a [`Future`] automatically with `.await`.
+///
+/// Defaults to calling [`PendingTransaction::get_receipt`].
+impl<P> IntoFuture for PendingTransaction<P>
+where
+    P: TempProvider + 'static,
+{
+    type Output = Result<TransactionReceipt>;
+
+    #[cfg(target_arch = "wasm32")]
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output>>>;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.get_receipt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::{Address, B256, U64};
+    use serde_json::json;
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_for_confirmations() {
+        let tx_hash = B256::with_last_byte(1);
+        let block = Arc::new(AtomicU64::new(9));
+        let provider = MockTransport::provider({
+            let block = block.clone();
+            move |method, params| match method {
+                "eth_chainId" => Ok(json!("0x1")),
+                "eth_sendTransaction" => Ok(json!(tx_hash)),
+                "eth_getTransactionReceipt" => {
+                    assert_eq!(params[0], json!(tx_hash));
+                    // The transaction is included in block 10.
+                    let block = block.fetch_add(1, Ordering::SeqCst) + 1;
+                    let receipt = (block >= 10).then(|| TransactionReceipt {
+                        transaction_hash: Some(tx_hash),
+                        block_number: Some(U256::from(10)),
+                        status_code: Some(U64::from(1)),
+                        ..Default::default()
+                    });
+                    Ok(serde_json::to_value(receipt).unwrap())
+                }
+                "eth_blockNumber" => Ok(json!(U64::from(block.load(Ordering::SeqCst)))),
+                "eth_getTransactionByHash" => Ok(known(tx_hash)),
+                _ => unreachable!("{method}"),
+            }
+        });
+        let abi = JsonAbi::parse(["function bump()"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+
+        let pending = contract.function("bump", &[]).unwrap().send().await.unwrap();
+        assert_eq!(pending.tx_hash(), tx_hash);
+
+        let receipt = pending.with_confirmations(3).await.unwrap();
+        assert_eq!(receipt.transaction_hash, Some(tx_hash));
+        assert_eq!(block.load(Ordering::SeqCst), 12);
+    }
+
+    fn known(tx_hash: B256) -> serde_json::Value {
+        serde_json::to_value(Transaction { hash: tx_hash, ..Default::default() }).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_or_timed_out() {
+        let tx_hash = B256::with_last_byte(1);
+        let polls = Arc::new(AtomicU64::new(0));
+        let provider = MockTransport::provider({
+            let polls = polls.clone();
+            move |method, _| match method {
+                "eth_getTransactionReceipt" => Ok(serde_json::Value::Null),
+                // The transaction is dropped after the second poll.
+                "eth_getTransactionByHash" if polls.fetch_add(1, Ordering::SeqCst) < 2 => {
+                    Ok(known(tx_hash))
+                }
+                "eth_getTransactionByHash" => Ok(serde_json::Value::Null),
+                _ => unreachable!("{method}"),
+            }
+        });
+
+        let pending = PendingTransaction::new(provider, tx_hash);
+        let err = pending.clone().with_timeout(DEFAULT_POLL_INTERVAL).await.unwrap_err();
+        assert!(matches!(err, Error::ReceiptTimeout(hash) if hash == tx_hash));
+        let err = pending.await.unwrap_err();
+        assert!(matches!(err, Error::TransactionDropped(hash) if hash == tx_hash));
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+    }
+}
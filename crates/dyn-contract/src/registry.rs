@@ -0,0 +1,22 @@
+use crate::Result;
+use alloy_json_abi::JsonAbi;
+use alloy_primitives::Address;
+use std::collections::HashMap;
+
+/// A source of contract ABIs by address, e.g. a block explorer or a local cache.
+///
+/// See [`ContractInstance::apply_upgrade_from_address`](crate::ContractInstance::apply_upgrade_from_address).
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait AbiRegistry: Send + Sync {
+    /// Returns the ABI of the contract at the given address, or `None` if it is not known.
+    async fn abi(&self, address: Address) -> Result<Option<JsonAbi>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl AbiRegistry for HashMap<Address, JsonAbi> {
+    async fn abi(&self, address: Address) -> Result<Option<JsonAbi>> {
+        Ok(self.get(&address).cloned())
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{Error, Result};
+use std::{future::Future, time::Duration};
+
+/// How [`CallBuilder::call`](crate::CallBuilder::call) retries `eth_call` requests that failed
+/// with a transient error, i.e. a transport failure or a [timeout](Error::Timeout).
+///
+/// Error responses from the node, such as reverts, are never retried, and neither are decoding
+/// failures.
+///
+/// [`ContractInstance::logs_paginated`](crate::ContractInstance::logs_paginated) retries failed
+/// `eth_getLogs` requests with the same delays, including error responses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of retries after the first attempt.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+    /// The factor by which the delay grows after each retry.
+    pub backoff: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_retries: 3, base_delay: Duration::from_millis(100), backoff: 2.0 }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the given retry, starting at `0`.
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay.mul_f64(self.backoff.powi(retry as i32))
+    }
+
+    /// Runs `attempt` until it succeeds, fails with an error rejected by `should_retry`, or all
+    /// retries failed, sleeping for the [delay](Self::delay) before each retry. Returns the last
+    /// result.
+    pub(crate) async fn run<T, F, Fut>(
+        &self,
+        mut attempt: F,
+        should_retry: impl Fn(&Error) -> bool,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut retries = 0;
+        loop {
+            match attempt().await {
+                Err(err) if retries < self.max_retries && should_retry(&err) => {
+                    tokio::time::sleep(self.delay(retries)).await;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Error {
+    /// Returns `true` if the request failed in a way that may succeed if it is sent again.
+    pub(crate) const fn is_transient(&self) -> bool {
+        match self {
+            Self::TransportError(err) => err.is_transport_error(),
+            Self::Timeout(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_json_abi::JsonAbi;
+    use alloy_json_rpc::{ErrorPayload, RpcError};
+    use alloy_primitives::{Address, Bytes, U256};
+    use alloy_transport::TransportErrorKind;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tokio::time::Instant;
+
+    #[test]
+    fn delay() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn with_retry() {
+        let attempts = Arc::new(Mutex::new(0));
+        let provider = MockTransport::provider({
+            let attempts = attempts.clone();
+            move |method, params| {
+                assert_eq!(method, "eth_call");
+                let mut attempts = attempts.lock().unwrap();
+                *attempts += 1;
+                match (params[0]["value"].as_str(), *attempts) {
+                    (Some("0x1"), _) => Err(RpcError::ErrorResp(ErrorPayload {
+                        code: 3,
+                        message: "execution reverted".into(),
+                        data: None,
+                    })),
+                    (_, 1 | 2) => Err(TransportErrorKind::custom_str("connection reset")),
+                    _ => Ok(json!(Bytes::from(U256::from(7).to_be_bytes_vec()))),
+                }
+            }
+        });
+        let abi = JsonAbi::parse(["function get() returns (uint256)"]).unwrap();
+        let contract = Interface::from(abi).connect(Address::with_last_byte(1), provider);
+        let policy = RetryPolicy { max_retries: 2, ..Default::default() };
+
+        // Two transport failures, then success.
+        let start = Instant::now();
+        let call = contract.function("get", &[]).unwrap().with_retry(policy);
+        assert_eq!(call.call().await.unwrap(), [DynSolValue::Uint(U256::from(7), 256)]);
+        assert_eq!(*attempts.lock().unwrap(), 3);
+        assert_eq!(start.elapsed(), Duration::from_millis(300));
+
+        // Reverts are not retried.
+        *attempts.lock().unwrap() = 0;
+        let call = call.value(U256::from(1));
+        assert!(matches!(call.call().await, Err(Error::TransportError(_))));
+        assert_eq!(*attempts.lock().unwrap(), 1);
+
+        // The last error is returned once all retries failed.
+        *attempts.lock().unwrap() = 0;
+        let call = contract
+            .function("get", &[])
+            .unwrap()
+            .with_retry(RetryPolicy { max_retries: 1, ..Default::default() });
+        let err = call.call().await.unwrap_err();
+        assert!(err.is_transient());
+        assert_eq!(*attempts.lock().unwrap(), 2);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{ContractInstance, Error, Interface, PendingTransaction, Result};
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_primitives::{Bytes, B256, U256, U64};
+use alloy_providers::provider::TempProvider;
+use alloy_rpc_types::{BlockId, CallInput, CallRequest};
+use alloy_sol_types::{Panic, Revert, SolError};
+
+/// The decoded reason of a revert, as returned by [`Interface::decode_revert`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum RevertReason {
+    /// A revert with an `Error(string)` message, e.g. from `require(condition, "message")`.
+    Message(String),
+    /// A `Panic(uint256)` with the given code, e.g. `0x11` for an arithmetic overflow.
+    Panic(U256),
+    /// A custom error of the contract's ABI.
+    CustomError {
+        /// The name of the error.
+        name: String,
+        /// The decoded parameters of the error.
+        args: Vec<DynSolValue>,
+    },
+    /// Revert data that matches none of the above. Empty if the contract reverted without
+    /// data, or the revert data is not known.
+    Unknown(Bytes),
+}
+
+impl Interface {
+    /// Decodes revert data as an `Error(string)` message, a `Panic(uint256)`, or one of the
+    /// contract's custom errors, in that order.
+    pub fn decode_revert(&self, data: &[u8]) -> RevertReason {
+        if let Ok(revert) = Revert::abi_decode(data, true) {
+            return RevertReason::Message(revert.reason);
+        }
+        if let Ok(panic) = Panic::abi_decode(data, true) {
+            return RevertReason::Panic(panic.code);
+        }
+        if data.len() >= 4 {
+            let (selector, params) = data.split_at(4);
+            let decoded =
+                self.abi().errors().find(|error| error.selector() == selector).and_then(|error| {
+                    Some((error.name.clone(), error.abi_decode_input(params, true).ok()?))
+                });
+            if let Some((name, args)) = decoded {
+                return RevertReason::CustomError { name, args };
+            }
+        }
+        RevertReason::Unknown(Bytes::copy_from_slice(data))
+    }
+}
+
+impl<P: TempProvider + Clone> ContractInstance<P> {
+    /// Waits for the transaction with the given hash to be included, returning the reason it
+    /// reverted with, or `None` if it succeeded.
+    ///
+    /// The receipt is polled as in [`PendingTransaction::get_receipt`]. Receipts do not include
+    /// revert data, so a reverted transaction is replayed via `eth_call` on the state before the
+    /// block it was included in, and the revert data of the replay is decoded with
+    /// [`Interface::decode_revert`]. If the replay does not revert, e.g. because earlier
+    /// transactions in the block changed the state, the reason is [`RevertReason::Unknown`] with
+    /// empty data.
+    pub async fn watch_for_revert(&self, tx_hash: B256) -> Result<Option<RevertReason>> {
+        let receipt = PendingTransaction::new(self.provider(), tx_hash).get_receipt().await?;
+        if receipt.status_code != Some(U64::ZERO) {
+            return Ok(None);
+        }
+
+        let tx = self.provider_ref().get_transaction_by_hash(tx_hash).await?;
+        let request = CallRequest {
+            from: Some(tx.from),
+            to: tx.to,
+            gas: Some(tx.gas),
+            value: Some(tx.value),
+            input: CallInput::new(tx.input),
+            ..Default::default()
+        };
+        let block =
+            receipt.block_number.map(|block| BlockId::from(block.to::<u64>().saturating_sub(1)));
+        let data = match self.provider_ref().call(request, block).await {
+            Ok(_) => Bytes::new(),
+            Err(err) => {
+                let err = Error::from(err);
+                match err.revert_data() {
+                    Some(data) => data,
+                    None if err.is_revert() => Bytes::new(),
+                    None => return Err(err),
+                }
+            }
+        };
+        Ok(Some(self.decode_revert(&data)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::Address;
+    use alloy_rpc_types::{Transaction, TransactionReceipt};
+    use serde_json::{json, Value};
+
+    fn abi() -> JsonAbi {
+        JsonAbi::parse([
+            "function withdraw(uint256 amount)",
+            "error InsufficientBalance(uint256 available, uint256 required)",
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn decode_revert() {
+        let interface = Interface::from(abi());
+        assert_eq!(
+            interface.decode_revert(&Revert::from("paused").abi_encode()),
+            RevertReason::Message("paused".into())
+        );
+        assert_eq!(
+            interface.decode_revert(&Panic::from(U256::from(0x11)).abi_encode()),
+            RevertReason::Panic(U256::from(0x11))
+        );
+
+        let error = &interface.abi().error("InsufficientBalance").unwrap()[0];
+        let args = [DynSolValue::Uint(U256::from(1), 256), DynSolValue::Uint(U256::from(2), 256)];
+        assert_eq!(
+            interface.decode_revert(&error.abi_encode_input(&args).unwrap()),
+            RevertReason::CustomError { name: "InsufficientBalance".into(), args: args.to_vec() }
+        );
+
+        assert_eq!(interface.decode_revert(&[]), RevertReason::Unknown(Bytes::new()));
+        assert_eq!(
+            interface.decode_revert(&[1, 2, 3, 4, 5]),
+            RevertReason::Unknown(Bytes::from_static(&[1, 2, 3, 4, 5]))
+        );
+    }
+
+    fn tx_provider(
+        status: u64,
+        revert_data: Bytes,
+    ) -> impl Fn(&str, &Value) -> alloy_transport::TransportResult<Value> {
+        let tx_hash = B256::with_last_byte(1);
+        move |method, params| match method {
+            "eth_getTransactionReceipt" => Ok(serde_json::to_value(TransactionReceipt {
+                transaction_hash: Some(tx_hash),
+                block_number: Some(U256::from(10)),
+                status_code: Some(U64::from(status)),
+                ..Default::default()
+            })
+            .unwrap()),
+            "eth_blockNumber" => Ok(json!(U64::from(10))),
+            "eth_getTransactionByHash" => Ok(serde_json::to_value(Transaction {
+                hash: tx_hash,
+                from: Address::with_last_byte(2),
+                to: Some(Address::with_last_byte(1)),
+                ..Default::default()
+            })
+            .unwrap()),
+            "eth_call" => {
+                assert_eq!(params[0]["from"], json!(Address::with_last_byte(2)));
+                // Replayed on the state after block 9, before the transaction was included.
+                assert_eq!(params[1], json!("0x9"));
+                Err(alloy_json_rpc::RpcError::ErrorResp(alloy_json_rpc::ErrorPayload {
+                    code: 3,
+                    message: "execution reverted".into(),
+                    data: Some(serde_json::value::to_raw_value(&revert_data).unwrap()),
+                }))
+            }
+            _ => unreachable!("{method}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn watch_for_revert() {
+        let tx_hash = B256::with_last_byte(1);
+        let reason = Bytes::from(Revert::from("paused").abi_encode());
+
+        let provider = MockTransport::provider(tx_provider(1, reason.clone()));
+        let contract = Interface::from(abi()).connect(Address::with_last_byte(1), provider);
+        assert_eq!(contract.watch_for_revert(tx_hash).await.unwrap(), None);
+
+        let provider = MockTransport::provider(tx_provider(0, reason));
+        let contract = Interface::from(abi()).connect(Address::with_last_byte(1), provider);
+        assert_eq!(
+            contract.watch_for_revert(tx_hash).await.unwrap(),
+            Some(RevertReason::Message("paused".into()))
+        );
+    }
+}
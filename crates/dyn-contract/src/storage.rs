@@ -0,0 +1,204 @@
+use crate::{ContractInstance, Error, Result};
+use alloy_dyn_abi::DynSolValue;
+use alloy_primitives::U256;
+use serde::{Deserialize, Deserializer};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// Computes the storage slots of a contract's state variables from the storage layout emitted by
+/// solc, i.e. the `storageLayout` output selection.
+///
+/// Variables are looked up by name. If several variables have the same name, e.g. because they
+/// are declared in different contracts of the inheritance hierarchy, the first one is used.
+#[derive(Clone, Debug)]
+pub struct StorageLayoutAnalyzer {
+    layout: StorageLayout,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct StorageLayout {
+    storage: Vec<StorageVariable>,
+    types: HashMap<String, StorageType>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct StorageVariable {
+    label: String,
+    offset: u8,
+    #[serde(deserialize_with = "from_str")]
+    slot: U256,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageType {
+    encoding: String,
+    #[serde(deserialize_with = "from_str")]
+    number_of_bytes: U256,
+    base: Option<String>,
+}
+
+fn from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+}
+
+impl StorageLayoutAnalyzer {
+    /// Parses a storage layout JSON, as emitted by solc.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map(|layout| Self { layout })
+            .map_err(Error::InvalidStorageLayout)
+    }
+
+    /// Parses a storage layout JSON value, as emitted by solc.
+    pub fn from_value(json: serde_json::Value) -> Result<Self> {
+        serde_json::from_value(json)
+            .map(|layout| Self { layout })
+            .map_err(Error::InvalidStorageLayout)
+    }
+
+    /// Returns the slot of the state variable with the given name and the offset, in bytes, of
+    /// the variable within the slot.
+    ///
+    /// Variables smaller than 32 bytes may be packed into the same slot as other variables.
+    pub fn slot_for_variable(&self, name: &str) -> Result<(U256, u8)> {
+        self.variable(name).map(|variable| (variable.slot, variable.offset))
+    }
+
+    /// Returns the storage slot of the value for `key` in the mapping state variable with the
+    /// given name.
+    ///
+    /// See [`ContractInstance::storage_slot_for_mapping`] for how the slot is computed.
+    pub fn slot_for_mapping_value(&self, mapping_name: &str, key: DynSolValue) -> Result<U256> {
+        let (variable, _) = self.variable_with_encoding(mapping_name, "mapping")?;
+        Ok(ContractInstance::<()>::storage_slot_for_mapping(variable.slot, key))
+    }
+
+    /// Returns the storage slot of the element at `index` in the dynamic array state variable
+    /// with the given name, and the offset, in bytes, of the element within the slot.
+    ///
+    /// Elements smaller than 17 bytes are packed, e.g. a `uint64[]` stores four elements per slot.
+    pub fn slot_for_array_element(&self, array_name: &str, index: U256) -> Result<(U256, u8)> {
+        let (variable, ty) = self.variable_with_encoding(array_name, "dynamic_array")?;
+        let start =
+            ContractInstance::<()>::storage_slot_for_dynamic_array(variable.slot, U256::ZERO);
+        let size = ty
+            .base
+            .as_ref()
+            .and_then(|base| self.layout.types.get(base))
+            .map_or(U256::from(32), |base| base.number_of_bytes);
+
+        let word = U256::from(32);
+        if size.is_zero() || size > U256::from(16) {
+            let slots_per_element = (size + word - U256::from(1)) / word;
+            return Ok((start.wrapping_add(index.wrapping_mul(slots_per_element)), 0));
+        }
+        let per_slot = word / size;
+        let offset = (index % per_slot) * size;
+        Ok((start.wrapping_add(index / per_slot), offset.to::<u8>()))
+    }
+
+    fn variable(&self, name: &str) -> Result<&StorageVariable> {
+        self.layout
+            .storage
+            .iter()
+            .find(|variable| variable.label == name)
+            .ok_or_else(|| Error::UnknownStorageVariable(name.to_string()))
+    }
+
+    fn variable_with_encoding(
+        &self,
+        name: &str,
+        encoding: &'static str,
+    ) -> Result<(&StorageVariable, &StorageType)> {
+        let variable = self.variable(name)?;
+        match self.layout.types.get(&variable.ty) {
+            Some(ty) if ty.encoding == encoding => Ok((variable, ty)),
+            _ => Err(Error::StorageTypeMismatch { variable: name.to_string(), expected: encoding }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, keccak256};
+
+    // Storage layout of:
+    //
+    // contract Token {
+    //     address owner;
+    //     bool paused;
+    //     mapping(address => uint256) balances;
+    //     uint64[] checkpoints;
+    //     uint256[] supplies;
+    // }
+    const LAYOUT: &str = r#"{
+        "storage": [
+            {"astId": 3, "contract": "Token.sol:Token", "label": "owner", "offset": 0, "slot": "0", "type": "t_address"},
+            {"astId": 5, "contract": "Token.sol:Token", "label": "paused", "offset": 20, "slot": "0", "type": "t_bool"},
+            {"astId": 9, "contract": "Token.sol:Token", "label": "balances", "offset": 0, "slot": "1", "type": "t_mapping(t_address,t_uint256)"},
+            {"astId": 12, "contract": "Token.sol:Token", "label": "checkpoints", "offset": 0, "slot": "2", "type": "t_array(t_uint64)dyn_storage"},
+            {"astId": 15, "contract": "Token.sol:Token", "label": "supplies", "offset": 0, "slot": "3", "type": "t_array(t_uint256)dyn_storage"}
+        ],
+        "types": {
+            "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+            "t_array(t_uint256)dyn_storage": {"base": "t_uint256", "encoding": "dynamic_array", "label": "uint256[]", "numberOfBytes": "32"},
+            "t_array(t_uint64)dyn_storage": {"base": "t_uint64", "encoding": "dynamic_array", "label": "uint64[]", "numberOfBytes": "32"},
+            "t_bool": {"encoding": "inplace", "label": "bool", "numberOfBytes": "1"},
+            "t_mapping(t_address,t_uint256)": {"encoding": "mapping", "key": "t_address", "label": "mapping(address => uint256)", "numberOfBytes": "32", "value": "t_uint256"},
+            "t_uint256": {"encoding": "inplace", "label": "uint256", "numberOfBytes": "32"},
+            "t_uint64": {"encoding": "inplace", "label": "uint64", "numberOfBytes": "8"}
+        }
+    }"#;
+
+    #[test]
+    fn slots() {
+        let layout = StorageLayoutAnalyzer::from_json(LAYOUT).unwrap();
+        assert_eq!(layout.slot_for_variable("owner").unwrap(), (U256::ZERO, 0));
+        assert_eq!(layout.slot_for_variable("paused").unwrap(), (U256::ZERO, 20));
+        assert!(matches!(
+            layout.slot_for_variable("totalSupply"),
+            Err(Error::UnknownStorageVariable(name)) if name == "totalSupply"
+        ));
+
+        let owner = address!("0000000000000000000000000000000000000001");
+        assert_eq!(
+            layout.slot_for_mapping_value("balances", DynSolValue::Address(owner)).unwrap(),
+            ContractInstance::<()>::storage_slot_for_mapping(
+                U256::from(1),
+                DynSolValue::Address(owner)
+            )
+        );
+        assert!(matches!(
+            layout.slot_for_mapping_value("owner", DynSolValue::Address(owner)),
+            Err(Error::StorageTypeMismatch { expected: "mapping", .. })
+        ));
+
+        let start =
+            |slot: u64| U256::from_be_bytes(keccak256(U256::from(slot).to_be_bytes::<32>()).0);
+        assert_eq!(
+            layout.slot_for_array_element("checkpoints", U256::from(5)).unwrap(),
+            (start(2) + U256::from(1), 8)
+        );
+        assert_eq!(
+            layout.slot_for_array_element("supplies", U256::from(5)).unwrap(),
+            (start(3) + U256::from(5), 0)
+        );
+        assert!(matches!(
+            layout.slot_for_array_element("balances", U256::ZERO),
+            Err(Error::StorageTypeMismatch { expected: "dynamic_array", .. })
+        ));
+
+        assert!(matches!(
+            StorageLayoutAnalyzer::from_json(r#"{"storage": []}"#),
+            Err(Error::InvalidStorageLayout(_))
+        ));
+    }
+}
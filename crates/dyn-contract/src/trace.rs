@@ -0,0 +1,121 @@
+use crate::{ContractInstance, Result};
+use alloy_dyn_abi::DynSolValue;
+use alloy_providers::provider::TempProvider;
+use alloy_rpc_trace_types::geth::{
+    GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+    GethDebugTracingOptions,
+};
+use alloy_rpc_types::{BlockId, BlockNumberOrTag};
+use std::time::Duration;
+
+pub use alloy_rpc_trace_types::geth::CallFrame;
+
+/// The call tree of a traced call, as returned by Geth's `callTracer`: the top-level call, with
+/// each sub-call nested in the [`calls`](CallFrame::calls) of its caller.
+pub type CallTrace = CallFrame;
+
+/// Options for tracing a call with [`ContractInstance::trace_call`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceCallOptions {
+    /// The tracer to use. Defaults to the built-in `callTracer`.
+    ///
+    /// The tracer's output must have the shape of a [`CallFrame`].
+    pub tracer: GethDebugTracerType,
+    /// How long the node may spend tracing. Defaults to the node's timeout.
+    pub timeout: Option<Duration>,
+    /// The tracer specific configuration, e.g. a
+    /// [`CallConfig`](alloy_rpc_trace_types::geth::CallConfig) serialized as JSON.
+    pub tracer_config: Option<serde_json::Value>,
+}
+
+impl Default for TraceCallOptions {
+    fn default() -> Self {
+        Self {
+            tracer: GethDebugBuiltInTracerType::CallTracer.into(),
+            timeout: None,
+            tracer_config: None,
+        }
+    }
+}
+
+impl From<TraceCallOptions> for GethDebugTracingCallOptions {
+    fn from(options: TraceCallOptions) -> Self {
+        let mut tracing_options = GethDebugTracingOptions::default().with_tracer(options.tracer);
+        if let Some(timeout) = options.timeout {
+            tracing_options = tracing_options.with_timeout(timeout);
+        }
+        if let Some(config) = options.tracer_config {
+            tracing_options.tracer_config = config.into();
+        }
+        Self { tracing_options, ..Default::default() }
+    }
+}
+
+impl<P: TempProvider + Clone> ContractInstance<P> {
+    /// Traces a call to the given function at the latest block via `debug_traceCall`, returning
+    /// the tree of calls it made.
+    ///
+    /// # Note
+    ///
+    /// `debug_traceCall` is not supported by all clients, and usually has to be enabled.
+    pub async fn trace_call(
+        &self,
+        function: &str,
+        args: &[DynSolValue],
+        options: TraceCallOptions,
+    ) -> Result<CallTrace> {
+        let request = self.function(function, args)?.request()?;
+        let block = BlockId::Number(BlockNumberOrTag::Latest);
+        let options = GethDebugTracingCallOptions::from(options);
+        Ok(self.provider_ref().raw_request("debug_traceCall", (request, block, options)).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Interface};
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::{Address, U256};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn trace_call() {
+        let (contract, token) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let provider = MockTransport::provider(move |method, params| {
+            assert_eq!(method, "debug_traceCall");
+            assert_eq!(params[0]["to"], json!(contract));
+            assert_eq!(params[1], "latest");
+            assert_eq!(params[2], json!({ "tracer": "callTracer", "timeout": "1000ms" }));
+            Ok(json!({
+                "from": Address::ZERO,
+                "to": contract,
+                "gas": "0x10000",
+                "gasUsed": "0x5000",
+                "input": "0x",
+                "type": "CALL",
+                "calls": [{
+                    "from": contract,
+                    "to": token,
+                    "gas": "0x8000",
+                    "gasUsed": "0x1000",
+                    "input": "0x",
+                    "error": "execution reverted",
+                    "type": "STATICCALL",
+                }],
+            }))
+        });
+        let abi = JsonAbi::parse(["function swap()"]).unwrap();
+        let contract = Interface::from(abi).connect(contract, provider);
+
+        let options =
+            TraceCallOptions { timeout: Some(Duration::from_secs(1)), ..Default::default() };
+        let trace = contract.trace_call("swap", &[], options).await.unwrap();
+        assert_eq!(trace.typ, "CALL");
+        assert_eq!(trace.gas_used, U256::from(0x5000));
+        assert_eq!(trace.calls.len(), 1);
+        assert_eq!(trace.calls[0].to, Some(token));
+        assert_eq!(trace.calls[0].error.as_deref(), Some("execution reverted"));
+        assert!(trace.calls[0].calls.is_empty());
+    }
+}
@@ -0,0 +1,340 @@
+use crate::Interface;
+use alloy_dyn_abi::DynSolType;
+use alloy_json_abi::{EventParam, InternalType, Param};
+use std::{collections::HashSet, fmt::Write};
+
+impl Interface {
+    /// Generates TypeScript type definitions for the contract, for use with e.g. ethers.js.
+    ///
+    /// The output contains an `interface` for the inputs and the outputs of each function, one
+    /// for the parameters of each event, and one for each struct they use, followed by the JSON
+    /// ABI as an `ABI` const. All names are prefixed with `contract_name`: the inputs of
+    /// `transfer` in `ERC20` are `ERC20TransferInputs`, and the ABI is `ERC20ABI`. Overloads
+    /// after the first are suffixed with their index in the ABI, and unnamed parameters are
+    /// named `_0`, `_1`, etc. Names that are already taken, e.g. by a function `foo1` and the
+    /// second overload of `foo`, are suffixed with `_2`, `_3`, etc.
+    ///
+    /// Integers of all sizes map to `bigint`, `address` and `string` to `string`, `bool` to
+    /// `boolean`, and `bytes` and fixed-size bytes to `Uint8Array`. Structs are named after their
+    /// internal type if the ABI includes it, or after the parameter otherwise, and each distinct
+    /// struct is only defined once.
+    pub fn generate_typescript_types(&self, contract_name: &str) -> String {
+        let mut generator =
+            Generator { prefix: contract_name, names: HashSet::new(), structs: Vec::new() };
+        let mut interfaces = Vec::new();
+        for functions in self.abi().functions.values() {
+            for (i, function) in functions.iter().enumerate() {
+                let name = generator.name(&function.name, i, &["Inputs", "Outputs"]);
+                interfaces.push(generator.interface(&format!("{name}Inputs"), &function.inputs));
+                interfaces.push(generator.interface(&format!("{name}Outputs"), &function.outputs));
+            }
+        }
+        for events in self.abi().events.values() {
+            for (i, event) in events.iter().enumerate() {
+                let name = generator.name(&event.name, i, &["Event"]);
+                interfaces.push(generator.interface(&format!("{name}Event"), &event.inputs));
+            }
+        }
+
+        let abi = serde_json::to_string_pretty(self.abi()).expect("JsonAbi serializes");
+        let mut out = String::new();
+        for (_, _, interface) in &generator.structs {
+            out.push_str(interface);
+        }
+        for interface in interfaces {
+            out.push_str(&interface);
+        }
+        let _ = writeln!(out, "export const {contract_name}ABI = {abi} as const;");
+        out
+    }
+}
+
+/// A parameter of a function or event.
+trait TsParam {
+    fn name(&self) -> &str;
+    fn ty(&self) -> &str;
+    fn components(&self) -> &[Param];
+    fn internal_type(&self) -> Option<&InternalType>;
+}
+
+macro_rules! impl_ts_param {
+    ($($t:ty),*) => {$(
+        impl TsParam for $t {
+            fn name(&self) -> &str {
+                &self.name
+            }
+
+            fn ty(&self) -> &str {
+                &self.ty
+            }
+
+            fn components(&self) -> &[Param] {
+                &self.components
+            }
+
+            fn internal_type(&self) -> Option<&InternalType> {
+                self.internal_type.as_ref()
+            }
+        }
+    )*};
+}
+
+impl_ts_param!(Param, EventParam);
+
+struct Generator<'a> {
+    prefix: &'a str,
+    /// The names of all interfaces generated so far.
+    names: HashSet<String>,
+    /// The struct interfaces, keyed by their qualified name and components, with their generated
+    /// name, in order of first use.
+    structs: Vec<((String, Vec<Param>), String, String)>,
+}
+
+impl Generator<'_> {
+    /// Returns the prefixed, capitalized name of the `i`th overload of an item, made unique for
+    /// each of the interfaces named after it with one of `suffixes`.
+    fn name(&mut self, item: &str, i: usize, suffixes: &[&str]) -> String {
+        let mut name = format!("{}{}", self.prefix, capitalize(item));
+        if i > 0 {
+            let _ = write!(name, "{i}");
+        }
+        self.unique(name, suffixes)
+    }
+
+    /// Returns `name`, or `name` suffixed with `_2`, `_3`, etc. if it is taken, such that no
+    /// interface has been named after it with one of `suffixes`, and reserves these names.
+    fn unique(&mut self, name: String, suffixes: &[&str]) -> String {
+        let taken = |names: &HashSet<String>, name: &str| {
+            suffixes.iter().any(|suffix| names.contains(&format!("{name}{suffix}")))
+        };
+        let mut unique = name.clone();
+        let mut n = 1;
+        while taken(&self.names, &unique) {
+            n += 1;
+            unique = format!("{name}_{n}");
+        }
+        self.names.extend(suffixes.iter().map(|suffix| format!("{unique}{suffix}")));
+        unique
+    }
+
+    /// Returns an interface with a field for each parameter.
+    fn interface<T: TsParam>(&mut self, name: &str, params: &[T]) -> String {
+        if params.is_empty() {
+            return format!("export interface {name} {{}}\n\n");
+        }
+        let mut out = format!("export interface {name} {{\n");
+        for (i, param) in params.iter().enumerate() {
+            let field =
+                if param.name().is_empty() { format!("_{i}") } else { param.name().to_string() };
+            let ty = self.ty(name, &field, param);
+            let _ = writeln!(out, "  {field}: {ty};");
+        }
+        out.push_str("}\n\n");
+        out
+    }
+
+    /// Returns the TypeScript type of `param`, the field `field` of the interface `parent`.
+    fn ty<T: TsParam>(&mut self, parent: &str, field: &str, param: &T) -> String {
+        let Some(dims) = param.ty().strip_prefix("tuple") else {
+            return DynSolType::parse(param.ty())
+                .map_or_else(|_| "unknown".into(), |ty| ts_type(&ty));
+        };
+        let (key, name) = match param.internal_type().and_then(|ty| ty.as_struct()) {
+            Some((contract, ty)) => {
+                let stem = ty.split('[').next().unwrap_or(ty);
+                let key = format!("{}.{stem}", contract.unwrap_or_default());
+                (key, format!("{}{stem}", self.prefix))
+            }
+            None => {
+                let name = format!("{parent}{}", capitalize(field));
+                (name.clone(), name)
+            }
+        };
+        let key = (key, param.components().to_vec());
+        let name = match self.structs.iter().find(|(existing, ..)| *existing == key) {
+            Some((_, name, _)) => name.clone(),
+            None => {
+                let name = self.unique(name, &[""]);
+                let interface = self.interface(&name, param.components());
+                self.structs.push((key, name.clone(), interface));
+                name
+            }
+        };
+        name + &"[]".repeat(dims.matches('[').count())
+    }
+}
+
+/// Returns the TypeScript type of a non-tuple type.
+fn ts_type(ty: &DynSolType) -> String {
+    match ty {
+        DynSolType::Bool => "boolean".into(),
+        DynSolType::Int(_) | DynSolType::Uint(_) => "bigint".into(),
+        DynSolType::Address | DynSolType::Function | DynSolType::String => "string".into(),
+        DynSolType::Bytes | DynSolType::FixedBytes(_) => "Uint8Array".into(),
+        DynSolType::Array(inner) | DynSolType::FixedArray(inner, _) => {
+            format!("{}[]", ts_type(inner))
+        }
+        DynSolType::Tuple(types) => {
+            let types = types.iter().map(ts_type).collect::<Vec<_>>();
+            format!("[{}]", types.join(", "))
+        }
+        // `CustomStruct` with the `eip712` feature.
+        #[allow(unreachable_patterns)]
+        _ => "unknown".into(),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_abi::JsonAbi;
+
+    /// Splits the generated code into the interfaces and the JSON ABI.
+    fn split(ts: &str, contract_name: &str) -> (String, JsonAbi) {
+        let (interfaces, abi) =
+            ts.split_once(&format!("export const {contract_name}ABI = ")).unwrap();
+        let abi = abi.strip_suffix(" as const;\n").unwrap();
+        (interfaces.to_string(), serde_json::from_str(abi).unwrap())
+    }
+
+    #[test]
+    fn erc20() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[
+                {"type": "function", "name": "balanceOf", "stateMutability": "view", "inputs": [{"name": "owner", "type": "address"}], "outputs": [{"name": "", "type": "uint256"}]},
+                {"type": "function", "name": "symbol", "stateMutability": "view", "inputs": [], "outputs": [{"name": "", "type": "string"}]},
+                {"type": "function", "name": "transfer", "stateMutability": "nonpayable", "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}], "outputs": [{"name": "", "type": "bool"}]},
+                {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [{"name": "from", "type": "address", "indexed": true}, {"name": "to", "type": "address", "indexed": true}, {"name": "value", "type": "uint256", "indexed": false}]}
+            ]"#,
+        )
+        .unwrap();
+        let ts = Interface::from(abi.clone()).generate_typescript_types("ERC20");
+
+        let (interfaces, parsed) = split(&ts, "ERC20");
+        assert_eq!(parsed, abi);
+        assert_eq!(
+            interfaces,
+            "export interface ERC20BalanceOfInputs {
+  owner: string;
+}
+
+export interface ERC20BalanceOfOutputs {
+  _0: bigint;
+}
+
+export interface ERC20SymbolInputs {}
+
+export interface ERC20SymbolOutputs {
+  _0: string;
+}
+
+export interface ERC20TransferInputs {
+  to: string;
+  amount: bigint;
+}
+
+export interface ERC20TransferOutputs {
+  _0: boolean;
+}
+
+export interface ERC20TransferEvent {
+  from: string;
+  to: string;
+  value: bigint;
+}
+
+"
+        );
+    }
+
+    #[test]
+    fn structs() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[
+                {"type": "function", "name": "submit", "stateMutability": "nonpayable", "inputs": [
+                    {"name": "orders", "type": "tuple[]", "internalType": "struct Exchange.Order[]", "components": [
+                        {"name": "maker", "type": "address"},
+                        {"name": "amounts", "type": "uint128[2]"},
+                        {"name": "signature", "type": "bytes"}
+                    ]},
+                    {"name": "meta", "type": "tuple", "components": [{"name": "tag", "type": "bytes32"}]}
+                ], "outputs": []}
+            ]"#,
+        )
+        .unwrap();
+        let ts = Interface::from(abi).generate_typescript_types("Exchange");
+        assert_eq!(
+            split(&ts, "Exchange").0,
+            "export interface ExchangeOrder {
+  maker: string;
+  amounts: bigint[];
+  signature: Uint8Array;
+}
+
+export interface ExchangeSubmitInputsMeta {
+  tag: Uint8Array;
+}
+
+export interface ExchangeSubmitInputs {
+  orders: ExchangeOrder[];
+  meta: ExchangeSubmitInputsMeta;
+}
+
+export interface ExchangeSubmitOutputs {}
+
+"
+        );
+    }
+
+    #[test]
+    fn unique_names() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[
+                {"type": "function", "name": "foo", "stateMutability": "nonpayable", "inputs": [
+                    {"name": "a", "type": "tuple", "internalType": "struct A.Item", "components": [{"name": "x", "type": "uint256"}]},
+                    {"name": "b", "type": "tuple", "internalType": "struct B.Item", "components": [{"name": "y", "type": "bool"}]}
+                ], "outputs": []},
+                {"type": "function", "name": "foo", "stateMutability": "nonpayable", "inputs": [
+                    {"name": "a", "type": "tuple[]", "internalType": "struct A.Item[]", "components": [{"name": "x", "type": "uint256"}]}
+                ], "outputs": []},
+                {"type": "function", "name": "foo1", "stateMutability": "nonpayable", "inputs": [], "outputs": []}
+            ]"#,
+        )
+        .unwrap();
+        let ts = Interface::from(abi).generate_typescript_types("C");
+        assert_eq!(
+            split(&ts, "C").0,
+            "export interface CItem {
+  x: bigint;
+}
+
+export interface CItem_2 {
+  y: boolean;
+}
+
+export interface CFooInputs {
+  a: CItem;
+  b: CItem_2;
+}
+
+export interface CFooOutputs {}
+
+export interface CFoo1Inputs {
+  a: CItem[];
+}
+
+export interface CFoo1Outputs {}
+
+export interface CFoo1_2Inputs {}
+
+export interface CFoo1_2Outputs {}
+
+"
+        );
+    }
+}
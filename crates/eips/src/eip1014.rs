@@ -0,0 +1,79 @@
+//! [EIP-1014] `CREATE2` and `CREATE` contract address helpers.
+//!
+//! [EIP-1014]: https://eips.ethereum.org/EIPS/eip-1014
+
+use alloy_primitives::{Address, B256};
+
+/// Computes the address of a contract created by `deployer` with the `CREATE2` opcode:
+/// `keccak256(0xff ++ deployer ++ salt ++ init_code_hash)[12..]`.
+pub fn create2_address(deployer: Address, salt: B256, init_code_hash: B256) -> Address {
+    deployer.create2(salt, init_code_hash)
+}
+
+/// Computes the address of a contract created by `deployer` with the `CREATE2` opcode, hashing
+/// the `init_code`.
+///
+/// See [`create2_address`].
+pub fn create2_address_from_code(deployer: Address, salt: B256, init_code: &[u8]) -> Address {
+    deployer.create2_from_code(salt, init_code)
+}
+
+/// Computes the address of a contract created by `deployer` with the `CREATE` opcode, or by a
+/// contract creation transaction: `keccak256(rlp([deployer, nonce]))[12..]`.
+pub fn create_address(deployer: Address, nonce: u64) -> Address {
+    deployer.create(nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, keccak256};
+
+    // https://eips.ethereum.org/EIPS/eip-1014#examples
+    #[test]
+    fn create2() {
+        for (deployer, salt, init_code, expected) in [
+            (
+                Address::ZERO,
+                B256::ZERO,
+                &[0x00][..],
+                address!("4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"),
+            ),
+            (
+                address!("deadbeef00000000000000000000000000000000"),
+                B256::ZERO,
+                &[0x00],
+                address!("B928f69Bb1D91Cd65274e3c79d8986362984fDA3"),
+            ),
+            (
+                address!("deadbeef00000000000000000000000000000000"),
+                b256!("000000000000000000000000feed000000000000000000000000000000000000"),
+                &[0x00],
+                address!("D04116cDd17beBE565EB2422F2497E06cC1C9833"),
+            ),
+            (
+                Address::ZERO,
+                B256::ZERO,
+                &[0xde, 0xad, 0xbe, 0xef],
+                address!("70f2b2914A2a4b783FaEFb75f459A580616Fcb5e"),
+            ),
+            (Address::ZERO, B256::ZERO, &[], address!("E33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0")),
+        ] {
+            assert_eq!(create2_address_from_code(deployer, salt, init_code), expected);
+            assert_eq!(create2_address(deployer, salt, keccak256(init_code)), expected);
+        }
+    }
+
+    #[test]
+    fn create() {
+        let deployer = address!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        assert_eq!(
+            create_address(deployer, 0),
+            address!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")
+        );
+        assert_eq!(
+            create_address(deployer, 1),
+            address!("343c43a37d37dff08ae8c4a11544c718abb4fcf8")
+        );
+    }
+}
@@ -0,0 +1,97 @@
+/// The outcome of [`analyze_fee_cap`] for an EIP-1559 transaction at a given base fee.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FeeCapsAnalysis {
+    /// The priority fee per gas the transaction would pay to the block producer, i.e. the
+    /// `max_priority_fee_per_gas`, capped by what is left of `max_fee_per_gas` after the base fee.
+    /// `0` if the transaction would not be included.
+    pub effective_priority_fee: u128,
+    /// The part of `max_fee_per_gas` beyond `base_fee + max_priority_fee_per_gas`, which can never
+    /// be charged at this base fee. `0` if the fee cap is not above that sum.
+    pub wasted_fee: u128,
+    /// Whether `max_fee_per_gas` covers the base fee, i.e. the transaction is includable.
+    pub would_be_included: bool,
+    /// Whether `max_fee_per_gas` is at least 110% of the base fee, so the transaction remains
+    /// includable if the base fee increases in the next block.
+    pub sufficient_headroom: bool,
+}
+
+/// Analyzes the fee caps of an EIP-1559 transaction against the given base fee, to detect
+/// transactions that are underpaying, i.e. not includable, or overpaying, i.e. setting
+/// `max_fee_per_gas` higher than necessary.
+///
+/// All values are per gas, in wei.
+pub const fn analyze_fee_cap(
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+    base_fee: u128,
+) -> FeeCapsAnalysis {
+    let would_be_included = max_fee_per_gas >= base_fee;
+    let effective_priority_fee = if would_be_included {
+        let available = max_fee_per_gas - base_fee;
+        if max_priority_fee_per_gas < available {
+            max_priority_fee_per_gas
+        } else {
+            available
+        }
+    } else {
+        0
+    };
+    FeeCapsAnalysis {
+        effective_priority_fee,
+        wasted_fee: max_fee_per_gas
+            .saturating_sub(base_fee.saturating_add(max_priority_fee_per_gas)),
+        would_be_included,
+        sufficient_headroom: max_fee_per_gas >= base_fee.saturating_mul(110) / 100,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn exact_fit() {
+        assert_eq!(
+            analyze_fee_cap(32 * GWEI, 2 * GWEI, 30 * GWEI),
+            FeeCapsAnalysis {
+                effective_priority_fee: 2 * GWEI,
+                wasted_fee: 0,
+                would_be_included: true,
+                sufficient_headroom: false,
+            }
+        );
+    }
+
+    #[test]
+    fn over_cap() {
+        assert_eq!(
+            analyze_fee_cap(100 * GWEI, 2 * GWEI, 30 * GWEI),
+            FeeCapsAnalysis {
+                effective_priority_fee: 2 * GWEI,
+                wasted_fee: 68 * GWEI,
+                would_be_included: true,
+                sufficient_headroom: true,
+            }
+        );
+
+        // The priority fee is capped by the fee cap.
+        let analysis = analyze_fee_cap(31 * GWEI, 2 * GWEI, 30 * GWEI);
+        assert_eq!(analysis.effective_priority_fee, GWEI);
+        assert_eq!(analysis.wasted_fee, 0);
+    }
+
+    #[test]
+    fn rejected() {
+        assert_eq!(
+            analyze_fee_cap(29 * GWEI, 2 * GWEI, 30 * GWEI),
+            FeeCapsAnalysis {
+                effective_priority_fee: 0,
+                wasted_fee: 0,
+                would_be_included: false,
+                sufficient_headroom: false,
+            }
+        );
+    }
+}
@@ -11,5 +11,8 @@ pub use constants::{
     ETHEREUM_BLOCK_GAS_LIMIT, INITIAL_BASE_FEE, MIN_PROTOCOL_BASE_FEE, MIN_PROTOCOL_BASE_FEE_U256,
 };
 
+mod fee_cap;
+pub use fee_cap::{analyze_fee_cap, FeeCapsAnalysis};
+
 mod helpers;
 pub use helpers::calc_next_block_base_fee;
@@ -0,0 +1,142 @@
+//! [EIP-2200] constants and helpers.
+//!
+//! [EIP-2200]: https://eips.ethereum.org/EIPS/eip-2200
+
+use alloy_primitives::U256;
+
+/// Gas cost of an `SSTORE` that is a no-op or that changes an already dirty slot.
+pub const SLOAD_GAS: u64 = 800;
+
+/// Gas cost of an `SSTORE` that changes a clean slot from zero to non-zero.
+pub const SSTORE_SET_GAS: u64 = 20_000;
+
+/// Gas cost of an `SSTORE` that changes a clean, non-zero slot.
+pub const SSTORE_RESET_GAS: u64 = 5_000;
+
+/// Gas refunded for clearing a slot that was originally non-zero.
+pub const SSTORE_CLEARS_SCHEDULE: u64 = 15_000;
+
+/// The kind of an `SSTORE`, determined by the slot's original value (at the start of the
+/// transaction), its current value, and the new value.
+///
+/// A slot is *clean* if its current value equals its original value, and *dirty* otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SstoreGas {
+    /// The new value equals the current value.
+    NoOp,
+    /// A clean slot is set from zero to a non-zero value.
+    InitialDirty,
+    /// A clean, non-zero slot is set to zero.
+    CleanReset,
+    /// A clean, non-zero slot is set to another non-zero value.
+    CleanChange,
+    /// A dirty slot is set to a value other than its original value or zero.
+    DirtyChange,
+    /// A dirty slot, originally non-zero, is set to zero.
+    DirtyReset,
+    /// A dirty slot is set back to its original value.
+    DirtyRestore,
+}
+
+/// Calculates the gas cost and refund of an `SSTORE`, as specified in [EIP-2200], from the slot's
+/// `original`, `current` and `new` values.
+///
+/// Returns the kind of the `SSTORE`, its gas cost, and the change to the refund counter, which is
+/// negative if a refund granted by an earlier `SSTORE` to the same slot is taken back.
+///
+/// The check that more than the call stipend of gas is left is not performed here.
+///
+/// [EIP-2200]: https://eips.ethereum.org/EIPS/eip-2200
+pub fn calc_sstore_gas(original: U256, current: U256, new: U256) -> (SstoreGas, u64, i64) {
+    if current == new {
+        return (SstoreGas::NoOp, SLOAD_GAS, 0);
+    }
+
+    if original == current {
+        return if original.is_zero() {
+            (SstoreGas::InitialDirty, SSTORE_SET_GAS, 0)
+        } else if new.is_zero() {
+            (SstoreGas::CleanReset, SSTORE_RESET_GAS, SSTORE_CLEARS_SCHEDULE as i64)
+        } else {
+            (SstoreGas::CleanChange, SSTORE_RESET_GAS, 0)
+        };
+    }
+
+    let mut refund = 0;
+    if !original.is_zero() {
+        if current.is_zero() {
+            refund -= SSTORE_CLEARS_SCHEDULE as i64;
+        } else if new.is_zero() {
+            refund += SSTORE_CLEARS_SCHEDULE as i64;
+        }
+    }
+
+    let kind = if original == new {
+        refund += if original.is_zero() {
+            (SSTORE_SET_GAS - SLOAD_GAS) as i64
+        } else {
+            (SSTORE_RESET_GAS - SLOAD_GAS) as i64
+        };
+        SstoreGas::DirtyRestore
+    } else if new.is_zero() {
+        SstoreGas::DirtyReset
+    } else {
+        SstoreGas::DirtyChange
+    };
+    (kind, SLOAD_GAS, refund)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sstore_gas() {
+        let [zero, one, two] = [U256::ZERO, U256::from(1), U256::from(2)];
+        let cases = [
+            ((zero, zero, zero), (SstoreGas::NoOp, 800, 0)),
+            ((zero, zero, one), (SstoreGas::InitialDirty, 20_000, 0)),
+            ((one, one, zero), (SstoreGas::CleanReset, 5_000, 15_000)),
+            ((one, one, two), (SstoreGas::CleanChange, 5_000, 0)),
+            ((zero, one, two), (SstoreGas::DirtyChange, 800, 0)),
+            // Takes back the refund for clearing the slot.
+            ((one, zero, two), (SstoreGas::DirtyChange, 800, -15_000)),
+            ((one, two, zero), (SstoreGas::DirtyReset, 800, 15_000)),
+            ((zero, one, zero), (SstoreGas::DirtyRestore, 800, 19_200)),
+            ((one, two, one), (SstoreGas::DirtyRestore, 800, 4_200)),
+            ((one, zero, one), (SstoreGas::DirtyRestore, 800, -10_800)),
+        ];
+        for ((original, current, new), expected) in cases {
+            assert_eq!(
+                calc_sstore_gas(original, current, new),
+                expected,
+                "{original} {current} {new}"
+            );
+        }
+    }
+
+    #[test]
+    fn sstore_sequences() {
+        // Sequences of `SSTORE`s to a single slot from the EIP's test cases, as
+        // `(original, values, gas used excluding pushes, refund)`.
+        let cases: [(u64, &[u64], u64, i64); 4] = [
+            (0, &[1, 0], 20_800, 19_200),
+            (1, &[0, 1], 5_800, 4_200),
+            (0, &[1, 2, 1], 21_600, 0),
+            (1, &[0, 1, 0], 10_800, 19_200),
+        ];
+        for (original, values, gas, refund) in cases {
+            let original = U256::from(original);
+            let mut current = original;
+            let (mut total_gas, mut total_refund) = (0, 0);
+            for &value in values {
+                let new = U256::from(value);
+                let (_, cost, delta) = calc_sstore_gas(original, current, new);
+                total_gas += cost;
+                total_refund += delta;
+                current = new;
+            }
+            assert_eq!((total_gas, total_refund), (gas, refund), "{original} {values:?}");
+        }
+    }
+}
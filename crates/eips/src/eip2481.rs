@@ -0,0 +1,118 @@
+//! [EIP-2481] (eth/66) request IDs.
+//!
+//! eth/66 wraps each request and response message in a pair of a request ID, chosen by the
+//! requester, and the message itself, so that responses can be matched to their requests. This
+//! module provides the same pairing for JSON payloads, where the ID is an `id` field next to the
+//! message's fields.
+//!
+//! [EIP-2481]: https://eips.ethereum.org/EIPS/eip-2481
+
+/// The ID of an eth/66 request, echoed in its response.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct RequestId(pub u64);
+
+impl RequestId {
+    /// Returns a random request ID, generated with the thread-local RNG.
+    #[cfg(feature = "rand")]
+    pub fn random() -> Self {
+        Self(rand::random())
+    }
+}
+
+impl From<u64> for RequestId {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+impl From<RequestId> for u64 {
+    fn from(id: RequestId) -> Self {
+        id.0
+    }
+}
+
+/// A message paired with the ID of the request it belongs to.
+///
+/// Serializes as the message with an additional `id` field, so the message must serialize as a
+/// map, e.g. a struct. See [`strip_request_id`] for the reverse.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WithRequestId<T> {
+    /// The request ID.
+    pub id: RequestId,
+    /// The message.
+    pub data: T,
+}
+
+impl<T> WithRequestId<T> {
+    /// Pairs the message with the given request ID.
+    pub const fn new(id: RequestId, data: T) -> Self {
+        Self { id, data }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for WithRequestId<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        struct Flat<'a, T> {
+            id: RequestId,
+            #[serde(flatten)]
+            data: &'a T,
+        }
+        Flat { id: self.id, data: &self.data }.serialize(serializer)
+    }
+}
+
+/// Splits a JSON message with an `id` field, as serialized by [`WithRequestId`], into the request
+/// ID and the message without the `id` field.
+#[cfg(feature = "serde")]
+pub fn strip_request_id<T: serde::de::DeserializeOwned>(
+    value: serde_json::Value,
+) -> Result<(RequestId, T), serde_json::Error> {
+    use serde::de::Error;
+
+    let serde_json::Value::Object(mut object) = value else {
+        return Err(serde_json::Error::custom("expected a JSON object with an `id` field"));
+    };
+    let id = object.remove("id").ok_or_else(|| serde_json::Error::missing_field("id"))?;
+    Ok((serde_json::from_value(id)?, serde_json::from_value(object.into())?))
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct GetBlockHeaders {
+        start_block: u64,
+        limit: u64,
+    }
+
+    #[test]
+    fn with_request_id() {
+        let message = GetBlockHeaders { start_block: 100, limit: 16 };
+        let value = serde_json::to_value(WithRequestId::new(RequestId(7), &message)).unwrap();
+        assert_eq!(value, json!({ "id": 7, "startBlock": 100, "limit": 16 }));
+
+        let (id, stripped) = strip_request_id::<GetBlockHeaders>(value).unwrap();
+        assert_eq!((id, stripped), (RequestId(7), message));
+
+        assert!(
+            strip_request_id::<GetBlockHeaders>(json!({ "startBlock": 1, "limit": 1 })).is_err()
+        );
+        assert!(strip_request_id::<GetBlockHeaders>(json!([7])).is_err());
+        assert!(strip_request_id::<GetBlockHeaders>(json!({ "id": "7", "startBlock": 1 })).is_err());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_ids() {
+        let ids =
+            (0..10_000).map(|_| RequestId::random()).collect::<std::collections::HashSet<_>>();
+        assert_eq!(ids.len(), 10_000);
+    }
+}
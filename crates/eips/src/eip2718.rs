@@ -2,7 +2,7 @@
 //!
 //! [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
 
-use alloy_primitives::{keccak256, Sealed, B256};
+use alloy_primitives::{keccak256, Bytes, Sealed, B256};
 use alloy_rlp::{BufMut, Header, EMPTY_STRING_CODE};
 
 // https://eips.ethereum.org/EIPS/eip-2718#transactiontype-only-goes-up-to-0x7f
@@ -168,3 +168,187 @@ pub trait Encodable2718: Sized + Send + Sync + 'static {
 /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
 pub trait Eip2718Envelope: Decodable2718 + Encodable2718 {}
 impl<T> Eip2718Envelope for T where T: Decodable2718 + Encodable2718 {}
+
+/// The known [EIP-2718] transaction types.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum TxType {
+    /// Legacy transaction, which has no type byte.
+    Legacy = 0,
+    /// [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) transaction.
+    Eip2930 = 1,
+    /// [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559) transaction.
+    Eip1559 = 2,
+    /// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) transaction.
+    Eip4844 = 3,
+}
+
+impl TryFrom<u8> for TxType {
+    type Error = Eip2718Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Legacy),
+            1 => Ok(Self::Eip2930),
+            2 => Ok(Self::Eip1559),
+            3 => Ok(Self::Eip4844),
+            ty => Err(Eip2718Error::UnexpectedType(ty)),
+        }
+    }
+}
+
+/// A borrowed [EIP-2718] encoded transaction, split into its type and the RLP encoded payload of
+/// that type. Returned by [`decode_typed_transaction`].
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypedTransactionRef<'a> {
+    /// A legacy transaction.
+    Legacy(&'a [u8]),
+    /// An EIP-2930 transaction, without the type byte.
+    Eip2930(&'a [u8]),
+    /// An EIP-1559 transaction, without the type byte.
+    Eip1559(&'a [u8]),
+    /// An EIP-4844 transaction, without the type byte.
+    Eip4844(&'a [u8]),
+}
+
+impl<'a> TypedTransactionRef<'a> {
+    /// Returns the type of the transaction.
+    pub const fn tx_type(&self) -> TxType {
+        match self {
+            Self::Legacy(_) => TxType::Legacy,
+            Self::Eip2930(_) => TxType::Eip2930,
+            Self::Eip1559(_) => TxType::Eip1559,
+            Self::Eip4844(_) => TxType::Eip4844,
+        }
+    }
+
+    /// Returns the RLP encoded payload of the transaction, without the type byte.
+    pub const fn payload(&self) -> &'a [u8] {
+        match self {
+            Self::Legacy(payload)
+            | Self::Eip2930(payload)
+            | Self::Eip1559(payload)
+            | Self::Eip4844(payload) => payload,
+        }
+    }
+}
+
+/// An owned [EIP-2718] encoded transaction, split into its type and the RLP encoded payload of
+/// that type.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypedTransaction {
+    /// The type of the transaction.
+    pub tx_type: TxType,
+    /// The RLP encoded payload of the transaction, without the type byte.
+    pub payload: Bytes,
+}
+
+impl TypedTransaction {
+    /// Splits an [EIP-2718] encoded transaction into its type and payload, see
+    /// [`decode_typed_transaction`].
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    pub fn decode(bytes: &[u8]) -> Result<Self, Eip2718Error> {
+        decode_typed_transaction(bytes).map(Into::into)
+    }
+}
+
+impl From<TypedTransactionRef<'_>> for TypedTransaction {
+    fn from(tx: TypedTransactionRef<'_>) -> Self {
+        Self { tx_type: tx.tx_type(), payload: Bytes::copy_from_slice(tx.payload()) }
+    }
+}
+
+/// Splits an [EIP-2718] encoded transaction into its type and the RLP encoded payload of that
+/// type, without decoding the payload.
+///
+/// Legacy transactions are recognized by starting with an RLP list header. Otherwise, the first
+/// byte is the type byte. In both cases the payload must be a single RLP list spanning the rest
+/// of the input.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub fn decode_typed_transaction(bytes: &[u8]) -> Result<TypedTransactionRef<'_>, Eip2718Error> {
+    let ty = *bytes.first().ok_or(alloy_rlp::Error::InputTooShort)?;
+    let (tx_type, payload) = if ty >= alloy_rlp::EMPTY_LIST_CODE {
+        (TxType::Legacy, bytes)
+    } else if ty <= TX_TYPE_BYTE_MAX && ty != TxType::Legacy as u8 {
+        (TxType::try_from(ty)?, &bytes[1..])
+    } else {
+        return Err(Eip2718Error::UnexpectedType(ty));
+    };
+
+    let mut buf = payload;
+    let header = Header::decode(&mut buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString.into());
+    }
+    if header.payload_length != buf.len() {
+        return Err(alloy_rlp::Error::UnexpectedLength.into());
+    }
+
+    Ok(match tx_type {
+        TxType::Legacy => TypedTransactionRef::Legacy(payload),
+        TxType::Eip2930 => TypedTransactionRef::Eip2930(payload),
+        TxType::Eip1559 => TypedTransactionRef::Eip1559(payload),
+        TxType::Eip4844 => TypedTransactionRef::Eip4844(payload),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_typed_transactions() {
+        // An RLP list of three single byte items.
+        let payload = [0xc3, 0x01, 0x02, 0x03];
+        let typed = |ty: u8| [&[ty][..], &payload].concat();
+
+        assert_eq!(
+            decode_typed_transaction(&payload).unwrap(),
+            TypedTransactionRef::Legacy(&payload)
+        );
+        let eip2930 = typed(1);
+        assert_eq!(
+            decode_typed_transaction(&eip2930).unwrap(),
+            TypedTransactionRef::Eip2930(&payload)
+        );
+        let eip1559 = typed(2);
+        assert_eq!(
+            decode_typed_transaction(&eip1559).unwrap(),
+            TypedTransactionRef::Eip1559(&payload)
+        );
+        let eip4844 = typed(3);
+        let tx = decode_typed_transaction(&eip4844).unwrap();
+        assert_eq!(tx, TypedTransactionRef::Eip4844(&payload));
+        assert_eq!(tx.tx_type(), TxType::Eip4844);
+
+        assert_eq!(
+            TypedTransaction::decode(&eip1559).unwrap(),
+            TypedTransaction {
+                tx_type: TxType::Eip1559,
+                payload: Bytes::copy_from_slice(&payload)
+            }
+        );
+    }
+
+    #[test]
+    fn decode_typed_transaction_errors() {
+        let err = |bytes: &[u8]| decode_typed_transaction(bytes).unwrap_err();
+
+        assert!(matches!(err(&[]), Eip2718Error::RlpError(alloy_rlp::Error::InputTooShort)));
+        for ty in [0x00, 0x04, 0x7f, 0x80, 0xbf] {
+            assert!(matches!(err(&[ty, 0xc0]), Eip2718Error::UnexpectedType(t) if t == ty));
+        }
+        // The payload is a string, or does not span the rest of the input.
+        assert!(matches!(err(&[0x02, 0x81, 0xff]), Eip2718Error::RlpError(_)));
+        assert!(matches!(err(&[0x02, 0xc1, 0x01, 0x02]), Eip2718Error::RlpError(_)));
+        assert!(matches!(err(&[0x02, 0xc2, 0x01]), Eip2718Error::RlpError(_)));
+    }
+}
@@ -0,0 +1,543 @@
+//! [EIP-4361] Sign-In with Ethereum messages.
+//!
+//! [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+
+use alloy_primitives::Address;
+use core::{fmt, iter::Peekable, str::Split};
+
+/// The suffix of the first line of a message, following the `[scheme://]domain`.
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+const URI_TAG: &str = "URI: ";
+const VERSION_TAG: &str = "Version: ";
+const CHAIN_ID_TAG: &str = "Chain ID: ";
+const NONCE_TAG: &str = "Nonce: ";
+const ISSUED_AT_TAG: &str = "Issued At: ";
+const EXPIRATION_TIME_TAG: &str = "Expiration Time: ";
+const NOT_BEFORE_TAG: &str = "Not Before: ";
+const REQUEST_ID_TAG: &str = "Request ID: ";
+const RESOURCES_TAG: &str = "Resources:";
+const RESOURCE_PREFIX: &str = "- ";
+
+/// Errors parsing an [EIP-4361] message.
+///
+/// [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum SiweParseError {
+    /// The first line is not a valid `[scheme://]domain wants you to sign in...` preamble.
+    #[error("Invalid preamble. Got {0:?}.")]
+    InvalidPreamble(String),
+    /// The message ended before a required field.
+    #[error("Missing field {0}.")]
+    MissingField(&'static str),
+    /// A field has an invalid value.
+    #[error("Invalid {field}. Got {value:?}.")]
+    InvalidField {
+        /// The name of the field.
+        field: &'static str,
+        /// The invalid value.
+        value: String,
+    },
+    /// A line does not match the expected field, or follows the last field.
+    #[error("Unexpected line {0:?}.")]
+    UnexpectedLine(String),
+}
+
+/// Errors verifying an [EIP-4361] message.
+///
+/// [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+#[cfg(feature = "k256")]
+#[derive(thiserror::Error, Debug)]
+pub enum SiweError {
+    /// The signer could not be recovered from the signature.
+    #[error(transparent)]
+    Signature(#[from] alloy_primitives::SignatureError),
+    /// The message was signed by another account than the one it names.
+    #[error("Signer mismatch. Expected {expected}, got {recovered}.")]
+    SignerMismatch {
+        /// The address in the message.
+        expected: Address,
+        /// The address recovered from the signature.
+        recovered: Address,
+    },
+}
+
+/// A Sign-In with Ethereum message, as defined by [EIP-4361].
+///
+/// Timestamps are kept as the RFC 3339 strings of the message, so that the message can be
+/// reproduced byte for byte.
+///
+/// [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SiweMessage {
+    /// The URI scheme of the origin of the request, e.g. `https`.
+    pub scheme: Option<String>,
+    /// The RFC 3986 authority that is requesting the signing.
+    pub domain: String,
+    /// The address of the account performing the signing.
+    pub address: Address,
+    /// A human-readable assertion that the user will sign, which must not contain newlines.
+    pub statement: Option<String>,
+    /// An RFC 3986 URI referring to the resource that is the subject of the signing.
+    pub uri: String,
+    /// The version of the message, which must be `1`.
+    pub version: String,
+    /// The EIP-155 chain ID to which the session is bound.
+    pub chain_id: u64,
+    /// A randomized token of at least 8 alphanumeric characters, used to prevent replay attacks.
+    pub nonce: String,
+    /// The RFC 3339 time at which the message was generated.
+    pub issued_at: String,
+    /// The RFC 3339 time at which the signed authentication message is no longer valid.
+    pub expiration_time: Option<String>,
+    /// The RFC 3339 time at which the signed authentication message becomes valid.
+    pub not_before: Option<String>,
+    /// A system-specific identifier that may be used to uniquely refer to the sign-in request.
+    pub request_id: Option<String>,
+    /// RFC 3986 URIs the user wishes to have resolved as part of authentication.
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Parses a message following the ABNF grammar of EIP-4361.
+    pub fn parse(text: &str) -> Result<Self, SiweParseError> {
+        let mut lines = text.split('\n').peekable();
+
+        let preamble = lines.next().unwrap_or_default();
+        let origin = preamble
+            .strip_suffix(PREAMBLE)
+            .ok_or_else(|| SiweParseError::InvalidPreamble(preamble.to_string()))?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme), domain),
+            None => (None, origin),
+        };
+        if scheme.map_or(false, |scheme| !is_scheme(scheme))
+            || domain.is_empty()
+            || domain.contains(char::is_whitespace)
+        {
+            return Err(SiweParseError::InvalidPreamble(preamble.to_string()));
+        }
+
+        let address = lines.next().ok_or(SiweParseError::MissingField("address"))?;
+        let address =
+            Address::parse_checksummed(address, None).map_err(|_| invalid("address", address))?;
+
+        expect_empty(&mut lines, "statement")?;
+        let statement = match lines.next() {
+            Some("") => None,
+            Some(statement) => {
+                expect_empty(&mut lines, "uri")?;
+                Some(statement.to_string())
+            }
+            None => return Err(SiweParseError::MissingField("uri")),
+        };
+
+        let uri = tagged(&mut lines, URI_TAG, "uri")?;
+        if !is_uri(uri) {
+            return Err(invalid("uri", uri));
+        }
+        let version = tagged(&mut lines, VERSION_TAG, "version")?;
+        if version != "1" {
+            return Err(invalid("version", version));
+        }
+        let chain_id = tagged(&mut lines, CHAIN_ID_TAG, "chain ID")?;
+        let chain_id = chain_id
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+            .then(|| chain_id.parse().ok())
+            .flatten()
+            .ok_or_else(|| invalid("chain ID", chain_id))?;
+        let nonce = tagged(&mut lines, NONCE_TAG, "nonce")?;
+        if nonce.len() < 8 || !nonce.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(invalid("nonce", nonce));
+        }
+        let issued_at = timestamp(tagged(&mut lines, ISSUED_AT_TAG, "issued at")?, "issued at")?;
+
+        let expiration_time = optional_tagged(&mut lines, EXPIRATION_TIME_TAG)
+            .map(|value| timestamp(value, "expiration time"))
+            .transpose()?;
+        let not_before = optional_tagged(&mut lines, NOT_BEFORE_TAG)
+            .map(|value| timestamp(value, "not before"))
+            .transpose()?;
+        let request_id = optional_tagged(&mut lines, REQUEST_ID_TAG).map(str::to_string);
+
+        let mut resources = Vec::new();
+        if lines.next_if_eq(&RESOURCES_TAG).is_some() {
+            while let Some(line) = lines.next_if(|line| line.starts_with(RESOURCE_PREFIX)) {
+                let resource = &line[RESOURCE_PREFIX.len()..];
+                if !is_uri(resource) {
+                    return Err(invalid("resource", resource));
+                }
+                resources.push(resource.to_string());
+            }
+        }
+
+        if let Some(line) = lines.next() {
+            return Err(SiweParseError::UnexpectedLine(line.to_string()));
+        }
+
+        Ok(Self {
+            scheme: scheme.map(str::to_string),
+            domain: domain.to_string(),
+            address,
+            statement,
+            uri: uri.to_string(),
+            version: version.to_string(),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+
+    /// Returns the message text that is signed, after the [EIP-191] prefix is applied by the
+    /// wallet, e.g. via `personal_sign`. This is the same as the [`Display`](fmt::Display)
+    /// implementation.
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    pub fn to_eip191_message(&self) -> String {
+        self.to_string()
+    }
+
+    /// Recovers the signer of the [EIP-191] signed message, and checks that it is the account in
+    /// the message, returning its address.
+    ///
+    /// This only verifies the signature. Whether the message is valid at the current time, or
+    /// for the expected domain and nonce, must be checked separately.
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    #[cfg(feature = "k256")]
+    pub fn verify(&self, signature: &alloy_primitives::Signature) -> Result<Address, SiweError> {
+        let recovered = signature.recover_address_from_msg(self.to_eip191_message())?;
+        if recovered != self.address {
+            return Err(SiweError::SignerMismatch { expected: self.address, recovered });
+        }
+        Ok(recovered)
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        writeln!(f, "{}{PREAMBLE}", self.domain)?;
+        writeln!(f, "{}", self.address.to_checksum(None))?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{URI_TAG}{}", self.uri)?;
+        writeln!(f, "{VERSION_TAG}{}", self.version)?;
+        writeln!(f, "{CHAIN_ID_TAG}{}", self.chain_id)?;
+        writeln!(f, "{NONCE_TAG}{}", self.nonce)?;
+        write!(f, "{ISSUED_AT_TAG}{}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\n{EXPIRATION_TIME_TAG}{expiration_time}")?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\n{NOT_BEFORE_TAG}{not_before}")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\n{REQUEST_ID_TAG}{request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\n{RESOURCES_TAG}")?;
+            for resource in &self.resources {
+                write!(f, "\n{RESOURCE_PREFIX}{resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::str::FromStr for SiweMessage {
+    type Err = SiweParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+type Lines<'a> = Peekable<Split<'a, char>>;
+
+fn invalid(field: &'static str, value: &str) -> SiweParseError {
+    SiweParseError::InvalidField { field, value: value.to_string() }
+}
+
+/// Consumes the empty line before `field`.
+fn expect_empty(lines: &mut Lines<'_>, field: &'static str) -> Result<(), SiweParseError> {
+    match lines.next() {
+        Some("") => Ok(()),
+        Some(line) => Err(SiweParseError::UnexpectedLine(line.to_string())),
+        None => Err(SiweParseError::MissingField(field)),
+    }
+}
+
+fn tagged<'a>(
+    lines: &mut Lines<'a>,
+    tag: &str,
+    field: &'static str,
+) -> Result<&'a str, SiweParseError> {
+    let line = lines.next().ok_or(SiweParseError::MissingField(field))?;
+    line.strip_prefix(tag).ok_or_else(|| SiweParseError::UnexpectedLine(line.to_string()))
+}
+
+fn optional_tagged<'a>(lines: &mut Lines<'a>, tag: &str) -> Option<&'a str> {
+    lines.next_if(|line| line.starts_with(tag)).map(|line| &line[tag.len()..])
+}
+
+fn timestamp(value: &str, field: &'static str) -> Result<String, SiweParseError> {
+    is_rfc3339(value).then(|| value.to_string()).ok_or_else(|| invalid(field, value))
+}
+
+/// Returns `true` if `s` is a valid RFC 3986 scheme.
+fn is_scheme(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    bytes.next().map_or(false, |b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+}
+
+/// Returns `true` if `s` looks like an absolute RFC 3986 URI, i.e. a scheme followed by a colon
+/// and no whitespace.
+fn is_uri(s: &str) -> bool {
+    s.split_once(':').map_or(false, |(scheme, _)| is_scheme(scheme))
+        && !s.contains(char::is_whitespace)
+}
+
+/// Returns `true` if `s` is an RFC 3339 `date-time`, e.g. `2021-09-30T16:25:24.000Z`.
+fn is_rfc3339(s: &str) -> bool {
+    fn number(s: &[u8], range: core::ops::RangeInclusive<u32>) -> bool {
+        s.iter().all(u8::is_ascii_digit)
+            && range.contains(&s.iter().fold(0, |n, &b| n * 10 + (b - b'0') as u32))
+    }
+
+    fn days_in_month(year: &[u8], month: &[u8]) -> u32 {
+        let value = |s: &[u8]| s.iter().fold(0, |n, &b| n * 10 + b.wrapping_sub(b'0') as u32);
+        let year = value(year);
+        match value(month) {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    let s = s.as_bytes();
+    if s.len() < 20 {
+        return false;
+    }
+    let (date_time, mut rest) = s.split_at(19);
+    let valid = number(&date_time[0..4], 0..=9999)
+        && date_time[4] == b'-'
+        && number(&date_time[5..7], 1..=12)
+        && date_time[7] == b'-'
+        && number(&date_time[8..10], 1..=days_in_month(&date_time[0..4], &date_time[5..7]))
+        && matches!(date_time[10], b'T' | b't')
+        && number(&date_time[11..13], 0..=23)
+        && date_time[13] == b':'
+        && number(&date_time[14..16], 0..=59)
+        && date_time[16] == b':'
+        && number(&date_time[17..19], 0..=60);
+    if !valid {
+        return false;
+    }
+
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let digits = fraction.iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 {
+            return false;
+        }
+        rest = &fraction[digits..];
+    }
+    match rest {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', hour @ .., b':', m1, m2] => {
+            number(hour, 0..=23) && hour.len() == 2 && number(&[*m1, *m2], 0..=59)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    // Test vectors from https://github.com/spruceid/siwe/tree/main/test
+    const COUPLE_OF_OPTIONAL_FIELDS: &str =
+        "service.org wants you to sign in with your Ethereum account:
+0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891757
+Issued At: 2021-09-30T16:25:24.000Z
+Resources:
+- ipfs://Qme7ss3ARVgxv6rXqVPiikMJ8u2NLgmgszg13pYrDKEoiu
+- https://example.com/my-web2-claim.json";
+
+    const NO_OPTIONAL_FIELD: &str = "service.org wants you to sign in with your Ethereum account:
+0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891757
+Issued At: 2021-09-30T16:25:24.000Z";
+
+    const ALL_FIELDS: &str = "https://service.org wants you to sign in with your Ethereum account:
+0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946
+
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891757
+Issued At: 2021-09-30T16:25:24.000Z
+Expiration Time: 2021-10-01T16:25:24+02:00
+Not Before: 2021-09-30T16:25:24Z
+Request ID: 200
+Resources:
+- ipfs://Qme7ss3ARVgxv6rXqVPiikMJ8u2NLgmgszg13pYrDKEoiu";
+
+    fn message() -> SiweMessage {
+        SiweMessage {
+            scheme: None,
+            domain: "service.org".into(),
+            address: address!("e5A12547fe4E872D192E3eCecb76F2Ce1aeA4946"),
+            statement: Some(
+                "I accept the ServiceOrg Terms of Service: https://service.org/tos".into(),
+            ),
+            uri: "https://service.org/login".into(),
+            version: "1".into(),
+            chain_id: 1,
+            nonce: "32891757".into(),
+            issued_at: "2021-09-30T16:25:24.000Z".into(),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(SiweMessage::parse(NO_OPTIONAL_FIELD).unwrap(), message());
+
+        let mut expected = message();
+        expected.resources = vec![
+            "ipfs://Qme7ss3ARVgxv6rXqVPiikMJ8u2NLgmgszg13pYrDKEoiu".into(),
+            "https://example.com/my-web2-claim.json".into(),
+        ];
+        assert_eq!(SiweMessage::parse(COUPLE_OF_OPTIONAL_FIELDS).unwrap(), expected);
+
+        let parsed = SiweMessage::parse(ALL_FIELDS).unwrap();
+        assert_eq!(parsed.scheme.as_deref(), Some("https"));
+        assert_eq!(parsed.statement, None);
+        assert_eq!(parsed.expiration_time.as_deref(), Some("2021-10-01T16:25:24+02:00"));
+        assert_eq!(parsed.not_before.as_deref(), Some("2021-09-30T16:25:24Z"));
+        assert_eq!(parsed.request_id.as_deref(), Some("200"));
+        assert_eq!(parsed.resources.len(), 1);
+
+        for text in [NO_OPTIONAL_FIELD, COUPLE_OF_OPTIONAL_FIELDS, ALL_FIELDS] {
+            assert_eq!(SiweMessage::parse(text).unwrap().to_eip191_message(), text);
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let cases = [
+            (
+                NO_OPTIONAL_FIELD.replace(
+                    "0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946",
+                    "0xe5a12547fe4e872d192e3ececb76f2ce1aea4946",
+                ),
+                invalid("address", "0xe5a12547fe4e872d192e3ececb76f2ce1aea4946"),
+            ),
+            (NO_OPTIONAL_FIELD.replace("Version: 1", "Version: 2"), invalid("version", "2")),
+            (
+                NO_OPTIONAL_FIELD.replace("Nonce: 32891757", "Nonce: 1234567"),
+                invalid("nonce", "1234567"),
+            ),
+            (NO_OPTIONAL_FIELD.replace("Chain ID: 1", "Chain ID: +1"), invalid("chain ID", "+1")),
+            (NO_OPTIONAL_FIELD.replace(".000Z", ""), invalid("issued at", "2021-09-30T16:25:24")),
+            (
+                NO_OPTIONAL_FIELD.replace("wants you to sign in", "wants you to sign"),
+                SiweParseError::InvalidPreamble(
+                    "service.org wants you to sign with your Ethereum account:".into(),
+                ),
+            ),
+            (
+                NO_OPTIONAL_FIELD.replace("\nNonce: 32891757", ""),
+                SiweParseError::UnexpectedLine("Issued At: 2021-09-30T16:25:24.000Z".into()),
+            ),
+            (format!("{NO_OPTIONAL_FIELD}\nfoo"), SiweParseError::UnexpectedLine("foo".into())),
+            (
+                NO_OPTIONAL_FIELD.split("\nURI").next().unwrap().to_string(),
+                SiweParseError::MissingField("uri"),
+            ),
+            (
+                NO_OPTIONAL_FIELD.split("\n\n").next().unwrap().to_string(),
+                SiweParseError::MissingField("statement"),
+            ),
+            (
+                NO_OPTIONAL_FIELD.split("\n\nURI").next().unwrap().to_string(),
+                SiweParseError::MissingField("uri"),
+            ),
+            (
+                NO_OPTIONAL_FIELD.split("\nChain ID").next().unwrap().to_string(),
+                SiweParseError::MissingField("chain ID"),
+            ),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(SiweMessage::parse(&text).unwrap_err(), expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn rfc3339() {
+        assert!(is_rfc3339("2021-09-30T16:25:24Z"));
+        assert!(is_rfc3339("2021-09-30t16:25:24.123456-07:30"));
+        assert!(!is_rfc3339("2021-13-30T16:25:24Z"));
+        assert!(!is_rfc3339("2021-02-31T16:25:24Z"));
+        assert!(!is_rfc3339("2021-04-31T16:25:24Z"));
+        assert!(!is_rfc3339("2021-02-29T16:25:24Z"));
+        assert!(!is_rfc3339("1900-02-29T16:25:24Z"));
+        assert!(is_rfc3339("2024-02-29T16:25:24Z"));
+        assert!(is_rfc3339("2000-02-29T16:25:24Z"));
+        assert!(is_rfc3339("2021-12-31T16:25:24Z"));
+        assert!(!is_rfc3339("2021-09-30T16:25:24.Z"));
+        assert!(!is_rfc3339("2021-09-30T16:25:24+0200"));
+        assert!(!is_rfc3339("2021-09-30 16:25:24Z"));
+    }
+
+    #[cfg(feature = "k256")]
+    #[test]
+    fn verify() {
+        use alloy_primitives::{eip191_hash_message, Signature};
+        use k256::ecdsa::SigningKey;
+
+        let key = SigningKey::from_bytes(&[1; 32].into()).unwrap();
+        let mut message = message();
+        message.address = Address::from_public_key(key.verifying_key());
+
+        let hash = eip191_hash_message(message.to_eip191_message());
+        let signature = Signature::from(key.sign_prehash_recoverable(hash.as_slice()).unwrap());
+        assert_eq!(message.verify(&signature).unwrap(), message.address);
+
+        // The signature does not match once the message is changed.
+        message.address = Address::ZERO;
+        let err = message.verify(&signature).unwrap_err();
+        assert!(matches!(err, SiweError::SignerMismatch { expected, .. } if expected.is_zero()));
+    }
+}
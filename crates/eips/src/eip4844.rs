@@ -1,6 +1,17 @@
 //! [EIP-4844] constants and helpers.
 //!
+//! Forks may change the blob gas target and maximum per block, e.g. with [EIP-7742]. The helpers
+//! that depend on them use [`TARGET_DATA_GAS_PER_BLOCK`] and [`MAX_DATA_GAS_PER_BLOCK`], and have
+//! a `_with_target`, `_with_max` or `_with_limits` variant that takes them as parameters instead.
+//! Only the utilization helpers, [`calc_blobspace_utilization`] and [`calc_max_utilization`], and
+//! [`block_blob_price_summary`] always use the EIP-4844 constants.
+//!
 //! [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+//! [EIP-7742]: https://eips.ethereum.org/EIPS/eip-7742
+
+use alloy_primitives::{B256, U256};
+use core::fmt;
+use sha2::{Digest, Sha256};
 
 /// Size a single field element in bytes.
 pub const FIELD_ELEMENT_BYTES: u64 = 32;
@@ -8,6 +19,9 @@ pub const FIELD_ELEMENT_BYTES: u64 = 32;
 /// How many field elements are stored in a single data blob.
 pub const FIELD_ELEMENTS_PER_BLOB: u64 = 4096;
 
+/// How many bytes are in a single data blob.
+pub const BYTES_PER_BLOB: u64 = FIELD_ELEMENT_BYTES * FIELD_ELEMENTS_PER_BLOB;
+
 /// Gas consumption of a single data blob.
 pub const DATA_GAS_PER_BLOB: u64 = 131_072u64; // 32*4096 = 131072 == 2^17 == 0x20000
 
@@ -32,13 +46,359 @@ pub const BLOB_TX_MIN_BLOB_GASPRICE: u128 = 1u128;
 /// Commitment version of a KZG commitment
 pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
 
+/// [EIP-4844] errors.
+///
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eip4844Error {
+    /// Adding the blobs would exceed the block's blob gas limit.
+    #[error("Block blob gas exceeded. Used {used}, requested {requested}, limit {limit}.")]
+    BlockBlobGasExceeded {
+        /// The blob gas already used in the block.
+        used: u64,
+        /// The blob gas requested.
+        requested: u64,
+        /// The block's blob gas limit.
+        limit: u64,
+    },
+    /// The maximum fee per blob gas is below the blob gas price.
+    #[error("Blob fee too low. Max fee per blob gas {have}, blob gas price {need}.")]
+    BlobFeeTooLow {
+        /// The maximum fee per blob gas the sender is willing to pay.
+        have: u128,
+        /// The blob gas price.
+        need: u128,
+    },
+    /// The header's blob gas used does not match the blob gas used by the block's transactions.
+    #[error("Blob gas used mismatch. Header {header}, computed {computed}.")]
+    BlobGasUsedMismatch {
+        /// The blob gas used according to the header.
+        header: u64,
+        /// The blob gas used by the block's transactions.
+        computed: u64,
+    },
+    /// The blob gas used exceeds the maximum per block, usually [`MAX_DATA_GAS_PER_BLOCK`].
+    #[error("Blob gas used exceeds the block maximum. Got {0}.")]
+    BlobGasUsedExceedsMax(u64),
+    /// The blob history has a different number of excess blob gas values and blob counts.
+    #[error("Blob history length mismatch. Got {excess_values} excess blob gas values and {blob_counts} blob counts.")]
+    HistoryLengthMismatch {
+        /// The number of excess blob gas values.
+        excess_values: usize,
+        /// The number of blob counts.
+        blob_counts: usize,
+    },
+}
+
+/// Errors when validating a blob transaction's versioned hashes against its sidecar commitments,
+/// see [`validate_versioned_hashes`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionedHashError {
+    /// The number of versioned hashes and commitments differ.
+    #[error("Versioned hash count mismatch. Got {hashes} hashes and {commitments} commitments.")]
+    LengthMismatch {
+        /// The number of versioned hashes.
+        hashes: usize,
+        /// The number of commitments.
+        commitments: usize,
+    },
+    /// The versioned hash at this index does not match its commitment.
+    #[error("Versioned hash mismatch. Got index {0}.")]
+    Mismatch(usize),
+}
+
+/// Tracks the blob gas used by the transactions of a block, e.g. while a transaction pool builds
+/// a block.
+///
+/// Accounting is in whole blobs of [`DATA_GAS_PER_BLOB`] each.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlobGasAccounting {
+    /// The blob gas used so far.
+    pub used: u64,
+    /// The maximum blob gas that may be used.
+    pub limit: u64,
+}
+
+impl Default for BlobGasAccounting {
+    fn default() -> Self {
+        Self::new(MAX_DATA_GAS_PER_BLOCK)
+    }
+}
+
+impl BlobGasAccounting {
+    /// Creates an empty accounting with the given blob gas limit.
+    pub const fn new(limit: u64) -> Self {
+        Self { used: 0, limit }
+    }
+
+    /// Adds the blob gas of `blob_count` blobs, or leaves the accounting unchanged and returns an
+    /// error if this would exceed the limit.
+    pub fn try_add(&mut self, blob_count: usize) -> Result<(), Eip4844Error> {
+        let requested = (blob_count as u64).saturating_mul(DATA_GAS_PER_BLOB);
+        match self.used.checked_add(requested) {
+            Some(used) if used <= self.limit => {
+                self.used = used;
+                Ok(())
+            }
+            _ => Err(Eip4844Error::BlockBlobGasExceeded {
+                used: self.used,
+                requested,
+                limit: self.limit,
+            }),
+        }
+    }
+
+    /// Removes the blob gas of `blob_count` blobs, e.g. when a transaction is evicted.
+    pub fn remove(&mut self, blob_count: usize) {
+        let blob_gas = (blob_count as u64).saturating_mul(DATA_GAS_PER_BLOB);
+        self.used = self.used.saturating_sub(blob_gas);
+    }
+
+    /// Returns the number of blobs that still fit within the limit.
+    pub const fn remaining_blobs(&self) -> usize {
+        (self.limit.saturating_sub(self.used) / DATA_GAS_PER_BLOB) as usize
+    }
+
+    /// Returns `true` if no more blobs fit within the limit.
+    pub const fn is_full(&self) -> bool {
+        self.remaining_blobs() == 0
+    }
+}
+
+/// Accumulates the blob gas used by the transactions added to a block, rejecting additions that
+/// would exceed the block's blob gas limit, [`MAX_DATA_GAS_PER_BLOCK`] unless created with
+/// [`BlobGasAccumulator::with_limit`].
+///
+/// Unlike [`BlobGasAccounting`], blobs can only be added, which matches building a block in
+/// order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlobGasAccumulator {
+    accounting: BlobGasAccounting,
+}
+
+impl BlobGasAccumulator {
+    /// Creates an empty accumulator.
+    pub const fn new() -> Self {
+        Self::with_limit(MAX_DATA_GAS_PER_BLOCK)
+    }
+
+    /// Creates an empty accumulator with the given maximum blob gas per block instead of
+    /// [`MAX_DATA_GAS_PER_BLOCK`].
+    pub const fn with_limit(max_blob_gas: u64) -> Self {
+        Self { accounting: BlobGasAccounting::new(max_blob_gas) }
+    }
+
+    /// Adds the blob gas of `count` blobs, returning the new total, or leaves the total unchanged
+    /// and returns an error if this would exceed the block's limit.
+    pub fn try_add_blobs(&mut self, count: u64) -> Result<u64, Eip4844Error> {
+        self.accounting.try_add(usize::try_from(count).unwrap_or(usize::MAX))?;
+        Ok(self.total())
+    }
+
+    /// Returns the total blob gas used so far.
+    pub const fn total(&self) -> u64 {
+        self.accounting.used
+    }
+}
+
+/// Estimates how many more blobs fit in the next block, and the blob pricing that results, as
+/// transactions of a pool are committed to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlobGasEstimator {
+    /// The excess blob gas of the next block.
+    pub base_excess: u64,
+    /// The number of blobs committed to the next block so far.
+    pub committed_blobs: u64,
+    /// The target blob gas per block, usually [`TARGET_DATA_GAS_PER_BLOCK`].
+    pub target_blob_gas: u64,
+    /// The maximum blob gas per block, usually [`MAX_DATA_GAS_PER_BLOCK`].
+    pub max_blob_gas: u64,
+}
+
+impl Default for BlobGasEstimator {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl BlobGasEstimator {
+    /// Creates an estimator for a block with the given excess blob gas and no blobs committed.
+    pub const fn new(base_excess: u64) -> Self {
+        Self::with_limits(base_excess, TARGET_DATA_GAS_PER_BLOCK, MAX_DATA_GAS_PER_BLOCK)
+    }
+
+    /// Creates an estimator as [`BlobGasEstimator::new`], with the given target and maximum blob
+    /// gas per block instead of [`TARGET_DATA_GAS_PER_BLOCK`] and [`MAX_DATA_GAS_PER_BLOCK`].
+    pub const fn with_limits(base_excess: u64, target_blob_gas: u64, max_blob_gas: u64) -> Self {
+        Self { base_excess, committed_blobs: 0, target_blob_gas, max_blob_gas }
+    }
+
+    /// Returns `true` if `count` more blobs fit in the block.
+    pub const fn can_fit_blobs(&self, count: u64) -> bool {
+        count <= self.remaining_blob_capacity()
+    }
+
+    /// Commits `count` more blobs to the block, or leaves the estimator unchanged and returns an
+    /// error if they do not fit.
+    pub fn add_blobs(&mut self, count: u64) -> Result<(), Eip4844Error> {
+        if !self.can_fit_blobs(count) {
+            return Err(Eip4844Error::BlockBlobGasExceeded {
+                used: self.committed_blobs.saturating_mul(DATA_GAS_PER_BLOB),
+                requested: count.saturating_mul(DATA_GAS_PER_BLOB),
+                limit: self.max_blob_gas,
+            });
+        }
+        self.committed_blobs += count;
+        Ok(())
+    }
+
+    /// Returns the number of blobs that still fit in the block.
+    pub const fn remaining_blob_capacity(&self) -> u64 {
+        (self.max_blob_gas / DATA_GAS_PER_BLOB).saturating_sub(self.committed_blobs)
+    }
+
+    /// Returns the excess blob gas of the block after this one, if the block includes the
+    /// committed blobs.
+    pub const fn projected_next_excess(&self) -> u64 {
+        calc_excess_blob_gas_with_target(
+            self.base_excess,
+            self.committed_blobs.saturating_mul(DATA_GAS_PER_BLOB),
+            self.target_blob_gas,
+        )
+    }
+
+    /// Returns the blob gas price of the block after this one, if the block includes the
+    /// committed blobs.
+    pub fn projected_next_blob_gasprice(&self) -> u128 {
+        calc_blob_gasprice(self.projected_next_excess())
+    }
+}
+
+/// Calculates the versioned hash of a KZG commitment: its `sha256` hash with the first byte
+/// replaced by [`VERSIONED_HASH_VERSION_KZG`].
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
+/// (`kzg_to_versioned_hash`).
+pub fn kzg_to_versioned_hash(commitment: &[u8; 48]) -> B256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    B256::new(hash)
+}
+
+/// Validates that the versioned hashes declared by a blob transaction match the commitments of
+/// its sidecar, i.e. that each hash equals the [`kzg_to_versioned_hash`] of the commitment at the
+/// same index.
+pub fn validate_versioned_hashes(
+    hashes: &[B256],
+    commitments: &[[u8; 48]],
+) -> Result<(), VersionedHashError> {
+    if hashes.len() != commitments.len() {
+        return Err(VersionedHashError::LengthMismatch {
+            hashes: hashes.len(),
+            commitments: commitments.len(),
+        });
+    }
+    match hashes.iter().zip(commitments).position(|(hash, c)| *hash != kzg_to_versioned_hash(c)) {
+        Some(index) => Err(VersionedHashError::Mismatch(index)),
+        None => Ok(()),
+    }
+}
+
 /// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used` and `excess_blob_gas`.
 ///
 /// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
 /// (`calc_excess_blob_gas`).
 #[inline]
 pub const fn calc_excess_blob_gas(parent_excess_blob_gas: u64, parent_blob_gas_used: u64) -> u64 {
-    (parent_excess_blob_gas + parent_blob_gas_used).saturating_sub(TARGET_DATA_GAS_PER_BLOCK)
+    calc_excess_blob_gas_with_target(
+        parent_excess_blob_gas,
+        parent_blob_gas_used,
+        TARGET_DATA_GAS_PER_BLOCK,
+    )
+}
+
+/// Calculates the `excess_blob_gas` as [`calc_excess_blob_gas`], with the given target blob gas
+/// per block instead of [`TARGET_DATA_GAS_PER_BLOCK`], for forks that change the target.
+#[inline]
+pub const fn calc_excess_blob_gas_with_target(
+    parent_excess_blob_gas: u64,
+    parent_blob_gas_used: u64,
+    target_blob_gas: u64,
+) -> u64 {
+    parent_excess_blob_gas.saturating_add(parent_blob_gas_used).saturating_sub(target_blob_gas)
+}
+
+/// Estimates the `excess_blob_gas` of the next block from the excess blob gas and blob counts of
+/// recent blocks, in ascending block order.
+///
+/// The next block's excess blob gas only depends on its parent, so this is
+/// [`calc_excess_blob_gas`] applied to the most recent entry, or `0` without any history. The
+/// result saturates at [`u64::MAX`].
+///
+/// Fails with [`Eip4844Error::HistoryLengthMismatch`] if `recent_excess_values` and
+/// `recent_blob_counts` have different lengths.
+pub const fn estimate_next_excess_blob_gas(
+    recent_excess_values: &[u64],
+    recent_blob_counts: &[u64],
+) -> Result<u64, Eip4844Error> {
+    if recent_excess_values.len() != recent_blob_counts.len() {
+        return Err(Eip4844Error::HistoryLengthMismatch {
+            excess_values: recent_excess_values.len(),
+            blob_counts: recent_blob_counts.len(),
+        });
+    }
+    Ok(match (recent_excess_values.last(), recent_blob_counts.last()) {
+        (Some(&excess_blob_gas), Some(&blob_count)) => {
+            calc_excess_blob_gas(excess_blob_gas, get_total_blob_gas(blob_count))
+        }
+        _ => 0,
+    })
+}
+
+/// Returns the average number of blobs per block of recent blocks, or `0.0` without any history.
+pub fn rolling_average_blob_count(recent_blob_counts: &[u64]) -> f64 {
+    if recent_blob_counts.is_empty() {
+        return 0.0;
+    }
+    recent_blob_counts.iter().map(|&count| count as f64).sum::<f64>()
+        / recent_blob_counts.len() as f64
+}
+
+/// Predicts the `excess_blob_gas` `n` blocks after a block with the given `current_excess`,
+/// assuming each of these blocks contains `avg_blobs_per_block` blobs, e.g. as computed by
+/// [`rolling_average_blob_count`].
+///
+/// The blob gas used per block is rounded down to a whole unit of gas.
+pub fn predict_excess_blob_gas_n_blocks_ahead(
+    current_excess: u64,
+    avg_blobs_per_block: f64,
+    n: u64,
+) -> u64 {
+    predict_excess_blob_gas_n_blocks_ahead_with_target(
+        current_excess,
+        avg_blobs_per_block,
+        n,
+        TARGET_DATA_GAS_PER_BLOCK,
+    )
+}
+
+/// Predicts the `excess_blob_gas` as [`predict_excess_blob_gas_n_blocks_ahead`], with the given
+/// target blob gas per block instead of [`TARGET_DATA_GAS_PER_BLOCK`].
+pub fn predict_excess_blob_gas_n_blocks_ahead_with_target(
+    current_excess: u64,
+    avg_blobs_per_block: f64,
+    n: u64,
+    target_blob_gas: u64,
+) -> u64 {
+    // Float to integer casts saturate, and map negative values and NaN to zero.
+    let blob_gas_used = (avg_blobs_per_block * DATA_GAS_PER_BLOB as f64) as u64;
+    // Each block changes the excess blob gas by the same amount, and once it has dropped to zero
+    // it stays there, so applying `calc_excess_blob_gas` `n` times is equivalent to:
+    if blob_gas_used >= target_blob_gas {
+        current_excess.saturating_add(n.saturating_mul(blob_gas_used - target_blob_gas))
+    } else {
+        current_excess.saturating_sub(n.saturating_mul(target_blob_gas - blob_gas_used))
+    }
 }
 
 /// Calculates the blob gas price from the header's excess blob gas field.
@@ -54,6 +414,584 @@ pub fn calc_blob_gasprice(excess_blob_gas: u64) -> u128 {
     )
 }
 
+/// Calculates the fee paid for the blobs of a transaction carrying `tx_blob_count` blobs, included
+/// in a block with the given `excess_blob_gas`.
+///
+/// The result saturates at `u128::MAX`.
+pub fn calc_blob_fee_for_transaction(tx_blob_count: usize, excess_blob_gas: u64) -> u128 {
+    blob_fee(tx_blob_count as u64, calc_blob_gasprice(excess_blob_gas))
+}
+
+/// Returns the fee paid for `blob_count` blobs at the given blob gas price, saturating at
+/// `u128::MAX`.
+const fn blob_fee(blob_count: u64, blob_gasprice: u128) -> u128 {
+    (DATA_GAS_PER_BLOB as u128).saturating_mul(blob_count as u128).saturating_mul(blob_gasprice)
+}
+
+/// Checks that a transaction's `max_fee_per_blob_gas` covers the blob gas price of a block with
+/// the given `excess_blob_gas`, e.g. before broadcasting it.
+///
+/// This is the blob gas counterpart of checking the max fee per gas against the base fee.
+pub fn validate_blob_transaction_gas(
+    max_fee_per_blob_gas: u128,
+    excess_blob_gas: u64,
+) -> Result<(), Eip4844Error> {
+    let blob_gasprice = calc_blob_gasprice(excess_blob_gas);
+    if max_fee_per_blob_gas < blob_gasprice {
+        return Err(Eip4844Error::BlobFeeTooLow {
+            have: max_fee_per_blob_gas,
+            need: blob_gasprice,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the minimum `max_fee_per_blob_gas` with which a transaction carrying `blobs` blobs is
+/// accepted in a block with the given `excess_blob_gas`, i.e. the blob gas price.
+///
+/// Fails if the blobs exceed the limit of [`MAX_BLOBS_PER_BLOCK`], so that the transaction cannot
+/// be included at any fee.
+pub fn min_blob_fee_for_inclusion(
+    excess_blob_gas: u64,
+    blobs: usize,
+) -> Result<u128, Eip4844Error> {
+    min_blob_fee_for_inclusion_with_max(excess_blob_gas, blobs, MAX_DATA_GAS_PER_BLOCK)
+}
+
+/// Returns the minimum `max_fee_per_blob_gas` as [`min_blob_fee_for_inclusion`], with the given
+/// maximum blob gas per block instead of [`MAX_DATA_GAS_PER_BLOCK`].
+pub fn min_blob_fee_for_inclusion_with_max(
+    excess_blob_gas: u64,
+    blobs: usize,
+    max_blob_gas: u64,
+) -> Result<u128, Eip4844Error> {
+    let requested = get_total_blob_gas(blobs as u64);
+    if requested > max_blob_gas {
+        return Err(Eip4844Error::BlockBlobGasExceeded { used: 0, requested, limit: max_blob_gas });
+    }
+    Ok(calc_blob_gasprice(excess_blob_gas))
+}
+
+/// Returns a `max_fee_per_blob_gas` of `buffer_bps` basis points above the blob gas price of a
+/// block with the given `excess_blob_gas`, so that the transaction remains includable if the blob
+/// gas price rises before it is.
+///
+/// The buffer is rounded up, and the result saturates at `u128::MAX`. A buffer of `0` returns
+/// the blob gas price.
+pub fn recommended_blob_fee_with_buffer(excess_blob_gas: u64, buffer_bps: u64) -> u128 {
+    let blob_gasprice = calc_blob_gasprice(excess_blob_gas);
+    let buffer = blob_gasprice.saturating_mul(buffer_bps as u128).saturating_add(9_999) / 10_000;
+    blob_gasprice.saturating_add(buffer)
+}
+
+/// Returns `true` if a replacement blob transaction's `new_max` fee per blob gas exceeds the
+/// replaced transaction's `old_max` by at least `min_bump_percent` percent, i.e.
+/// `new_max >= old_max * (100 + min_bump_percent) / 100`, as required by mempools to replace a
+/// pending blob transaction.
+///
+/// The product is computed without overflow, so the result is exact for all inputs.
+pub fn meets_blob_fee_bump(old_max: u128, new_max: u128, min_bump_percent: u64) -> bool {
+    let required =
+        U256::from(old_max) * (U256::from(min_bump_percent) + U256::from(100)) / U256::from(100);
+    U256::from(new_max) >= required
+}
+
+/// Calculates the fee paid for the blobs of a transaction, as [`calc_blob_fee_for_transaction`],
+/// checking that the transaction's `max_fee_per_blob_gas` covers the blob gas price.
+///
+/// The sender pays the blob gas price, not the maximum fee.
+pub fn calc_max_blob_fee_for_transaction(
+    tx_blob_count: usize,
+    max_fee_per_blob_gas: u128,
+    excess_blob_gas: u64,
+) -> Result<u128, Eip4844Error> {
+    validate_blob_transaction_gas(max_fee_per_blob_gas, excess_blob_gas)?;
+    Ok(calc_blob_fee_for_transaction(tx_blob_count, excess_blob_gas))
+}
+
+/// The blob pricing of a block, computed once from its excess blob gas so that it can be applied
+/// to all of the block's transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlockBlobContext {
+    excess_blob_gas: u64,
+    blob_gasprice: u128,
+}
+
+impl BlockBlobContext {
+    /// Creates the context of a block with the given `excess_blob_gas`, computing its blob gas
+    /// price with [`calc_blob_gasprice`].
+    pub fn new(excess_blob_gas: u64) -> Self {
+        Self { excess_blob_gas, blob_gasprice: calc_blob_gasprice(excess_blob_gas) }
+    }
+
+    /// Returns the excess blob gas of the block.
+    pub const fn excess_blob_gas(&self) -> u64 {
+        self.excess_blob_gas
+    }
+
+    /// Returns the blob gas price of the block.
+    pub const fn blob_gasprice(&self) -> u128 {
+        self.blob_gasprice
+    }
+
+    /// Returns the fee paid for `blob_count` blobs in the block, as
+    /// [`calc_blob_fee_for_transaction`].
+    ///
+    /// The result saturates at `u128::MAX`.
+    pub const fn cost_for_blobs(&self, blob_count: u64) -> u128 {
+        blob_fee(blob_count, self.blob_gasprice)
+    }
+}
+
+/// A summary of the blob pricing of a block, as computed by [`block_blob_price_summary`].
+///
+/// Its [`Display`](fmt::Display) implementation formats the prices in gwei, e.g. for a UI.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlobPriceSummary {
+    /// The excess blob gas of the block.
+    pub excess_blob_gas: u64,
+    /// The number of blobs in the block.
+    pub blob_count: u64,
+    /// The blob gas price of the block, in wei.
+    pub blob_gasprice: u128,
+    /// The fee paid for all blobs of the block, in wei.
+    pub total_blob_fee: u128,
+    /// The excess blob gas of the next block.
+    pub next_excess: u64,
+    /// The blob gas price of the next block, in wei.
+    pub next_blob_gasprice: u128,
+}
+
+impl fmt::Display for BlobPriceSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Blob Price: {}, Blob Count: {}, Total Blob Fee: {}, Excess Blob Gas: {}, \
+             Next Excess Blob Gas: {}, Next Blob Price: {}",
+            Gwei(self.blob_gasprice),
+            self.blob_count,
+            Gwei(self.total_blob_fee),
+            self.excess_blob_gas,
+            self.next_excess,
+            Gwei(self.next_blob_gasprice),
+        )
+    }
+}
+
+/// Formats an amount of wei in gwei, truncated to two decimals.
+struct Gwei(u128);
+
+impl fmt::Display for Gwei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const WEI_PER_GWEI: u128 = 1_000_000_000;
+        let cents = self.0 % WEI_PER_GWEI / (WEI_PER_GWEI / 100);
+        write!(f, "{}.{cents:02} Gwei", self.0 / WEI_PER_GWEI)
+    }
+}
+
+/// Summarizes the blob pricing of a block with the given `excess_blob_gas`, carrying `blob_count`
+/// blobs.
+///
+/// The fees and the next excess blob gas saturate at their maximum value instead of overflowing.
+pub fn block_blob_price_summary(excess_blob_gas: u64, blob_count: u64) -> BlobPriceSummary {
+    let context = BlockBlobContext::new(excess_blob_gas);
+    let next_excess = calc_excess_blob_gas(excess_blob_gas, get_total_blob_gas(blob_count));
+    BlobPriceSummary {
+        excess_blob_gas,
+        blob_count,
+        blob_gasprice: context.blob_gasprice(),
+        total_blob_fee: context.cost_for_blobs(blob_count),
+        next_excess,
+        next_blob_gasprice: checked_calc_blob_gasprice(next_excess).unwrap_or(u128::MAX),
+    }
+}
+
+/// Returns the number of consecutive full blocks, starting from a block with the given
+/// `excess_blob_gas`, after which the blob gas price is at least double its current value.
+///
+/// Each full block uses [`MAX_DATA_GAS_PER_BLOCK`], increasing the excess blob gas by
+/// [`TARGET_DATA_GAS_PER_BLOCK`].
+///
+/// Very large excess blob gas values overflow the blob gas price computation. This stops as soon as
+/// the price can no longer be computed, so it returns `0` if the current price cannot be computed.
+pub fn blocks_to_double_blob_fee(excess_blob_gas: u64) -> u64 {
+    blocks_to_double_blob_fee_with_limits(
+        excess_blob_gas,
+        TARGET_DATA_GAS_PER_BLOCK,
+        MAX_DATA_GAS_PER_BLOCK,
+    )
+}
+
+/// Returns the number of consecutive full blocks after which the blob gas price is at least
+/// double its current value, as [`blocks_to_double_blob_fee`], with the given target and maximum
+/// blob gas per block instead of [`TARGET_DATA_GAS_PER_BLOCK`] and [`MAX_DATA_GAS_PER_BLOCK`].
+///
+/// Returns `u64::MAX` if `max_blob_gas` does not exceed `target_blob_gas`, as the price then
+/// never rises.
+pub fn blocks_to_double_blob_fee_with_limits(
+    excess_blob_gas: u64,
+    target_blob_gas: u64,
+    max_blob_gas: u64,
+) -> u64 {
+    let Some(target) = checked_calc_blob_gasprice(excess_blob_gas) else { return 0 };
+    if max_blob_gas <= target_blob_gas {
+        return u64::MAX;
+    }
+    let target = target.saturating_mul(2);
+    let mut excess_blob_gas = excess_blob_gas;
+    let mut blocks = 0;
+    while checked_calc_blob_gasprice(excess_blob_gas).map_or(false, |price| price < target) {
+        excess_blob_gas =
+            calc_excess_blob_gas_with_target(excess_blob_gas, max_blob_gas, target_blob_gas);
+        blocks += 1;
+    }
+    blocks
+}
+
+/// Returns the number of consecutive empty blocks, starting from a block with the given
+/// `excess_blob_gas`, after which the blob gas price is at most half its current value.
+///
+/// Each empty block decreases the excess blob gas by [`TARGET_DATA_GAS_PER_BLOCK`]. Since the blob
+/// gas price cannot fall below [`BLOB_TX_MIN_BLOB_GASPRICE`], this stops once the excess blob gas
+/// reaches zero, even if the price has not been halved.
+///
+/// Like [`blocks_to_double_blob_fee`], this returns `0` if the current price cannot be computed.
+pub fn blocks_to_halve_blob_fee(excess_blob_gas: u64) -> u64 {
+    blocks_to_halve_blob_fee_with_target(excess_blob_gas, TARGET_DATA_GAS_PER_BLOCK)
+}
+
+/// Returns the number of consecutive empty blocks after which the blob gas price is at most half
+/// its current value, as [`blocks_to_halve_blob_fee`], with the given target blob gas per block
+/// instead of [`TARGET_DATA_GAS_PER_BLOCK`].
+///
+/// Returns `u64::MAX` if `target_blob_gas` is zero and the price can be halved, as the excess
+/// blob gas then never decreases.
+pub fn blocks_to_halve_blob_fee_with_target(excess_blob_gas: u64, target_blob_gas: u64) -> u64 {
+    let Some(target) = checked_calc_blob_gasprice(excess_blob_gas) else { return 0 };
+    if target_blob_gas == 0 && excess_blob_gas > 0 {
+        return u64::MAX;
+    }
+    let target = target / 2;
+    let mut excess_blob_gas = excess_blob_gas;
+    let mut blocks = 0;
+    // The price only decreases from here, so it can always be computed.
+    while excess_blob_gas > 0 && calc_blob_gasprice(excess_blob_gas) > target {
+        excess_blob_gas = calc_excess_blob_gas_with_target(excess_blob_gas, 0, target_blob_gas);
+        blocks += 1;
+    }
+    blocks
+}
+
+/// The blob gas of consecutive blocks, in ascending block order, used to compute statistics of
+/// their blob gas prices.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlobFeeHistory {
+    /// The `excess_blob_gas` of each block.
+    pub excess_blob_gas: Vec<u64>,
+    /// The `blob_gas_used` of each block.
+    pub blob_gas_used: Vec<u64>,
+}
+
+impl BlobFeeHistory {
+    /// Appends a block, dropping the oldest blocks so that at most `max_size` are stored.
+    pub fn push_block(&mut self, excess_blob_gas: u64, blob_gas_used: u64, max_size: usize) {
+        self.excess_blob_gas.push(excess_blob_gas);
+        self.blob_gas_used.push(blob_gas_used);
+        for values in [&mut self.excess_blob_gas, &mut self.blob_gas_used] {
+            let excess = values.len().saturating_sub(max_size);
+            values.drain(..excess);
+        }
+    }
+
+    /// Returns the mean blob gas price of the stored blocks, or `0` if there are none.
+    pub fn average_blob_fee(&self) -> u128 {
+        let prices = self.blob_gasprices();
+        if prices.is_empty() {
+            return 0;
+        }
+        prices.iter().fold(0u128, |sum, price| sum.saturating_add(*price)) / prices.len() as u128
+    }
+
+    /// Returns the median blob gas price of the stored blocks, or `0` if there are none.
+    ///
+    /// For an even number of blocks, this is the mean of the two middle prices.
+    pub fn median_blob_fee(&self) -> u128 {
+        let prices = self.blob_gasprices();
+        let mid = prices.len() / 2;
+        match prices.len() {
+            0 => 0,
+            // The prices are sorted, so this cannot overflow.
+            len if len % 2 == 0 => prices[mid - 1] + (prices[mid] - prices[mid - 1]) / 2,
+            _ => prices[mid],
+        }
+    }
+
+    /// Returns the blob gas price at the given percentile, between `0.0` and `100.0`, of the
+    /// stored blocks, or `0` if there are none.
+    ///
+    /// This uses the nearest-rank method, so the result is always one of the stored blocks'
+    /// prices.
+    pub fn percentile_blob_fee(&self, pct: f64) -> u128 {
+        let prices = self.blob_gasprices();
+        if prices.is_empty() {
+            return 0;
+        }
+        let rank = (pct.clamp(0.0, 100.0) / 100.0 * prices.len() as f64).ceil() as usize;
+        prices[rank.saturating_sub(1).min(prices.len() - 1)]
+    }
+
+    /// Returns the blob gas prices of the stored blocks, sorted in ascending order.
+    fn blob_gasprices(&self) -> Vec<u128> {
+        let mut prices: Vec<_> =
+            self.excess_blob_gas.iter().map(|e| calc_blob_gasprice(*e)).collect();
+        prices.sort_unstable();
+        prices
+    }
+}
+
+/// The fees paid by a blob transaction, as computed by [`calc_total_blob_tx_fee`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TotalTxFee {
+    /// The gas used by the transaction's execution.
+    pub gas_used: u64,
+    /// The EIP-1559 fee paid for the transaction's execution, in wei.
+    pub execution_fee: u128,
+    /// The fee paid for the transaction's blobs, in wei.
+    pub blob_fee: u128,
+    /// The sum of the execution and blob fees, in wei.
+    pub total: u128,
+}
+
+impl TotalTxFee {
+    /// Returns the effective price paid per unit of execution gas, i.e. the base fee plus the
+    /// priority fee actually paid, or `0` if no gas was used.
+    ///
+    /// This does not include the blob fee, which is paid for blob gas instead.
+    pub const fn effective_gas_price(&self) -> u128 {
+        if self.gas_used == 0 {
+            0
+        } else {
+            self.execution_fee / self.gas_used as u128
+        }
+    }
+}
+
+/// Calculates the fees paid by a blob transaction carrying `blob_count` blobs and using
+/// `gas_used` gas, included in a block with the given `excess_blob_gas` and `base_fee`.
+///
+/// The execution fee is computed as for any EIP-1559 transaction, with an effective gas price of
+/// `min(max_fee_per_gas, base_fee + max_priority_fee_per_gas)`. The blob fee is computed from the
+/// blob gas price derived from `excess_blob_gas`, independently of the execution fee.
+pub fn calc_total_blob_tx_fee(
+    blob_count: u64,
+    excess_blob_gas: u64,
+    gas_used: u64,
+    base_fee: u128,
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+) -> TotalTxFee {
+    let effective_gas_price =
+        max_fee_per_gas.min(base_fee.saturating_add(max_priority_fee_per_gas));
+    let execution_fee = (gas_used as u128).saturating_mul(effective_gas_price);
+    let blob_fee = blob_fee(blob_count, calc_blob_gasprice(excess_blob_gas));
+    TotalTxFee { gas_used, execution_fee, blob_fee, total: execution_fee.saturating_add(blob_fee) }
+}
+
+/// Returns the blob gas used by a transaction with the given number of blob versioned hashes.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
+/// (`get_total_blob_gas`).
+#[inline]
+pub const fn get_total_blob_gas(blob_versioned_hash_count: u64) -> u64 {
+    blob_versioned_hash_count.saturating_mul(DATA_GAS_PER_BLOB)
+}
+
+/// Returns the blob gas used by a block whose transactions carry the given numbers of blob
+/// versioned hashes, i.e. the expected value of the header's `blob_gas_used` field.
+///
+/// The result saturates at `u64::MAX`.
+pub fn total_blob_gas_used(versioned_hash_counts: impl Iterator<Item = u64>) -> u64 {
+    versioned_hash_counts.fold(0, |total, count| total.saturating_add(get_total_blob_gas(count)))
+}
+
+/// Validates a header's `blob_gas_used` field against the numbers of blob versioned hashes of the
+/// block's transactions, see [`total_blob_gas_used`].
+///
+/// Also checks that the blob gas used does not exceed [`MAX_DATA_GAS_PER_BLOCK`].
+pub fn validate_blob_gas_used(
+    blob_gas_used: u64,
+    versioned_hash_counts: impl Iterator<Item = u64>,
+) -> Result<(), Eip4844Error> {
+    validate_blob_gas_used_with_max(blob_gas_used, versioned_hash_counts, MAX_DATA_GAS_PER_BLOCK)
+}
+
+/// Validates a header's `blob_gas_used` field as [`validate_blob_gas_used`], with the given
+/// maximum blob gas per block instead of [`MAX_DATA_GAS_PER_BLOCK`].
+pub fn validate_blob_gas_used_with_max(
+    blob_gas_used: u64,
+    versioned_hash_counts: impl Iterator<Item = u64>,
+    max_blob_gas: u64,
+) -> Result<(), Eip4844Error> {
+    let computed = total_blob_gas_used(versioned_hash_counts);
+    if blob_gas_used != computed {
+        return Err(Eip4844Error::BlobGasUsedMismatch { header: blob_gas_used, computed });
+    }
+    if blob_gas_used > max_blob_gas {
+        return Err(Eip4844Error::BlobGasUsedExceedsMax(blob_gas_used));
+    }
+    Ok(())
+}
+
+/// Returns the number of blobs needed to post `data_len` bytes, i.e. `data_len` divided by
+/// [`BYTES_PER_BLOB`], rounded up.
+pub const fn blobs_needed(data_len: usize) -> usize {
+    let bytes_per_blob = BYTES_PER_BLOB as usize;
+    data_len / bytes_per_blob + (data_len % bytes_per_blob != 0) as usize
+}
+
+/// Returns the data gas used by the blobs needed to post `data_len` bytes.
+///
+/// The result saturates at `u64::MAX`, see [`checked_data_gas_for_bytes`] for a variant that
+/// checks that the blobs fit in a single block.
+pub const fn data_gas_for_bytes(data_len: usize) -> u64 {
+    (blobs_needed(data_len) as u64).saturating_mul(DATA_GAS_PER_BLOB)
+}
+
+/// Returns the data gas used by the blobs needed to post `data_len` bytes, or `None` if they
+/// exceed [`MAX_BLOBS_PER_BLOCK`].
+pub const fn checked_data_gas_for_bytes(data_len: usize) -> Option<u64> {
+    checked_data_gas_for_bytes_with_max(data_len, MAX_DATA_GAS_PER_BLOCK)
+}
+
+/// Returns the data gas used by the blobs needed to post `data_len` bytes, as
+/// [`checked_data_gas_for_bytes`], with the given maximum blob gas per block instead of
+/// [`MAX_DATA_GAS_PER_BLOCK`].
+pub const fn checked_data_gas_for_bytes_with_max(
+    data_len: usize,
+    max_blob_gas: u64,
+) -> Option<u64> {
+    if blobs_needed(data_len) as u64 > max_blob_gas / DATA_GAS_PER_BLOB {
+        return None;
+    }
+    Some(data_gas_for_bytes(data_len))
+}
+
+/// Returns the blob gas used by a block as a fraction of [`TARGET_DATA_GAS_PER_BLOCK`], capped at
+/// `1.0`.
+pub fn calc_blobspace_utilization(block_blob_gas_used: u64) -> f64 {
+    (block_blob_gas_used as f64 / TARGET_DATA_GAS_PER_BLOCK as f64).min(1.0)
+}
+
+/// Returns the blob gas used by a block as a fraction of [`MAX_DATA_GAS_PER_BLOCK`], capped at
+/// `1.0`.
+pub fn calc_max_utilization(block_blob_gas_used: u64) -> f64 {
+    (block_blob_gas_used as f64 / MAX_DATA_GAS_PER_BLOCK as f64).min(1.0)
+}
+
+/// How much of a block's blobspace is used, relative to its target and maximum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlobspaceStatus {
+    /// Less than [`TARGET_DATA_GAS_PER_BLOCK`] is used, decreasing the excess blob gas.
+    UnderTarget,
+    /// Exactly [`TARGET_DATA_GAS_PER_BLOCK`] is used, leaving the excess blob gas unchanged.
+    AtTarget,
+    /// More than [`TARGET_DATA_GAS_PER_BLOCK`], but less than [`MAX_DATA_GAS_PER_BLOCK`] is used,
+    /// increasing the excess blob gas.
+    OverTarget {
+        /// The blob gas used above the target.
+        excess_gas: u64,
+    },
+    /// [`MAX_DATA_GAS_PER_BLOCK`] or more is used.
+    AtMax,
+}
+
+impl BlobspaceStatus {
+    /// Classifies the blob gas used by a block.
+    pub const fn classify(blob_gas_used: u64) -> Self {
+        Self::classify_with_limits(blob_gas_used, TARGET_DATA_GAS_PER_BLOCK, MAX_DATA_GAS_PER_BLOCK)
+    }
+
+    /// Classifies the blob gas used by a block as [`BlobspaceStatus::classify`], with the given
+    /// target and maximum blob gas per block instead of [`TARGET_DATA_GAS_PER_BLOCK`] and
+    /// [`MAX_DATA_GAS_PER_BLOCK`].
+    pub const fn classify_with_limits(blob_gas_used: u64, target: u64, max: u64) -> Self {
+        if blob_gas_used >= max {
+            Self::AtMax
+        } else if blob_gas_used > target {
+            Self::OverTarget { excess_gas: blob_gas_used - target }
+        } else if blob_gas_used == target {
+            Self::AtTarget
+        } else {
+            Self::UnderTarget
+        }
+    }
+}
+
+/// Gas cost of a zero byte of calldata.
+const CALLDATA_ZERO_BYTE_GAS: u128 = 4;
+
+/// Gas cost of a non-zero byte of calldata, see [EIP-2028](https://eips.ethereum.org/EIPS/eip-2028).
+const CALLDATA_NON_ZERO_BYTE_GAS: u128 = 16;
+
+/// A way of making data available on L1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DaMode {
+    /// Post the data as transaction calldata.
+    Calldata,
+    /// Post the data in blobs.
+    Blob,
+}
+
+/// The result of [`cheaper_da`]: the cheaper way to post some data, along with the cost of both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DaChoice {
+    /// The cheaper way to post the data. Ties are resolved in favor of calldata.
+    pub mode: DaMode,
+    /// The cost, in wei, of posting the data as calldata.
+    pub calldata_cost: u128,
+    /// The cost, in wei, of posting the data in blobs.
+    pub blob_cost: u128,
+}
+
+impl DaChoice {
+    const fn new(calldata_cost: u128, blob_cost: u128) -> Self {
+        let mode = if blob_cost < calldata_cost { DaMode::Blob } else { DaMode::Calldata };
+        Self { mode, calldata_cost, blob_cost }
+    }
+}
+
+/// Compares the cost of posting `data_len` bytes as calldata against posting them in blobs, at
+/// the given blob gas price and calldata (execution) gas price.
+///
+/// Every byte of calldata is assumed to be non-zero, which gives an upper bound on its cost. Use
+/// [`cheaper_da_exact`] to account for zero bytes. Only the data itself is priced, not the
+/// intrinsic gas of the transaction carrying it.
+pub const fn cheaper_da(data_len: usize, blob_gasprice: u128, calldata_gasprice: u128) -> DaChoice {
+    let calldata_gas = (data_len as u128).saturating_mul(CALLDATA_NON_ZERO_BYTE_GAS);
+    DaChoice::new(
+        calldata_gas.saturating_mul(calldata_gasprice),
+        blob_cost(data_len, blob_gasprice),
+    )
+}
+
+/// Same as [`cheaper_da`], but computes the exact calldata cost of `data`, charging zero bytes
+/// less than non-zero ones.
+pub fn cheaper_da_exact(data: &[u8], blob_gasprice: u128, calldata_gasprice: u128) -> DaChoice {
+    let zero_bytes = data.iter().filter(|&&byte| byte == 0).count() as u128;
+    let non_zero_bytes = data.len() as u128 - zero_bytes;
+    let calldata_gas =
+        zero_bytes * CALLDATA_ZERO_BYTE_GAS + non_zero_bytes * CALLDATA_NON_ZERO_BYTE_GAS;
+    DaChoice::new(
+        calldata_gas.saturating_mul(calldata_gasprice),
+        blob_cost(data.len(), blob_gasprice),
+    )
+}
+
+/// Returns the cost of posting `data_len` bytes in as few blobs as possible.
+const fn blob_cost(data_len: usize, blob_gasprice: u128) -> u128 {
+    blob_fee(blobs_needed(data_len) as u64, blob_gasprice)
+}
+
 /// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
 ///
 /// This is used to calculate the blob price.
@@ -63,25 +1001,47 @@ pub fn calc_blob_gasprice(excess_blob_gas: u64) -> u128 {
 ///
 /// # Panics
 ///
-/// This function panics if `denominator` is zero.
+/// This function panics if `denominator` is zero, or if the result overflows, see
+/// [`checked_fake_exponential`].
 #[inline]
 fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> u128 {
+    checked_fake_exponential(factor, numerator, denominator)
+        .expect("attempt to multiply with overflow")
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` as [`fake_exponential`], returning
+/// `None` instead of overflowing.
+///
+/// # Panics
+///
+/// This function panics if `denominator` is zero.
+fn checked_fake_exponential(factor: u64, numerator: u64, denominator: u64) -> Option<u128> {
     assert_ne!(denominator, 0, "attempt to divide by zero");
     let factor = factor as u128;
     let numerator = numerator as u128;
     let denominator = denominator as u128;
 
     let mut i = 1;
-    let mut output = 0;
+    let mut output = 0u128;
     let mut numerator_accum = factor * denominator;
     while numerator_accum > 0 {
-        output += numerator_accum;
+        output = output.checked_add(numerator_accum)?;
 
         // Denominator is asserted as not zero at the start of the function.
-        numerator_accum = (numerator_accum * numerator) / (denominator * i);
+        numerator_accum = numerator_accum.checked_mul(numerator)? / (denominator * i);
         i += 1;
     }
-    output / denominator
+    Some(output / denominator)
+}
+
+/// Calculates the blob gas price as [`calc_blob_gasprice`], returning `None` instead of
+/// overflowing for very large excess blob gas values.
+fn checked_calc_blob_gasprice(excess_blob_gas: u64) -> Option<u128> {
+    checked_fake_exponential(
+        BLOB_TX_MIN_BLOB_GASPRICE as u64,
+        excess_blob_gas,
+        BLOB_GASPRICE_UPDATE_FRACTION,
+    )
 }
 
 #[cfg(test)]
@@ -151,6 +1111,190 @@ mod tests {
         }
     }
 
+    #[test]
+    fn blob_fee_forecasts() {
+        // The price doubles every `ln(2) * BLOB_GASPRICE_UPDATE_FRACTION` excess blob gas, which
+        // takes just under 6 full blocks.
+        assert_eq!(blocks_to_double_blob_fee(0), 6);
+        for excess in [10 * 1024 * 1024, 50_000_000, 100_000_000] {
+            let blocks = blocks_to_double_blob_fee(excess);
+            assert!((5..=7).contains(&blocks), "test: {excess}");
+            let doubled = excess + blocks * TARGET_DATA_GAS_PER_BLOCK;
+            assert!(calc_blob_gasprice(doubled) >= 2 * calc_blob_gasprice(excess));
+
+            let blocks = blocks_to_halve_blob_fee(doubled);
+            assert!((5..=7).contains(&blocks), "test: {excess}");
+        }
+
+        // Stops once the price overflows instead of panicking.
+        let overflow = (0..).map(|i| i * TARGET_DATA_GAS_PER_BLOCK);
+        let overflow = overflow.take_while(|&excess| checked_calc_blob_gasprice(excess).is_some());
+        let overflow = overflow.last().unwrap() + TARGET_DATA_GAS_PER_BLOCK;
+        let blocks = blocks_to_double_blob_fee(overflow - 3 * TARGET_DATA_GAS_PER_BLOCK);
+        assert!(blocks <= 3, "{blocks}");
+        assert_eq!(blocks_to_double_blob_fee(u64::MAX), 0);
+        assert_eq!(blocks_to_halve_blob_fee(u64::MAX), 0);
+        let blocks = blocks_to_halve_blob_fee(overflow - TARGET_DATA_GAS_PER_BLOCK);
+        assert!((5..=7).contains(&blocks), "{blocks}");
+
+        // The minimum price cannot be halved.
+        assert_eq!(blocks_to_halve_blob_fee(0), 0);
+        assert_eq!(blocks_to_halve_blob_fee(TARGET_DATA_GAS_PER_BLOCK), 1);
+    }
+
+    #[test]
+    fn blob_gas_accounting() {
+        let mut accounting = BlobGasAccounting::default();
+        assert_eq!(accounting.remaining_blobs(), MAX_BLOBS_PER_BLOCK);
+
+        accounting.try_add(4).unwrap();
+        assert_eq!(accounting.remaining_blobs(), 2);
+        // A failed add leaves the accounting unchanged.
+        assert_eq!(
+            accounting.try_add(3),
+            Err(Eip4844Error::BlockBlobGasExceeded {
+                used: 4 * DATA_GAS_PER_BLOB,
+                requested: 3 * DATA_GAS_PER_BLOB,
+                limit: MAX_DATA_GAS_PER_BLOCK,
+            })
+        );
+        assert_eq!(accounting.used, 4 * DATA_GAS_PER_BLOB);
+
+        accounting.remove(1);
+        accounting.try_add(3).unwrap();
+        assert!(accounting.is_full());
+        assert!(accounting.try_add(1).is_err());
+        accounting.try_add(0).unwrap();
+
+        accounting.remove(10);
+        assert_eq!(accounting, BlobGasAccounting::default());
+        assert!(accounting.try_add(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn blob_gas_accumulator() {
+        let mut accumulator = BlobGasAccumulator::new();
+        assert_eq!(accumulator.total(), 0);
+        assert_eq!(accumulator.try_add_blobs(2).unwrap(), 2 * DATA_GAS_PER_BLOB);
+        assert_eq!(accumulator.try_add_blobs(4).unwrap(), MAX_DATA_GAS_PER_BLOCK);
+        assert!(matches!(
+            accumulator.try_add_blobs(1),
+            Err(Eip4844Error::BlockBlobGasExceeded { used: MAX_DATA_GAS_PER_BLOCK, .. })
+        ));
+        assert!(accumulator.try_add_blobs(u64::MAX).is_err());
+        assert_eq!(accumulator.total(), MAX_DATA_GAS_PER_BLOCK);
+    }
+
+    #[test]
+    fn blob_gas_estimator() {
+        let excess = 10 * DATA_GAS_PER_BLOB;
+        let mut estimator = BlobGasEstimator::new(excess);
+        assert_eq!(estimator.remaining_blob_capacity(), MAX_BLOBS_PER_BLOCK as u64);
+        // An empty block lowers the excess blob gas by the target.
+        assert_eq!(estimator.projected_next_excess(), excess - TARGET_DATA_GAS_PER_BLOCK);
+
+        estimator.add_blobs(TARGET_BLOBS_PER_BLOCK).unwrap();
+        assert_eq!(estimator.projected_next_excess(), excess);
+        assert_eq!(estimator.projected_next_blob_gasprice(), calc_blob_gasprice(excess));
+
+        // Fill the block one blob at a time.
+        while estimator.can_fit_blobs(1) {
+            estimator.add_blobs(1).unwrap();
+        }
+        assert_eq!(estimator.committed_blobs, MAX_BLOBS_PER_BLOCK as u64);
+        assert_eq!(estimator.remaining_blob_capacity(), 0);
+        assert!(estimator.can_fit_blobs(0));
+        assert_eq!(
+            estimator.add_blobs(1),
+            Err(Eip4844Error::BlockBlobGasExceeded {
+                used: MAX_DATA_GAS_PER_BLOCK,
+                requested: DATA_GAS_PER_BLOB,
+                limit: MAX_DATA_GAS_PER_BLOCK,
+            })
+        );
+        assert_eq!(estimator.committed_blobs, MAX_BLOBS_PER_BLOCK as u64);
+        let next_excess = excess + MAX_DATA_GAS_PER_BLOCK - TARGET_DATA_GAS_PER_BLOCK;
+        assert_eq!(estimator.projected_next_excess(), next_excess);
+        assert!(estimator.projected_next_blob_gasprice() >= calc_blob_gasprice(excess));
+
+        assert!(!BlobGasEstimator::default().can_fit_blobs(u64::MAX));
+        assert!(BlobGasEstimator::default().add_blobs(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn total_blob_tx_fee() {
+        // Blob gas price of 2 at this excess blob gas.
+        let excess = 2314058;
+
+        let fee = calc_total_blob_tx_fee(2, excess, 21_000, 10, 100, 3);
+        assert_eq!(fee.execution_fee, 21_000 * 13);
+        assert_eq!(fee.blob_fee, 2 * DATA_GAS_PER_BLOB as u128 * 2);
+        assert_eq!(fee.total, fee.execution_fee + fee.blob_fee);
+        assert_eq!(fee.effective_gas_price(), 13);
+
+        // The priority fee is capped by the max fee, without affecting the blob fee.
+        let capped = calc_total_blob_tx_fee(2, excess, 21_000, 10, 11, 3);
+        assert_eq!(capped.effective_gas_price(), 11);
+        assert_eq!(capped.blob_fee, fee.blob_fee);
+
+        // The blob fee does not affect the execution fee.
+        let no_blobs = calc_total_blob_tx_fee(0, excess, 21_000, 10, 100, 3);
+        assert_eq!(no_blobs.blob_fee, 0);
+        assert_eq!(no_blobs.execution_fee, fee.execution_fee);
+
+        assert_eq!(calc_total_blob_tx_fee(1, 0, 0, 10, 100, 3).effective_gas_price(), 0);
+    }
+
+    #[test]
+    fn data_gas_for_payload() {
+        let blob = BYTES_PER_BLOB as usize;
+        assert_eq!(blobs_needed(0), 0);
+        assert_eq!(blobs_needed(1), 1);
+        assert_eq!(blobs_needed(blob), 1);
+        assert_eq!(blobs_needed(blob + 1), 2);
+        assert_eq!(blobs_needed(usize::MAX), usize::MAX / blob + 1);
+
+        assert_eq!(data_gas_for_bytes(blob + 1), 2 * DATA_GAS_PER_BLOB);
+        assert_eq!(data_gas_for_bytes(usize::MAX), u64::MAX);
+
+        let max = MAX_BLOBS_PER_BLOCK * blob;
+        assert_eq!(checked_data_gas_for_bytes(max), Some(MAX_DATA_GAS_PER_BLOCK));
+        assert_eq!(checked_data_gas_for_bytes(max + 1), None);
+    }
+
+    #[test]
+    fn cheaper_da_costs() {
+        let blob = DATA_GAS_PER_BLOB as u128;
+
+        // A full blob at 1 wei/gas is far cheaper than the equivalent calldata at 10 wei/gas.
+        let choice = cheaper_da(BYTES_PER_BLOB as usize, 1, 10);
+        assert_eq!(
+            choice,
+            DaChoice { mode: DaMode::Blob, calldata_cost: 16 * blob * 10, blob_cost: blob }
+        );
+
+        // Small payloads still pay for a whole blob.
+        let choice = cheaper_da(100, 1, 10);
+        assert_eq!(
+            choice,
+            DaChoice { mode: DaMode::Calldata, calldata_cost: 16_000, blob_cost: blob }
+        );
+
+        // One byte over a blob needs two of them.
+        assert_eq!(cheaper_da(BYTES_PER_BLOB as usize + 1, 1, 0).blob_cost, 2 * blob);
+
+        // Nothing to post, ties go to calldata.
+        assert_eq!(
+            cheaper_da(0, 1, 1),
+            DaChoice { mode: DaMode::Calldata, calldata_cost: 0, blob_cost: 0 }
+        );
+
+        // Zero bytes are cheaper with the exact calculation.
+        let data = [0, 0, 1, 2];
+        assert_eq!(cheaper_da_exact(&data, 1, 10).calldata_cost, (2 * 4 + 2 * 16) * 10);
+        assert_eq!(cheaper_da(data.len(), 1, 10).calldata_cost, 4 * 16 * 10);
+    }
+
     // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L78
     #[test]
     fn fake_exp() {
@@ -176,4 +1320,347 @@ mod tests {
             assert_eq!(actual, expected, "test: {t:?}");
         }
     }
+
+    #[test]
+    fn excess_blob_gas_oracle() {
+        let excess = [0, DATA_GAS_PER_BLOB, 2 * DATA_GAS_PER_BLOB];
+        let blob_counts = [4, 4, 6];
+        assert_eq!(estimate_next_excess_blob_gas(&excess, &blob_counts), Ok(5 * DATA_GAS_PER_BLOB));
+        assert_eq!(estimate_next_excess_blob_gas(&[], &[]), Ok(0));
+        assert_eq!(
+            estimate_next_excess_blob_gas(&[u64::MAX], &[u64::MAX]),
+            Ok(u64::MAX - TARGET_DATA_GAS_PER_BLOCK)
+        );
+        assert_eq!(
+            estimate_next_excess_blob_gas(&[0, 0], &[0]),
+            Err(Eip4844Error::HistoryLengthMismatch { excess_values: 2, blob_counts: 1 })
+        );
+
+        let avg = rolling_average_blob_count(&blob_counts);
+        assert!((avg - 14.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(rolling_average_blob_count(&[]), 0.0);
+
+        for (excess, blobs) in [(0, 6), (10 * DATA_GAS_PER_BLOB, 1), (DATA_GAS_PER_BLOB, 3)] {
+            let mut expected = excess;
+            for n in 0..5 {
+                assert_eq!(
+                    predict_excess_blob_gas_n_blocks_ahead(excess, blobs as f64, n),
+                    expected
+                );
+                expected = calc_excess_blob_gas(expected, blobs * DATA_GAS_PER_BLOB);
+            }
+        }
+        assert_eq!(predict_excess_blob_gas_n_blocks_ahead(DATA_GAS_PER_BLOB, 0.0, u64::MAX), 0);
+    }
+
+    #[test]
+    fn versioned_hashes() {
+        let commitments = [[1u8; 48], [2u8; 48]];
+        let hashes: Vec<_> = commitments.iter().map(kzg_to_versioned_hash).collect();
+        assert_eq!(hashes[0][0], VERSIONED_HASH_VERSION_KZG);
+        assert_eq!(hashes[0][1..], Sha256::digest([1u8; 48])[1..]);
+
+        assert_eq!(validate_versioned_hashes(&hashes, &commitments), Ok(()));
+        assert_eq!(
+            validate_versioned_hashes(&hashes[..1], &commitments),
+            Err(VersionedHashError::LengthMismatch { hashes: 1, commitments: 2 })
+        );
+        assert_eq!(
+            validate_versioned_hashes(&[hashes[0], hashes[0]], &commitments),
+            Err(VersionedHashError::Mismatch(1))
+        );
+    }
+
+    #[test]
+    fn blob_fee_for_transaction() {
+        let excess_blob_gas = 10 * BLOB_GASPRICE_UPDATE_FRACTION;
+        let blob_gasprice = calc_blob_gasprice(excess_blob_gas);
+        assert!(blob_gasprice > 1);
+        assert_eq!(
+            calc_blob_fee_for_transaction(2, excess_blob_gas),
+            2 * DATA_GAS_PER_BLOB as u128 * blob_gasprice
+        );
+        assert_eq!(calc_blob_fee_for_transaction(0, excess_blob_gas), 0);
+
+        assert_eq!(
+            calc_max_blob_fee_for_transaction(2, blob_gasprice + 1, excess_blob_gas),
+            Ok(calc_blob_fee_for_transaction(2, excess_blob_gas))
+        );
+        assert_eq!(
+            calc_max_blob_fee_for_transaction(2, blob_gasprice - 1, excess_blob_gas),
+            Err(Eip4844Error::BlobFeeTooLow { have: blob_gasprice - 1, need: blob_gasprice })
+        );
+    }
+
+    #[test]
+    fn blob_transaction_gas() {
+        let excess_blob_gas = 10 * BLOB_GASPRICE_UPDATE_FRACTION;
+        let blob_gasprice = calc_blob_gasprice(excess_blob_gas);
+        assert_eq!(validate_blob_transaction_gas(blob_gasprice, excess_blob_gas), Ok(()));
+        assert_eq!(validate_blob_transaction_gas(u128::MAX, excess_blob_gas), Ok(()));
+        assert_eq!(
+            validate_blob_transaction_gas(blob_gasprice - 1, excess_blob_gas),
+            Err(Eip4844Error::BlobFeeTooLow { have: blob_gasprice - 1, need: blob_gasprice })
+        );
+
+        // The blob gas price is at least 1 wei.
+        assert_eq!(validate_blob_transaction_gas(BLOB_TX_MIN_BLOB_GASPRICE, 0), Ok(()));
+        assert!(validate_blob_transaction_gas(0, 0).is_err());
+    }
+
+    #[test]
+    fn blob_fee_for_inclusion() {
+        let excess_blob_gas = 10 * BLOB_GASPRICE_UPDATE_FRACTION;
+        let blob_gasprice = calc_blob_gasprice(excess_blob_gas);
+        assert_eq!(min_blob_fee_for_inclusion(excess_blob_gas, 1), Ok(blob_gasprice));
+        assert_eq!(
+            min_blob_fee_for_inclusion(excess_blob_gas, MAX_BLOBS_PER_BLOCK),
+            Ok(blob_gasprice)
+        );
+        assert_eq!(
+            min_blob_fee_for_inclusion(excess_blob_gas, MAX_BLOBS_PER_BLOCK + 1),
+            Err(Eip4844Error::BlockBlobGasExceeded {
+                used: 0,
+                requested: MAX_DATA_GAS_PER_BLOCK + DATA_GAS_PER_BLOB,
+                limit: MAX_DATA_GAS_PER_BLOCK,
+            })
+        );
+
+        assert_eq!(recommended_blob_fee_with_buffer(excess_blob_gas, 0), blob_gasprice);
+        assert_eq!(
+            recommended_blob_fee_with_buffer(excess_blob_gas, 2_000),
+            blob_gasprice + (blob_gasprice * 2_000 + 9_999) / 10_000
+        );
+        // The buffer is rounded up.
+        assert_eq!(recommended_blob_fee_with_buffer(0, 0), 1);
+        assert_eq!(recommended_blob_fee_with_buffer(0, 1), 2);
+    }
+
+    #[test]
+    fn blob_fee_bump() {
+        // Geth requires a 100% bump for blob transactions.
+        assert!(meets_blob_fee_bump(10, 20, 100));
+        assert!(!meets_blob_fee_bump(10, 19, 100));
+        assert!(meets_blob_fee_bump(10, 11, 10));
+        assert!(!meets_blob_fee_bump(10, 10, 10));
+        assert!(meets_blob_fee_bump(10, 10, 0));
+        // The required fee is rounded down.
+        assert!(meets_blob_fee_bump(15, 16, 10));
+
+        assert!(!meets_blob_fee_bump(u128::MAX, u128::MAX, 1));
+        assert!(meets_blob_fee_bump(u128::MAX / 2, u128::MAX, 100));
+        assert!(!meets_blob_fee_bump(u128::MAX / 2 + 1, u128::MAX, 100));
+        assert!(!meets_blob_fee_bump(u128::MAX / 4, u128::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn blob_fee_history() {
+        let mut history = BlobFeeHistory::default();
+        assert_eq!(history.average_blob_fee(), 0);
+        assert_eq!(history.median_blob_fee(), 0);
+        assert_eq!(history.percentile_blob_fee(50.0), 0);
+
+        for i in 0..8 {
+            history.push_block(i * BLOB_GASPRICE_UPDATE_FRACTION, MAX_DATA_GAS_PER_BLOCK, 5);
+        }
+        assert_eq!(history.excess_blob_gas.len(), 5);
+        assert_eq!(history.blob_gas_used.len(), 5);
+        assert_eq!(history.excess_blob_gas[0], 3 * BLOB_GASPRICE_UPDATE_FRACTION);
+
+        let prices: Vec<_> =
+            history.excess_blob_gas.iter().map(|e| calc_blob_gasprice(*e)).collect();
+        let (min, max) = (prices[0], prices[4]);
+        let median = history.median_blob_fee();
+        assert!(min < median && median < max);
+        assert_eq!(median, prices[2]);
+        assert_eq!(history.average_blob_fee(), prices.iter().sum::<u128>() / 5);
+        assert_eq!(history.percentile_blob_fee(0.0), min);
+        assert_eq!(history.percentile_blob_fee(40.0), prices[1]);
+        assert_eq!(history.percentile_blob_fee(100.0), max);
+
+        history.push_block(0, 0, 6);
+        assert_eq!(history.median_blob_fee(), (prices[1] + prices[2]) / 2);
+    }
+
+    #[test]
+    fn total_blob_gas() {
+        let max_blobs = MAX_BLOBS_PER_BLOCK as u64;
+        assert_eq!(get_total_blob_gas(0), 0);
+        assert_eq!(get_total_blob_gas(max_blobs), MAX_DATA_GAS_PER_BLOCK);
+        assert!(get_total_blob_gas(max_blobs + 1) > MAX_DATA_GAS_PER_BLOCK);
+    }
+
+    #[test]
+    fn blobspace_utilization() {
+        assert_eq!(calc_blobspace_utilization(0), 0.0);
+        assert_eq!(calc_blobspace_utilization(DATA_GAS_PER_BLOB), 1.0 / 3.0);
+        assert_eq!(calc_blobspace_utilization(TARGET_DATA_GAS_PER_BLOCK), 1.0);
+        assert_eq!(calc_blobspace_utilization(MAX_DATA_GAS_PER_BLOCK), 1.0);
+        assert_eq!(calc_max_utilization(TARGET_DATA_GAS_PER_BLOCK), 0.5);
+        assert_eq!(calc_max_utilization(MAX_DATA_GAS_PER_BLOCK), 1.0);
+
+        assert_eq!(BlobspaceStatus::classify(0), BlobspaceStatus::UnderTarget);
+        assert_eq!(
+            BlobspaceStatus::classify(TARGET_DATA_GAS_PER_BLOCK - 1),
+            BlobspaceStatus::UnderTarget
+        );
+        assert_eq!(BlobspaceStatus::classify(TARGET_DATA_GAS_PER_BLOCK), BlobspaceStatus::AtTarget);
+        assert_eq!(
+            BlobspaceStatus::classify(TARGET_DATA_GAS_PER_BLOCK + DATA_GAS_PER_BLOB),
+            BlobspaceStatus::OverTarget { excess_gas: DATA_GAS_PER_BLOB }
+        );
+        assert_eq!(
+            BlobspaceStatus::classify(MAX_DATA_GAS_PER_BLOCK - 1),
+            BlobspaceStatus::OverTarget { excess_gas: TARGET_DATA_GAS_PER_BLOCK - 1 }
+        );
+        assert_eq!(BlobspaceStatus::classify(MAX_DATA_GAS_PER_BLOCK), BlobspaceStatus::AtMax);
+    }
+
+    #[test]
+    fn block_blob_context() {
+        for excess_blob_gas in [0, 10 * DATA_GAS_PER_BLOB, 5 * BLOB_GASPRICE_UPDATE_FRACTION] {
+            let context = BlockBlobContext::new(excess_blob_gas);
+            assert_eq!(context.excess_blob_gas(), excess_blob_gas);
+            assert_eq!(context.blob_gasprice(), calc_blob_gasprice(excess_blob_gas));
+            for blobs in 0..=MAX_BLOBS_PER_BLOCK {
+                assert_eq!(
+                    context.cost_for_blobs(blobs as u64),
+                    calc_blob_fee_for_transaction(blobs, excess_blob_gas)
+                );
+            }
+        }
+        let context = BlockBlobContext { excess_blob_gas: 0, blob_gasprice: u128::MAX };
+        assert_eq!(context.cost_for_blobs(1), u128::MAX);
+    }
+
+    #[test]
+    fn blob_price_summary() {
+        let excess_blob_gas = 20 * BLOB_GASPRICE_UPDATE_FRACTION;
+        let summary = block_blob_price_summary(excess_blob_gas, 6);
+
+        let blob_gasprice = calc_blob_gasprice(excess_blob_gas);
+        let next_excess = excess_blob_gas + 6 * DATA_GAS_PER_BLOB - TARGET_DATA_GAS_PER_BLOCK;
+        assert_eq!(
+            summary,
+            BlobPriceSummary {
+                excess_blob_gas,
+                blob_count: 6,
+                blob_gasprice,
+                total_blob_fee: blob_gasprice * 6 * DATA_GAS_PER_BLOB as u128,
+                next_excess,
+                next_blob_gasprice: calc_blob_gasprice(next_excess),
+            }
+        );
+        assert!(summary.next_blob_gasprice > summary.blob_gasprice);
+
+        let summary = block_blob_price_summary(0, u64::MAX);
+        assert_eq!(summary.total_blob_fee, DATA_GAS_PER_BLOB as u128 * u64::MAX as u128);
+        assert_eq!(summary.next_excess, u64::MAX - TARGET_DATA_GAS_PER_BLOCK);
+        assert_eq!(summary.next_blob_gasprice, u128::MAX);
+
+        let summary = BlobPriceSummary {
+            excess_blob_gas: 1,
+            blob_count: 2,
+            blob_gasprice: 1_234_567_890,
+            total_blob_fee: 7_380_000_000,
+            next_excess: 3,
+            next_blob_gasprice: 5,
+        };
+        assert_eq!(
+            summary.to_string(),
+            "Blob Price: 1.23 Gwei, Blob Count: 2, Total Blob Fee: 7.38 Gwei, Excess Blob Gas: 1, \
+             Next Excess Blob Gas: 3, Next Blob Price: 0.00 Gwei"
+        );
+    }
+
+    #[test]
+    fn blob_gas_used() {
+        assert_eq!(total_blob_gas_used([].into_iter()), 0);
+        assert_eq!(total_blob_gas_used([1, 0, 2].into_iter()), 3 * DATA_GAS_PER_BLOB);
+        assert_eq!(total_blob_gas_used([u64::MAX, 1].into_iter()), u64::MAX);
+
+        assert_eq!(validate_blob_gas_used(3 * DATA_GAS_PER_BLOB, [1, 0, 2].into_iter()), Ok(()));
+        assert_eq!(
+            validate_blob_gas_used(DATA_GAS_PER_BLOB, [1, 0, 2].into_iter()),
+            Err(Eip4844Error::BlobGasUsedMismatch {
+                header: DATA_GAS_PER_BLOB,
+                computed: 3 * DATA_GAS_PER_BLOB
+            })
+        );
+        let counts = [MAX_BLOBS_PER_BLOCK as u64, 1];
+        assert_eq!(
+            validate_blob_gas_used(total_blob_gas_used(counts.into_iter()), counts.into_iter()),
+            Err(Eip4844Error::BlobGasUsedExceedsMax(MAX_DATA_GAS_PER_BLOCK + DATA_GAS_PER_BLOB))
+        );
+    }
+
+    #[test]
+    fn custom_target_and_max() {
+        // The target and maximum of 6 and 9 blobs introduced by EIP-7691.
+        let (target, max) = (6 * DATA_GAS_PER_BLOB, 9 * DATA_GAS_PER_BLOB);
+
+        assert_eq!(
+            calc_excess_blob_gas_with_target(0, 6 * DATA_GAS_PER_BLOB, TARGET_DATA_GAS_PER_BLOCK),
+            calc_excess_blob_gas(0, 6 * DATA_GAS_PER_BLOB)
+        );
+        assert_eq!(calc_excess_blob_gas_with_target(0, 6 * DATA_GAS_PER_BLOB, target), 0);
+        assert_eq!(
+            calc_excess_blob_gas_with_target(DATA_GAS_PER_BLOB, 9 * DATA_GAS_PER_BLOB, target),
+            4 * DATA_GAS_PER_BLOB
+        );
+
+        assert_eq!(predict_excess_blob_gas_n_blocks_ahead_with_target(0, 6.0, 10, target), 0);
+        assert_eq!(
+            predict_excess_blob_gas_n_blocks_ahead_with_target(0, 9.0, 10, target),
+            30 * DATA_GAS_PER_BLOB
+        );
+
+        let counts = [MAX_BLOBS_PER_BLOCK as u64, 1];
+        let used = total_blob_gas_used(counts.into_iter());
+        assert!(validate_blob_gas_used(used, counts.into_iter()).is_err());
+        assert_eq!(validate_blob_gas_used_with_max(used, counts.into_iter(), max), Ok(()));
+
+        assert_eq!(
+            BlobspaceStatus::classify_with_limits(6 * DATA_GAS_PER_BLOB, target, max),
+            BlobspaceStatus::AtTarget
+        );
+        assert_eq!(
+            BlobspaceStatus::classify_with_limits(7 * DATA_GAS_PER_BLOB, target, max),
+            BlobspaceStatus::OverTarget { excess_gas: DATA_GAS_PER_BLOB }
+        );
+        assert_eq!(BlobspaceStatus::classify_with_limits(max, target, max), BlobspaceStatus::AtMax);
+
+        let mut accumulator = BlobGasAccumulator::with_limit(max);
+        assert_eq!(accumulator.try_add_blobs(9), Ok(max));
+        assert!(accumulator.try_add_blobs(1).is_err());
+
+        let mut estimator = BlobGasEstimator::with_limits(0, target, max);
+        assert_eq!(estimator.remaining_blob_capacity(), 9);
+        estimator.add_blobs(9).unwrap();
+        assert_eq!(estimator.projected_next_excess(), 3 * DATA_GAS_PER_BLOB);
+        assert!(matches!(
+            estimator.add_blobs(1),
+            Err(Eip4844Error::BlockBlobGasExceeded { limit, .. }) if limit == max
+        ));
+
+        assert!(min_blob_fee_for_inclusion(0, 9).is_err());
+        assert_eq!(min_blob_fee_for_inclusion_with_max(0, 9, max), Ok(1));
+        assert!(min_blob_fee_for_inclusion_with_max(0, 10, max).is_err());
+
+        let blob = BYTES_PER_BLOB as usize;
+        assert_eq!(checked_data_gas_for_bytes(9 * blob), None);
+        assert_eq!(checked_data_gas_for_bytes_with_max(9 * blob, max), Some(max));
+        assert_eq!(checked_data_gas_for_bytes_with_max(9 * blob + 1, max), None);
+
+        // Full blocks raise the excess blob gas by `max - target` = 3 blobs, as with the EIP-4844
+        // constants, and empty blocks lower it by the target, twice as fast.
+        assert_eq!(blocks_to_double_blob_fee_with_limits(0, target, max), 6);
+        assert_eq!(blocks_to_double_blob_fee_with_limits(0, target, target), u64::MAX);
+        let excess = 100_000_000;
+        let blocks = blocks_to_halve_blob_fee(excess);
+        let halved = blocks_to_halve_blob_fee_with_target(excess, target);
+        assert_eq!(halved, (blocks + 1) / 2);
+        assert_eq!(blocks_to_halve_blob_fee_with_target(excess, 0), u64::MAX);
+        assert_eq!(blocks_to_halve_blob_fee_with_target(0, 0), 0);
+    }
 }
@@ -0,0 +1,134 @@
+//! [EIP-7594] (PeerDAS) constants and data column helpers.
+//!
+//! [EIP-7594]: https://eips.ethereum.org/EIPS/eip-7594
+
+use alloy_primitives::U256;
+use sha2::{Digest, Sha256};
+
+/// The number of data column sidecar subnets.
+pub const DATA_COLUMN_SIDECAR_SUBNET_COUNT: u64 = 128;
+
+/// The number of custody groups the data columns are divided into.
+pub const NUMBER_OF_CUSTODY_GROUPS: u64 = 128;
+
+/// The number of data columns each extended blob is split into.
+pub const DATA_COLUMNS_PER_BLOB: u64 = 128;
+
+/// The number of cells in an extended blob, i.e. one per data column.
+pub const CELLS_PER_EXT_BLOB: u64 = 128;
+
+/// [EIP-7594] errors.
+///
+/// [EIP-7594]: https://eips.ethereum.org/EIPS/eip-7594
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eip7594Error {
+    /// The data column index is out of range.
+    #[error("Invalid data column index. Got {0}, expected less than {DATA_COLUMNS_PER_BLOB}.")]
+    InvalidColumnIndex(u64),
+}
+
+/// The index of a data column, in `0..DATA_COLUMNS_PER_BLOB`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DataColumnIndex(pub u64);
+
+impl DataColumnIndex {
+    /// Checks that the index is less than [`DATA_COLUMNS_PER_BLOB`].
+    pub const fn validate(&self) -> Result<(), Eip7594Error> {
+        if self.0 >= DATA_COLUMNS_PER_BLOB {
+            return Err(Eip7594Error::InvalidColumnIndex(self.0));
+        }
+        Ok(())
+    }
+}
+
+impl From<u64> for DataColumnIndex {
+    fn from(index: u64) -> Self {
+        Self(index)
+    }
+}
+
+impl From<DataColumnIndex> for u64 {
+    fn from(index: DataColumnIndex) -> Self {
+        index.0
+    }
+}
+
+/// Returns the sorted indices of the data columns a node with the given ID must custody, when
+/// custodying `custody_subnet_count` custody groups.
+///
+/// The custody groups are derived from consecutive node IDs starting at `node_id`, see
+/// `get_custody_groups` and `compute_columns_for_custody_group` in the
+/// [consensus specs](https://github.com/ethereum/consensus-specs/blob/dev/specs/fulu/das-core.md).
+///
+/// # Panics
+///
+/// This function panics if `custody_subnet_count` exceeds [`NUMBER_OF_CUSTODY_GROUPS`].
+pub fn custodied_columns(node_id: U256, custody_subnet_count: u64) -> Vec<u64> {
+    assert!(
+        custody_subnet_count <= NUMBER_OF_CUSTODY_GROUPS,
+        "custody subnet count {custody_subnet_count} exceeds {NUMBER_OF_CUSTODY_GROUPS}"
+    );
+
+    let mut custody_groups = Vec::with_capacity(custody_subnet_count as usize);
+    let mut current_id = node_id;
+    while (custody_groups.len() as u64) < custody_subnet_count {
+        let hash = Sha256::digest(current_id.to_le_bytes::<32>());
+        let prefix: [u8; 8] = hash[..8].try_into().expect("SHA-256 digests are 32 bytes");
+        let group = u64::from_le_bytes(prefix) % NUMBER_OF_CUSTODY_GROUPS;
+        if !custody_groups.contains(&group) {
+            custody_groups.push(group);
+        }
+        current_id = current_id.wrapping_add(U256::from(1));
+    }
+
+    let columns_per_group = DATA_COLUMNS_PER_BLOB / NUMBER_OF_CUSTODY_GROUPS;
+    let mut columns = custody_groups
+        .into_iter()
+        .flat_map(|group| (0..columns_per_group).map(move |i| NUMBER_OF_CUSTODY_GROUPS * i + group))
+        .collect::<Vec<_>>();
+    columns.sort_unstable();
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_index() {
+        assert_eq!(DataColumnIndex(0).validate(), Ok(()));
+        assert_eq!(DataColumnIndex(DATA_COLUMNS_PER_BLOB - 1).validate(), Ok(()));
+        assert_eq!(
+            DataColumnIndex(DATA_COLUMNS_PER_BLOB).validate(),
+            Err(Eip7594Error::InvalidColumnIndex(DATA_COLUMNS_PER_BLOB))
+        );
+    }
+
+    #[test]
+    fn custody() {
+        let node_id = U256::from(0xdeadbeefu64);
+        assert!(custodied_columns(node_id, 0).is_empty());
+
+        let mut previous = Vec::new();
+        for count in [1, 4, 8, 64, NUMBER_OF_CUSTODY_GROUPS] {
+            let columns = custodied_columns(node_id, count);
+            assert_eq!(columns.len() as u64, count);
+            assert!(columns.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(columns.iter().all(|&column| DataColumnIndex(column).validate().is_ok()));
+            // Custodying more groups keeps the previous ones.
+            assert!(previous.iter().all(|column| columns.contains(column)));
+            previous = columns;
+        }
+        assert_eq!(previous, (0..DATA_COLUMNS_PER_BLOB).collect::<Vec<_>>());
+
+        // Deterministic and wrapping at the maximum node ID.
+        assert_eq!(custodied_columns(node_id, 8), custodied_columns(node_id, 8));
+        assert_eq!(custodied_columns(U256::MAX, 4).len(), 4);
+    }
+
+    #[test]
+    #[should_panic = "exceeds"]
+    fn custody_too_many_groups() {
+        custodied_columns(U256::ZERO, NUMBER_OF_CUSTODY_GROUPS + 1);
+    }
+}
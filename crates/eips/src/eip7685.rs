@@ -0,0 +1,414 @@
+//! [EIP-7685] general purpose execution layer requests.
+//!
+//! Contains the request types introduced by [EIP-6110] (deposits), [EIP-7002] (withdrawals), and
+//! [EIP-7251] (consolidations).
+//!
+//! [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+//! [EIP-6110]: https://eips.ethereum.org/EIPS/eip-6110
+//! [EIP-7002]: https://eips.ethereum.org/EIPS/eip-7002
+//! [EIP-7251]: https://eips.ethereum.org/EIPS/eip-7251
+
+use alloy_primitives::{b256, Address, Bytes, FixedBytes, B256};
+use alloy_rlp::BufMut;
+use sha2::{Digest, Sha256};
+
+/// The [EIP-6110] deposit request type byte.
+///
+/// [EIP-6110]: https://eips.ethereum.org/EIPS/eip-6110
+pub const DEPOSIT_REQUEST_TYPE: u8 = 0x00;
+
+/// The [EIP-7002] withdrawal request type byte.
+///
+/// [EIP-7002]: https://eips.ethereum.org/EIPS/eip-7002
+pub const WITHDRAWAL_REQUEST_TYPE: u8 = 0x01;
+
+/// The [EIP-7251] consolidation request type byte.
+///
+/// [EIP-7251]: https://eips.ethereum.org/EIPS/eip-7251
+pub const CONSOLIDATION_REQUEST_TYPE: u8 = 0x02;
+
+/// The requests hash of a block without any requests, i.e. `sha256("")`.
+pub const EMPTY_REQUESTS_HASH: B256 =
+    b256!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+
+/// [EIP-7685] decoding errors.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input ended before the request of the given type was complete.
+    #[error("Request of type {type_byte} is too short. Expected {expected} bytes, got {actual}.")]
+    InputTooShort {
+        /// The type byte of the request being decoded.
+        type_byte: u8,
+        /// The expected length of the request data.
+        expected: usize,
+        /// The remaining length of the input.
+        actual: usize,
+    },
+}
+
+/// A validator deposit, as defined in [EIP-6110].
+///
+/// [EIP-6110]: https://eips.ethereum.org/EIPS/eip-6110
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct DepositRequest {
+    /// Validator public key.
+    pub pubkey: FixedBytes<48>,
+    /// Withdrawal credentials.
+    pub withdrawal_credentials: B256,
+    /// Amount of ether deposited in gwei.
+    pub amount: u64,
+    /// Deposit signature.
+    pub signature: FixedBytes<96>,
+    /// Deposit index.
+    pub index: u64,
+}
+
+impl DepositRequest {
+    /// The length of the packed request data.
+    pub const SIZE: usize = 48 + 32 + 8 + 96 + 8;
+
+    /// Encode the packed request data. Integers are little-endian, as emitted by the deposit
+    /// contract.
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        out.put_slice(self.pubkey.as_slice());
+        out.put_slice(self.withdrawal_credentials.as_slice());
+        out.put_u64_le(self.amount);
+        out.put_slice(self.signature.as_slice());
+        out.put_u64_le(self.index);
+    }
+
+    /// Decode the packed request data. The input must be exactly [`Self::SIZE`] bytes long.
+    fn decode(data: &[u8]) -> Self {
+        let (pubkey, data) = data.split_at(48);
+        let (withdrawal_credentials, data) = data.split_at(32);
+        let (amount, data) = data.split_at(8);
+        let (signature, index) = data.split_at(96);
+        Self {
+            pubkey: FixedBytes::from_slice(pubkey),
+            withdrawal_credentials: B256::from_slice(withdrawal_credentials),
+            amount: u64::from_le_bytes(amount.try_into().unwrap()),
+            signature: FixedBytes::from_slice(signature),
+            index: u64::from_le_bytes(index.try_into().unwrap()),
+        }
+    }
+}
+
+/// A validator withdrawal request, as defined in [EIP-7002].
+///
+/// [EIP-7002]: https://eips.ethereum.org/EIPS/eip-7002
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct WithdrawalRequest {
+    /// Address of the account requesting the withdrawal.
+    pub source_address: Address,
+    /// Public key of the validator to withdraw from.
+    pub validator_pubkey: FixedBytes<48>,
+    /// Amount of ether to withdraw in gwei.
+    pub amount: u64,
+}
+
+impl WithdrawalRequest {
+    /// The length of the packed request data.
+    pub const SIZE: usize = 20 + 48 + 8;
+
+    /// Encode the packed request data. The amount is big-endian, as emitted by the withdrawal
+    /// request contract.
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        out.put_slice(self.source_address.as_slice());
+        out.put_slice(self.validator_pubkey.as_slice());
+        out.put_u64(self.amount);
+    }
+
+    /// Decode the packed request data. The input must be exactly [`Self::SIZE`] bytes long.
+    fn decode(data: &[u8]) -> Self {
+        let (source_address, data) = data.split_at(20);
+        let (validator_pubkey, amount) = data.split_at(48);
+        Self {
+            source_address: Address::from_slice(source_address),
+            validator_pubkey: FixedBytes::from_slice(validator_pubkey),
+            amount: u64::from_be_bytes(amount.try_into().unwrap()),
+        }
+    }
+}
+
+/// A validator consolidation request, as defined in [EIP-7251].
+///
+/// [EIP-7251]: https://eips.ethereum.org/EIPS/eip-7251
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct ConsolidationRequest {
+    /// Address of the account requesting the consolidation.
+    pub source_address: Address,
+    /// Public key of the validator to consolidate from.
+    pub source_pubkey: FixedBytes<48>,
+    /// Public key of the validator to consolidate into.
+    pub target_pubkey: FixedBytes<48>,
+}
+
+impl ConsolidationRequest {
+    /// The length of the packed request data.
+    pub const SIZE: usize = 20 + 48 + 48;
+
+    /// Encode the packed request data.
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        out.put_slice(self.source_address.as_slice());
+        out.put_slice(self.source_pubkey.as_slice());
+        out.put_slice(self.target_pubkey.as_slice());
+    }
+
+    /// Decode the packed request data. The input must be exactly [`Self::SIZE`] bytes long.
+    fn decode(data: &[u8]) -> Self {
+        let (source_address, data) = data.split_at(20);
+        let (source_pubkey, target_pubkey) = data.split_at(48);
+        Self {
+            source_address: Address::from_slice(source_address),
+            source_pubkey: FixedBytes::from_slice(source_pubkey),
+            target_pubkey: FixedBytes::from_slice(target_pubkey),
+        }
+    }
+}
+
+/// An [EIP-7685] execution layer request.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExecutionLayerRequest {
+    /// An [EIP-6110] deposit request.
+    ///
+    /// [EIP-6110]: https://eips.ethereum.org/EIPS/eip-6110
+    Deposit(DepositRequest),
+    /// An [EIP-7002] withdrawal request.
+    ///
+    /// [EIP-7002]: https://eips.ethereum.org/EIPS/eip-7002
+    Withdrawal(WithdrawalRequest),
+    /// An [EIP-7251] consolidation request.
+    ///
+    /// [EIP-7251]: https://eips.ethereum.org/EIPS/eip-7251
+    Consolidation(ConsolidationRequest),
+    /// A request of a type unknown to this implementation.
+    Unknown {
+        /// The request type byte.
+        type_byte: u8,
+        /// The opaque request data.
+        data: Bytes,
+    },
+}
+
+impl From<DepositRequest> for ExecutionLayerRequest {
+    fn from(request: DepositRequest) -> Self {
+        Self::Deposit(request)
+    }
+}
+
+impl From<WithdrawalRequest> for ExecutionLayerRequest {
+    fn from(request: WithdrawalRequest) -> Self {
+        Self::Withdrawal(request)
+    }
+}
+
+impl From<ConsolidationRequest> for ExecutionLayerRequest {
+    fn from(request: ConsolidationRequest) -> Self {
+        Self::Consolidation(request)
+    }
+}
+
+impl ExecutionLayerRequest {
+    /// Returns the request type byte.
+    pub const fn type_byte(&self) -> u8 {
+        match self {
+            Self::Deposit(_) => DEPOSIT_REQUEST_TYPE,
+            Self::Withdrawal(_) => WITHDRAWAL_REQUEST_TYPE,
+            Self::Consolidation(_) => CONSOLIDATION_REQUEST_TYPE,
+            Self::Unknown { type_byte, .. } => *type_byte,
+        }
+    }
+
+    /// Encode the request data, without the type byte.
+    pub fn encode_data(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Deposit(request) => request.encode(out),
+            Self::Withdrawal(request) => request.encode(out),
+            Self::Consolidation(request) => request.encode(out),
+            Self::Unknown { data, .. } => out.put_slice(data),
+        }
+    }
+
+    /// Encode the requests in the packed format, i.e. the concatenation of each request's type
+    /// byte followed by its data.
+    pub fn encode(requests: &[Self]) -> Bytes {
+        let mut out = Vec::new();
+        for request in requests {
+            out.put_u8(request.type_byte());
+            request.encode_data(&mut out);
+        }
+        out.into()
+    }
+}
+
+/// Decode requests in the packed format produced by [`ExecutionLayerRequest::encode`].
+///
+/// The data of known request types has a fixed length. The length of unknown request types cannot
+/// be determined, so an unknown type byte consumes the remainder of the input as
+/// [`ExecutionLayerRequest::Unknown`] data.
+pub fn decode_requests(mut data: &[u8]) -> Result<Vec<ExecutionLayerRequest>, DecodeError> {
+    let mut requests = Vec::new();
+    while let Some((&type_byte, rest)) = data.split_first() {
+        let size = match type_byte {
+            DEPOSIT_REQUEST_TYPE => DepositRequest::SIZE,
+            WITHDRAWAL_REQUEST_TYPE => WithdrawalRequest::SIZE,
+            CONSOLIDATION_REQUEST_TYPE => ConsolidationRequest::SIZE,
+            _ => rest.len(),
+        };
+        if rest.len() < size {
+            return Err(DecodeError::InputTooShort {
+                type_byte,
+                expected: size,
+                actual: rest.len(),
+            });
+        }
+
+        let (request, rest) = rest.split_at(size);
+        requests.push(match type_byte {
+            DEPOSIT_REQUEST_TYPE => DepositRequest::decode(request).into(),
+            WITHDRAWAL_REQUEST_TYPE => WithdrawalRequest::decode(request).into(),
+            CONSOLIDATION_REQUEST_TYPE => ConsolidationRequest::decode(request).into(),
+            _ => ExecutionLayerRequest::Unknown { type_byte, data: request.to_vec().into() },
+        });
+        data = rest;
+    }
+    Ok(requests)
+}
+
+/// Compute the requests hash committed to in the block header.
+///
+/// Requests are grouped by type in ascending order. The hash is the `sha256` of the concatenated
+/// `sha256(type_byte ++ data)` digests of each group, where `data` is the concatenation of the
+/// group's request data. Groups whose `type_byte ++ data` is at most one byte long, i.e. types
+/// without any requests or with only empty [`ExecutionLayerRequest::Unknown`] data, are skipped.
+///
+/// See also [the EIP-7685 specification](https://eips.ethereum.org/EIPS/eip-7685#block-header).
+pub fn requests_hash(requests: &[ExecutionLayerRequest]) -> B256 {
+    let mut types = requests.iter().map(ExecutionLayerRequest::type_byte).collect::<Vec<_>>();
+    types.sort_unstable();
+    types.dedup();
+
+    let mut hasher = Sha256::new();
+    for type_byte in types {
+        let mut group = vec![type_byte];
+        for request in requests.iter().filter(|request| request.type_byte() == type_byte) {
+            request.encode_data(&mut group);
+        }
+        if group.len() <= 1 {
+            continue;
+        }
+        hasher.update(Sha256::digest(group));
+    }
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit(index: u64) -> DepositRequest {
+        DepositRequest {
+            pubkey: FixedBytes::repeat_byte(0x11),
+            withdrawal_credentials: B256::repeat_byte(0x22),
+            amount: 32_000_000_000,
+            signature: FixedBytes::repeat_byte(0x33),
+            index,
+        }
+    }
+
+    fn withdrawal() -> WithdrawalRequest {
+        WithdrawalRequest {
+            source_address: Address::repeat_byte(0x44),
+            validator_pubkey: FixedBytes::repeat_byte(0x55),
+            amount: 1,
+        }
+    }
+
+    fn consolidation() -> ConsolidationRequest {
+        ConsolidationRequest {
+            source_address: Address::repeat_byte(0x66),
+            source_pubkey: FixedBytes::repeat_byte(0x77),
+            target_pubkey: FixedBytes::repeat_byte(0x88),
+        }
+    }
+
+    #[test]
+    fn type_bytes() {
+        assert_eq!(ExecutionLayerRequest::from(deposit(0)).type_byte(), 0x00);
+        assert_eq!(ExecutionLayerRequest::from(withdrawal()).type_byte(), 0x01);
+        assert_eq!(ExecutionLayerRequest::from(consolidation()).type_byte(), 0x02);
+        let unknown = ExecutionLayerRequest::Unknown { type_byte: 0x7f, data: Bytes::new() };
+        assert_eq!(unknown.type_byte(), 0x7f);
+    }
+
+    #[test]
+    fn packed_roundtrip() {
+        let requests = vec![
+            deposit(0).into(),
+            deposit(1).into(),
+            withdrawal().into(),
+            consolidation().into(),
+            ExecutionLayerRequest::Unknown { type_byte: 0x03, data: Bytes::from_static(&[1, 2]) },
+        ];
+        let encoded = ExecutionLayerRequest::encode(&requests);
+        assert_eq!(
+            encoded.len(),
+            2 * (1 + DepositRequest::SIZE)
+                + (1 + WithdrawalRequest::SIZE)
+                + (1 + ConsolidationRequest::SIZE)
+                + 3
+        );
+        assert_eq!(decode_requests(&encoded).unwrap(), requests);
+    }
+
+    #[test]
+    fn decode_truncated() {
+        let encoded = ExecutionLayerRequest::encode(&[withdrawal().into()]);
+        let err = decode_requests(&encoded[..encoded.len() - 1]).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InputTooShort {
+                type_byte: WITHDRAWAL_REQUEST_TYPE,
+                expected: WithdrawalRequest::SIZE,
+                actual: WithdrawalRequest::SIZE - 1,
+            }
+        );
+    }
+
+    #[test]
+    fn hash_empty() {
+        assert_eq!(requests_hash(&[]), EMPTY_REQUESTS_HASH);
+    }
+
+    #[test]
+    fn hash_skips_empty_groups() {
+        let empty = ExecutionLayerRequest::Unknown { type_byte: 0x03, data: Bytes::new() };
+        assert_eq!(requests_hash(std::slice::from_ref(&empty)), EMPTY_REQUESTS_HASH);
+        let requests = [withdrawal().into(), empty];
+        assert_eq!(requests_hash(&requests), requests_hash(&[withdrawal().into()]));
+    }
+
+    #[test]
+    fn hash_groups_by_type() {
+        let mut deposits = vec![DEPOSIT_REQUEST_TYPE];
+        deposit(0).encode(&mut deposits);
+        deposit(1).encode(&mut deposits);
+        let mut withdrawals = vec![WITHDRAWAL_REQUEST_TYPE];
+        withdrawal().encode(&mut withdrawals);
+
+        let mut expected = Sha256::new();
+        expected.update(Sha256::digest(deposits));
+        expected.update(Sha256::digest(withdrawals));
+        let expected = B256::from_slice(&expected.finalize());
+
+        // Ordering across types does not matter, ordering within a type does.
+        let requests = [withdrawal().into(), deposit(0).into(), deposit(1).into()];
+        assert_eq!(requests_hash(&requests), expected);
+        let requests = [deposit(1).into(), deposit(0).into(), withdrawal().into()];
+        assert_ne!(requests_hash(&requests), expected);
+    }
+}
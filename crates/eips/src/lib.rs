@@ -15,16 +15,30 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod eip1014;
+pub use eip1014::{create2_address, create_address};
+
 pub mod eip1559;
 pub use eip1559::calc_next_block_base_fee;
 
+pub mod eip2200;
+pub use eip2200::calc_sstore_gas;
+
+pub mod eip2481;
+
 pub mod eip2718;
 
 pub mod eip2930;
 
+pub mod eip4361;
+
 pub mod eip4788;
 
 pub mod eip4844;
 pub use eip4844::{calc_blob_gasprice, calc_excess_blob_gas};
 
+pub mod eip7594;
+
+pub mod eip7685;
+
 pub mod merge;
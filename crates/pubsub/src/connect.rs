@@ -26,6 +26,14 @@ pub trait PubSubConnect: Sized + Send + Sync + 'static {
         self.connect()
     }
 
+    /// Returns the maximum number of active subscriptions, if any.
+    ///
+    /// Subscribing beyond this limit fails, and the new subscription is
+    /// immediately cancelled on the server. Defaults to unlimited.
+    fn max_subscriptions(&self) -> Option<usize> {
+        None
+    }
+
     /// Convert the configuration object into a service with a running backend.
     fn into_service(self) -> Pbf<'static, PubSubFrontend, TransportError> {
         Box::pin(PubSubService::connect(self))
@@ -1,13 +1,15 @@
-use crate::{ix::PubSubInstruction, managers::InFlight, RawSubscription};
+use crate::{ix::PubSubInstruction, managers::InFlight, RawSubscription, Subscription};
 use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
-use alloy_primitives::U256;
+use alloy_primitives::{B256, U256};
 use alloy_transport::{TransportError, TransportErrorKind, TransportFut};
 use futures::{future::try_join_all, FutureExt, TryFutureExt};
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
 use std::{
     future::Future,
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 
 /// A `PubSubFrontend` is [`Transport`] composed of a channel to a running
 /// PubSub service.
@@ -29,11 +31,84 @@ impl PubSubFrontend {
         &self,
         id: U256,
     ) -> impl Future<Output = Result<RawSubscription, TransportError>> + Send + 'static {
+        self.get_subscription_with_history(id, 0)
+    }
+
+    /// Get the subscription ID for a local ID. The subscription first yields
+    /// up to `k` of the most recent notifications retained for it, oldest
+    /// first.
+    ///
+    /// No notifications are retained unless enabled with
+    /// [`PubSubFrontend::retain_history`].
+    pub fn get_subscription_with_history(
+        &self,
+        id: U256,
+        k: usize,
+    ) -> impl Future<Output = Result<RawSubscription, TransportError>> + Send + 'static {
+        let backend_tx = self.tx.clone();
+        async move {
+            let (tx, rx) = oneshot::channel();
+            backend_tx
+                .send(PubSubInstruction::GetSub(id, k, tx))
+                .map_err(|_| TransportErrorKind::backend_gone())?;
+            rx.await.map_err(|_| TransportErrorKind::backend_gone())
+        }
+    }
+
+    /// Get a typed receiver for a local ID, deserializing its notifications
+    /// into `T`.
+    pub fn get_typed_subscription<T: DeserializeOwned + 'static>(
+        &self,
+        id: U256,
+    ) -> impl Future<Output = Result<Subscription<T>, TransportError>> + Send + 'static {
+        self.get_subscription(id).map_ok(Into::into)
+    }
+
+    /// Get a receiver for each active subscription, e.g. for a dashboard, in
+    /// order of local ID.
+    pub fn get_all_subscriptions(
+        &self,
+    ) -> impl Future<Output = Result<Vec<RawSubscription>, TransportError>> + Send + 'static {
+        let backend_tx = self.tx.clone();
+        async move {
+            let (tx, rx) = oneshot::channel();
+            backend_tx
+                .send(PubSubInstruction::GetAllSubs(tx))
+                .map_err(|_| TransportErrorKind::backend_gone())?;
+            rx.await.map_err(|_| TransportErrorKind::backend_gone())
+        }
+    }
+
+    /// Retain up to `capacity` of the most recent notifications for a local
+    /// ID, to be replayed by [`PubSubFrontend::get_subscription_with_history`].
+    /// Retention is off by default, and a capacity of `0` disables it again.
+    ///
+    /// Retained notifications are kept in memory, serialized, for as long as
+    /// the subscription is active, even if there are no receivers. This costs
+    /// up to `capacity` times the size of a notification, which may be
+    /// significant for large notifications such as full blocks.
+    pub fn retain_history(&self, id: U256, capacity: usize) -> Result<(), TransportError> {
+        self.tx
+            .send(PubSubInstruction::SetHistory(id, capacity))
+            .map_err(|_| TransportErrorKind::backend_gone())
+    }
+
+    /// Get a receiver for the notifications of all subscriptions, each tagged
+    /// with the local ID of its subscription, e.g. for debugging.
+    ///
+    /// This is separate from the per-subscription channels, and does not
+    /// affect them. Notifications are only copied to it while a receiver
+    /// exists.
+    pub fn subscribe_all(
+        &self,
+    ) -> impl Future<Output = Result<broadcast::Receiver<(B256, Box<RawValue>)>, TransportError>>
+           + Send
+           + 'static {
         let backend_tx = self.tx.clone();
         async move {
             let (tx, rx) = oneshot::channel();
             backend_tx
-                .send(PubSubInstruction::GetSub(id, tx))
+                .send(PubSubInstruction::SubscribeAll(tx))
                 .map_err(|_| TransportErrorKind::backend_gone())?;
             rx.await.map_err(|_| TransportErrorKind::backend_gone())
         }
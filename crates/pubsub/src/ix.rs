@@ -1,25 +1,38 @@
 use crate::{managers::InFlight, RawSubscription};
 
-use alloy_primitives::U256;
+use alloy_primitives::{B256, U256};
+use serde_json::value::RawValue;
 use std::fmt;
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast, oneshot};
 
 /// Instructions for the pubsub service.
 pub(crate) enum PubSubInstruction {
     /// Send a request.
     Request(InFlight),
-    /// Get the subscription ID for a local ID.
-    GetSub(U256, oneshot::Sender<RawSubscription>),
+    /// Get the subscription ID for a local ID, replaying up to the given
+    /// number of retained notifications.
+    GetSub(U256, usize, oneshot::Sender<RawSubscription>),
+    /// Get a receiver for each subscription.
+    GetAllSubs(oneshot::Sender<Vec<RawSubscription>>),
+    /// Set the number of notifications to retain for a local ID.
+    SetHistory(U256, usize),
     /// Unsubscribe from a subscription.
     Unsubscribe(U256),
+    /// Get a receiver for the notifications of all subscriptions.
+    SubscribeAll(oneshot::Sender<broadcast::Receiver<(B256, Box<RawValue>)>>),
 }
 
 impl fmt::Debug for PubSubInstruction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Request(arg0) => f.debug_tuple("Request").field(arg0).finish(),
-            Self::GetSub(arg0, _) => f.debug_tuple("GetSub").field(arg0).finish(),
+            Self::GetSub(arg0, arg1, _) => f.debug_tuple("GetSub").field(arg0).field(arg1).finish(),
+            Self::GetAllSubs(_) => f.write_str("GetAllSubs"),
+            Self::SetHistory(arg0, arg1) => {
+                f.debug_tuple("SetHistory").field(arg0).field(arg1).finish()
+            }
             Self::Unsubscribe(arg0) => f.debug_tuple("Unsubscribe").field(arg0).finish(),
+            Self::SubscribeAll(_) => f.write_str("SubscribeAll"),
         }
     }
 }
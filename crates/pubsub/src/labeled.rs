@@ -0,0 +1,153 @@
+use crate::{Subscription, SubscriptionItem};
+use serde::de::DeserializeOwned;
+use std::ops::{Deref, DerefMut};
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+impl<T> Subscription<T> {
+    /// Attach a human-readable label to the subscription, e.g.
+    /// `"newHeads-mainnet"`, for tracing and debugging.
+    pub fn with_label(self, label: impl Into<String>) -> LabeledSubscription<T> {
+        LabeledSubscription { inner: self, label: label.into() }
+    }
+}
+
+/// A [`Subscription`] with a human-readable label, created by
+/// [`Subscription::with_label`].
+///
+/// All methods of the subscription are available via [`Deref`]. The label is
+/// included in the [`Debug`] output, and kept by
+/// [`resubscribe`](Self::resubscribe).
+///
+/// [`recv`](Self::recv), [`recv_any`](Self::recv_any) and
+/// [`try_recv`](Self::try_recv) log lag as a warning and closing as a debug
+/// event, with the label as the `label` field. Other methods reached via
+/// [`Deref`] don't log. The label is only known to the client, so it is not
+/// passed to the pubsub service.
+#[derive(Debug)]
+pub struct LabeledSubscription<T> {
+    inner: Subscription<T>,
+    label: String,
+}
+
+impl<T> LabeledSubscription<T> {
+    /// Get the label of the subscription.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Remove the label, returning the inner [`Subscription`].
+    #[allow(clippy::missing_const_for_fn)] // erroneous lint
+    pub fn into_inner(self) -> Subscription<T> {
+        self.inner
+    }
+
+    /// Wrapper for [`Subscription::resubscribe`]. Create a new subscription
+    /// with the same label, starting from the current tail element.
+    pub fn resubscribe(&self) -> Self {
+        self.inner.resubscribe().with_label(self.label.clone())
+    }
+}
+
+impl<T: DeserializeOwned> LabeledSubscription<T> {
+    /// Wrapper for [`Subscription::recv`], logging lag and closing with the
+    /// label.
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        let res = self.inner.recv().await;
+        self.trace_recv(res)
+    }
+
+    /// Wrapper for [`Subscription::recv_any`], logging lag and closing with
+    /// the label.
+    pub async fn recv_any(&mut self) -> Result<SubscriptionItem<T>, RecvError> {
+        let res = self.inner.recv_any().await;
+        self.trace_recv(res)
+    }
+
+    /// Wrapper for [`Subscription::try_recv`], logging lag and closing with
+    /// the label.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let res = self.inner.try_recv();
+        match &res {
+            Err(TryRecvError::Lagged(missed)) => self.trace_lag(*missed),
+            Err(TryRecvError::Closed) => self.trace_closed(),
+            _ => {}
+        }
+        res
+    }
+
+    fn trace_recv<U>(&self, res: Result<U, RecvError>) -> Result<U, RecvError> {
+        match &res {
+            Err(RecvError::Lagged(missed)) => self.trace_lag(*missed),
+            Err(RecvError::Closed) => self.trace_closed(),
+            Ok(_) => {}
+        }
+        res
+    }
+
+    fn trace_lag(&self, missed: u64) {
+        warn!(label = %self.label, local_id = %self.local_id(), missed, "Subscription lagged.");
+    }
+
+    fn trace_closed(&self) {
+        debug!(label = %self.label, local_id = %self.local_id(), "Subscription closed.");
+    }
+}
+
+impl<T> Deref for LabeledSubscription<T> {
+    type Target = Subscription<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for LabeledSubscription<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RawSubscription;
+    use alloy_primitives::B256;
+    use std::collections::VecDeque;
+    use tokio::sync::broadcast;
+
+    #[tokio::test]
+    async fn with_label() {
+        let (tx, rx) = broadcast::channel(16);
+        let sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        let mut sub = sub.with_label("newHeads-mainnet");
+        assert_eq!(sub.label(), "newHeads-mainnet");
+        assert!(format!("{sub:?}").contains("newHeads-mainnet"));
+
+        let mut resubscribed = sub.resubscribe();
+        assert_eq!(resubscribed.label(), "newHeads-mainnet");
+
+        tx.send(serde_json::value::to_raw_value(&1).unwrap()).unwrap();
+        assert_eq!(sub.recv().await.unwrap(), 1);
+        assert_eq!(resubscribed.recv().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn recv_errors() {
+        let (tx, rx) = broadcast::channel(1);
+        let sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        let mut sub = sub.with_label("logs-ERC20-Transfer");
+        for value in 1..=2u64 {
+            tx.send(serde_json::value::to_raw_value(&value).unwrap()).unwrap();
+        }
+
+        // Errors are passed through after logging, and still tracked.
+        assert_eq!(sub.try_recv().unwrap_err(), TryRecvError::Lagged(1));
+        assert!(sub.lag_rate() > 0.0);
+        assert_eq!(sub.recv().await.unwrap(), 2);
+        drop(tx);
+        assert_eq!(sub.recv().await.unwrap_err(), RecvError::Closed);
+        assert_eq!(sub.try_recv().unwrap_err(), TryRecvError::Closed);
+    }
+}
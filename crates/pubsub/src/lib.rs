@@ -29,9 +29,36 @@ mod ix;
 mod handle;
 pub use handle::{ConnectionHandle, ConnectionInterface};
 
+mod labeled;
+pub use labeled::LabeledSubscription;
+
 mod managers;
 
+mod map_err;
+pub use map_err::MappedErrSubscription;
+
+mod merge;
+pub use merge::PriorityMergedSubscription;
+
+mod notification;
+pub use notification::parse_notification;
+
+mod on_close;
+pub use on_close::OnCloseSubscription;
+
 mod service;
 
+mod split;
+pub use split::{SubscriptionHandle, SubscriptionReader};
+
+mod stream;
+pub use stream::{
+    ChunkSubscription, FlatMapSubscription, RetryOnLagSubscription, SkipSubscription,
+    SubscriptionError, SubscriptionStream, ThrottleSubscription,
+};
+
 mod sub;
-pub use sub::{RawSubscription, Subscription, SubscriptionItem};
+pub use sub::{
+    CollectOutcome, RawSubscription, Subscription, SubscriptionEnum, SubscriptionEvent,
+    SubscriptionItem,
+};
@@ -1,11 +1,14 @@
 use alloy_json_rpc::SerializedRequest;
 use alloy_primitives::B256;
 use serde_json::value::RawValue;
-use std::{fmt, hash::Hash};
+use std::{collections::VecDeque, fmt, hash::Hash};
 use tokio::sync::broadcast;
 
 use crate::RawSubscription;
 
+/// The capacity of the broadcast channel of each subscription.
+pub(crate) const CHANNEL_CAPACITY: usize = 16;
+
 #[derive(Clone)]
 /// An active subscription.
 pub(crate) struct ActiveSubscription {
@@ -14,7 +17,17 @@ pub(crate) struct ActiveSubscription {
     /// The serialized subscription request.
     pub(crate) request: SerializedRequest,
     /// The channel via which notifications are broadcast.
+    ///
+    /// NB: This is a broadcast channel even if there is a single receiver.
+    /// Requests with the same params share one active subscription, so a new
+    /// receiver may be added at any time, and [`RawSubscription`] exposes
+    /// `resubscribe` and `same_channel`, which an `mpsc` channel can't support.
     pub(crate) tx: broadcast::Sender<Box<RawValue>>,
+    /// The most recent notifications, replayed to new receivers by
+    /// [`ActiveSubscription::subscribe_with_history`].
+    history: VecDeque<Box<RawValue>>,
+    /// The maximum number of notifications to retain in `history`.
+    history_capacity: usize,
 }
 
 // NB: We implement this to prevent any incorrect future implementations.
@@ -51,6 +64,7 @@ impl fmt::Debug for ActiveSubscription {
             .field("local_id", &self.local_id)
             .field("request", &self.request)
             .field("subscribers", &self.tx.receiver_count())
+            .field("history", &self.history.len())
             .finish()
     }
 }
@@ -59,8 +73,24 @@ impl ActiveSubscription {
     /// Create a new active subscription.
     pub(crate) fn new(request: SerializedRequest) -> Self {
         let local_id = request.params_hash();
-        let (tx, _rx) = broadcast::channel(16);
-        Self { request, local_id, tx }
+        let (tx, _rx) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { request, local_id, tx, history: VecDeque::new(), history_capacity: 0 }
+    }
+
+    /// Retain up to `capacity` of the most recent notifications, so that they
+    /// can be replayed to new receivers with
+    /// [`ActiveSubscription::subscribe_with_history`]. A capacity of `0`, the
+    /// default, disables the history.
+    ///
+    /// Retained notifications are kept in memory, serialized, for as long as
+    /// the subscription is active, even if there are no receivers. This costs
+    /// up to `capacity` times the size of a notification per subscription,
+    /// which may be significant for large notifications such as full blocks.
+    pub(crate) fn set_history_capacity(&mut self, capacity: usize) {
+        self.history_capacity = capacity;
+        while self.history.len() > capacity {
+            self.history.pop_front();
+        }
     }
 
     /// Serialize the request as a boxed [`RawValue`].
@@ -72,14 +102,86 @@ impl ActiveSubscription {
 
     /// Get a subscription.
     pub(crate) fn subscribe(&self) -> RawSubscription {
-        RawSubscription { rx: self.tx.subscribe(), local_id: self.local_id }
+        self.subscribe_with_history(0)
+    }
+
+    /// Get a subscription that first yields up to `k` of the most recent
+    /// retained notifications, oldest first.
+    ///
+    /// See [`ActiveSubscription::set_history_capacity`].
+    pub(crate) fn subscribe_with_history(&self, k: usize) -> RawSubscription {
+        let skip = self.history.len().saturating_sub(k);
+        RawSubscription {
+            rx: self.tx.subscribe(),
+            local_id: self.local_id,
+            replay: self.history.iter().skip(skip).cloned().collect(),
+        }
     }
 
     /// Notify the subscription channel of a new value, if any receiver exists.
-    /// If no receiver exists, the notification is dropped.
+    /// If no receiver exists, the notification is dropped, unless it is
+    /// retained in the history.
     pub(crate) fn notify(&mut self, notification: Box<RawValue>) {
+        if self.history_capacity > 0 {
+            if self.history.len() == self.history_capacity {
+                self.history.pop_front();
+            }
+            self.history.push_back(notification.clone());
+        }
         if self.tx.receiver_count() > 0 {
             let _ = self.tx.send(notification);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request, RequestMeta};
+
+    fn active_sub() -> ActiveSubscription {
+        let meta = RequestMeta { method: "eth_subscribe", id: Id::Number(1) };
+        ActiveSubscription::new(Request { meta, params: ("newHeads",) }.serialize().unwrap())
+    }
+
+    fn notification(n: u64) -> Box<RawValue> {
+        serde_json::value::to_raw_value(&n).unwrap()
+    }
+
+    fn drain(sub: &mut RawSubscription) -> Vec<String> {
+        std::iter::from_fn(|| sub.try_recv().ok()).map(|value| value.get().to_owned()).collect()
+    }
+
+    #[test]
+    fn history_is_off_by_default() {
+        let mut active = active_sub();
+        active.notify(notification(1));
+
+        let mut sub = active.subscribe_with_history(5);
+        assert!(sub.is_empty());
+        active.notify(notification(2));
+        assert_eq!(drain(&mut sub), ["2"]);
+    }
+
+    #[test]
+    fn subscribe_with_history() {
+        let mut active = active_sub();
+        active.set_history_capacity(3);
+        for n in 1..=4 {
+            active.notify(notification(n));
+        }
+
+        let mut late = active.subscribe_with_history(2);
+        let mut all = active.subscribe_with_history(10);
+        let mut none = active.subscribe();
+        assert_eq!(late.len(), 2);
+        active.notify(notification(5));
+
+        assert_eq!(drain(&mut late), ["3", "4", "5"]);
+        assert_eq!(drain(&mut all), ["2", "3", "4", "5"]);
+        assert_eq!(drain(&mut none), ["5"]);
+
+        active.set_history_capacity(1);
+        assert_eq!(drain(&mut active.subscribe_with_history(10)), ["5"]);
+    }
+}
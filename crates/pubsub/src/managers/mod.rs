@@ -1,5 +1,5 @@
 mod active_sub;
-pub(crate) use active_sub::ActiveSubscription;
+pub(crate) use active_sub::{ActiveSubscription, CHANNEL_CAPACITY};
 
 mod in_flight;
 pub(crate) use in_flight::InFlight;
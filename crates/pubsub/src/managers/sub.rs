@@ -1,7 +1,32 @@
-use crate::{managers::ActiveSubscription, RawSubscription};
+use crate::{
+    managers::{ActiveSubscription, CHANNEL_CAPACITY},
+    RawSubscription,
+};
 use alloy_json_rpc::{EthNotification, SerializedRequest};
 use alloy_primitives::{B256, U256};
 use bimap::BiBTreeMap;
+use serde_json::value::RawValue;
+use std::fmt;
+use tokio::sync::broadcast;
+
+/// An error managing subscriptions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SubError {
+    /// The maximum number of active subscriptions was reached.
+    CapacityExceeded(usize),
+}
+
+impl fmt::Display for SubError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityExceeded(max) => {
+                write!(f, "subscription capacity exceeded: at most {max} active subscriptions")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubError {}
 
 #[derive(Default, Debug)]
 pub(crate) struct SubscriptionManager {
@@ -9,9 +34,21 @@ pub(crate) struct SubscriptionManager {
     local_to_sub: BiBTreeMap<B256, ActiveSubscription>,
     /// Tracks the CURRENT server id for a subscription.
     local_to_server: BiBTreeMap<B256, U256>,
+    /// The maximum number of active subscriptions, if any.
+    max_subscriptions: Option<usize>,
+    /// The channel via which every notification of a known subscription is
+    /// also broadcast, created by the first call to
+    /// [`SubscriptionManager::subscribe_all`].
+    all: Option<broadcast::Sender<(B256, Box<RawValue>)>>,
 }
 
 impl SubscriptionManager {
+    /// Limit the number of active subscriptions to `max_subscriptions`.
+    pub(crate) const fn with_max_subscriptions(mut self, max_subscriptions: Option<usize>) -> Self {
+        self.max_subscriptions = max_subscriptions;
+        self
+    }
+
     /// Get an iterator over the subscriptions.
     pub(crate) fn iter(&self) -> impl Iterator<Item = (&B256, &ActiveSubscription)> {
         self.local_to_sub.iter()
@@ -22,8 +59,19 @@ impl SubscriptionManager {
         self.local_to_sub.len()
     }
 
-    /// Insert a subscription.
-    fn insert(&mut self, request: SerializedRequest, server_id: U256) -> RawSubscription {
+    /// Insert a subscription, failing if the maximum number of subscriptions
+    /// is reached.
+    fn insert(
+        &mut self,
+        request: SerializedRequest,
+        server_id: U256,
+    ) -> Result<RawSubscription, SubError> {
+        if let Some(max) = self.max_subscriptions {
+            if self.local_to_sub.len() >= max {
+                return Err(SubError::CapacityExceeded(max));
+            }
+        }
+
         let active = ActiveSubscription::new(request);
         let sub = active.subscribe();
 
@@ -31,22 +79,26 @@ impl SubscriptionManager {
         self.local_to_server.insert(local_id, server_id);
         self.local_to_sub.insert(local_id, active);
 
-        sub
+        Ok(sub)
     }
 
     /// Insert or update the server_id for a subscription.
+    ///
+    /// Fails if the subscription is new and the maximum number of
+    /// subscriptions is reached. Updating a known subscription always
+    /// succeeds.
     pub(crate) fn upsert(
         &mut self,
         request: SerializedRequest,
         server_id: U256,
-    ) -> RawSubscription {
+    ) -> Result<RawSubscription, SubError> {
         let local_id = request.params_hash();
 
         // If we already know a subscription with the exact params,
         // we can just update the server_id and get a new listener.
         if self.local_to_sub.contains_left(&local_id) {
             self.change_server_id(local_id, server_id);
-            self.get_subscription(local_id).expect("checked existence")
+            Ok(self.get_subscription(local_id).expect("checked existence"))
         } else {
             self.insert(request, server_id)
         }
@@ -57,8 +109,32 @@ impl SubscriptionManager {
         self.local_to_server.get_by_right(&server_id).copied()
     }
 
+    /// Get the current server_id of a subscription, if it is active on the
+    /// server.
+    pub(crate) fn server_id_for(&self, local_id: B256) -> Option<U256> {
+        self.local_to_server.get_by_left(&local_id).copied()
+    }
+
+    /// Drop all server_ids, keeping the subscriptions, e.g. before
+    /// re-subscribing after a reconnect.
+    pub(crate) fn clear_server_ids_only(&mut self) {
+        self.local_to_server.clear();
+    }
+
     /// Drop all server_ids.
+    #[deprecated(note = "use `clear_server_ids_only` instead")]
+    #[allow(dead_code)]
     pub(crate) fn drop_server_ids(&mut self) {
+        self.clear_server_ids_only();
+    }
+
+    /// Remove all subscriptions and their server_ids at once, dropping their
+    /// channels.
+    // Not yet used by the service, which keeps its subscriptions until it is
+    // dropped.
+    #[allow(dead_code)]
+    pub(crate) fn clear(&mut self) {
+        self.local_to_sub.clear();
         self.local_to_server.clear();
     }
 
@@ -73,11 +149,39 @@ impl SubscriptionManager {
         let _ = self.local_to_server.remove_by_left(&local_id);
     }
 
+    /// Remove a subscription by its server_id, returning its local_id if the
+    /// sub was known.
+    pub(crate) fn remove_sub_by_server_id(&mut self, server_id: U256) -> Option<B256> {
+        let local_id = self.local_id_for(server_id)?;
+        self.remove_sub(local_id);
+        Some(local_id)
+    }
+
+    /// Get a receiver for the notifications of all subscriptions, tagged with
+    /// their local ID. This is separate from the per-subscription channels,
+    /// and notifications are only copied to it while a receiver exists.
+    pub(crate) fn subscribe_all(&mut self) -> broadcast::Receiver<(B256, Box<RawValue>)> {
+        match &self.all {
+            Some(tx) => tx.subscribe(),
+            None => {
+                let (tx, rx) = broadcast::channel(CHANNEL_CAPACITY);
+                self.all = Some(tx);
+                rx
+            }
+        }
+    }
+
     /// Notify the subscription channel of a new value, if the sub is known,
     /// and if any receiver exists. If the sub id is unknown, or no receiver
     /// exists, the notification is dropped.
+    ///
+    /// Notifications of known subscriptions are also sent to the receivers of
+    /// [`SubscriptionManager::subscribe_all`], if any.
     pub(crate) fn notify(&mut self, notification: EthNotification) {
         if let Some(local_id) = self.local_id_for(notification.subscription) {
+            if let Some(all) = self.all.as_ref().filter(|all| all.receiver_count() > 0) {
+                let _ = all.send((local_id, notification.result.clone()));
+            }
             if let Some((_, mut sub)) = self.local_to_sub.remove_by_left(&local_id) {
                 sub.notify(notification.result);
                 self.local_to_sub.insert(local_id, sub);
@@ -85,8 +189,180 @@ impl SubscriptionManager {
         }
     }
 
+    /// Set the number of notifications to retain for a subscription, if the
+    /// sub is known.
+    pub(crate) fn set_history_capacity(&mut self, local_id: B256, capacity: usize) {
+        if let Some((_, mut sub)) = self.local_to_sub.remove_by_left(&local_id) {
+            sub.set_history_capacity(capacity);
+            self.local_to_sub.insert(local_id, sub);
+        }
+    }
+
     /// Get a receiver for a subscription.
     pub(crate) fn get_subscription(&self, local_id: B256) -> Option<RawSubscription> {
-        self.local_to_sub.get_by_left(&local_id).map(ActiveSubscription::subscribe)
+        self.get_subscription_with_history(local_id, 0)
+    }
+
+    /// Get a receiver for each subscription, in order of local_id.
+    pub(crate) fn get_all_subscriptions(&self) -> Vec<RawSubscription> {
+        self.local_to_sub.right_values().map(ActiveSubscription::subscribe).collect()
+    }
+
+    /// Get a receiver for a subscription, pre-seeded with up to `k` retained
+    /// notifications.
+    pub(crate) fn get_subscription_with_history(
+        &self,
+        local_id: B256,
+        k: usize,
+    ) -> Option<RawSubscription> {
+        self.local_to_sub.get_by_left(&local_id).map(|sub| sub.subscribe_with_history(k))
+    }
+}
+
+/// Consumes the manager, yielding the local ID, current server ID (if any) and
+/// request of each subscription. Used to unsubscribe on service shutdown.
+impl IntoIterator for SubscriptionManager {
+    type Item = (B256, Option<U256>, SerializedRequest);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let Self { local_to_sub, local_to_server, .. } = self;
+        local_to_sub
+            .into_iter()
+            .map(|(local_id, sub)| {
+                (local_id, local_to_server.get_by_left(&local_id).copied(), sub.request)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request, RequestMeta};
+
+    fn request(kind: &'static str) -> SerializedRequest {
+        let meta = RequestMeta { method: "eth_subscribe", id: Id::Number(1) };
+        Request { meta, params: (kind,) }.serialize().unwrap()
+    }
+
+    #[test]
+    fn remove_sub_by_server_id() {
+        let mut subs = SubscriptionManager::default();
+        let heads = subs.upsert(request("newHeads"), U256::from(1)).unwrap().local_id();
+        let logs = subs.upsert(request("logs"), U256::from(2)).unwrap().local_id();
+
+        assert_eq!(subs.server_id_for(heads), Some(U256::from(1)));
+        assert_eq!(subs.remove_sub_by_server_id(U256::from(1)), Some(heads));
+        assert_eq!(subs.server_id_for(heads), None);
+        assert_eq!(subs.local_id_for(U256::from(1)), None);
+        assert!(subs.get_subscription(heads).is_none());
+        assert_eq!(subs.len(), 1);
+
+        assert_eq!(subs.remove_sub_by_server_id(U256::from(1)), None);
+        assert_eq!(subs.local_id_for(U256::from(2)), Some(logs));
+        assert!(subs.get_subscription(logs).is_some());
+    }
+
+    #[test]
+    fn max_subscriptions() {
+        let mut subs = SubscriptionManager::default().with_max_subscriptions(Some(2));
+        subs.upsert(request("newHeads"), U256::from(1)).unwrap();
+        subs.upsert(request("logs"), U256::from(2)).unwrap();
+        assert_eq!(
+            subs.upsert(request("newPendingTransactions"), U256::from(3)).unwrap_err(),
+            SubError::CapacityExceeded(2)
+        );
+
+        // Re-upserting a known subscription, e.g. after a reconnect, does not count.
+        subs.upsert(request("newHeads"), U256::from(4)).unwrap();
+        assert_eq!(subs.len(), 2);
+
+        subs.remove_sub_by_server_id(U256::from(2));
+        subs.upsert(request("newPendingTransactions"), U256::from(3)).unwrap();
+    }
+
+    #[test]
+    fn clear() {
+        let mut subs = SubscriptionManager::default();
+        let heads = subs.upsert(request("newHeads"), U256::from(1)).unwrap();
+        let logs = subs.upsert(request("logs"), U256::from(2)).unwrap().local_id();
+
+        subs.clear_server_ids_only();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs.local_id_for(U256::from(1)), None);
+        assert!(subs.get_subscription(logs).is_some());
+
+        subs.upsert(request("logs"), U256::from(3)).unwrap();
+        subs.clear();
+        assert_eq!(subs.len(), 0);
+        assert_eq!(subs.iter().count(), 0);
+        assert_eq!(subs.local_id_for(U256::from(3)), None);
+        assert!(subs.get_subscription(heads.local_id()).is_none());
+        assert!(heads.is_closed());
+    }
+
+    #[test]
+    fn subscribe_all() {
+        let notification = |server_id: u64, result: &str| EthNotification {
+            subscription: U256::from(server_id),
+            result: RawValue::from_string(result.into()).unwrap(),
+        };
+
+        let mut subs = SubscriptionManager::default();
+        let mut heads = subs.upsert(request("newHeads"), U256::from(1)).unwrap();
+        let logs = subs.upsert(request("logs"), U256::from(2)).unwrap().local_id();
+
+        // Nothing is sent to the catch-all channel before it is subscribed to.
+        subs.notify(notification(1, "0"));
+        let mut all = subs.subscribe_all();
+        subs.notify(notification(1, "1"));
+        subs.notify(notification(2, "2"));
+        subs.notify(notification(3, "3"));
+
+        let (local_id, result) = all.try_recv().unwrap();
+        assert_eq!((local_id, result.get()), (heads.local_id(), "1"));
+        let (local_id, result) = all.try_recv().unwrap();
+        assert_eq!((local_id, result.get()), (logs, "2"));
+        assert!(all.try_recv().is_err());
+
+        // Normal delivery is unaffected.
+        assert_eq!(heads.try_recv().unwrap().get(), "0");
+        assert_eq!(heads.try_recv().unwrap().get(), "1");
+    }
+
+    #[test]
+    fn get_all_subscriptions() {
+        let mut subs = SubscriptionManager::default();
+        assert!(subs.get_all_subscriptions().is_empty());
+        let heads = subs.upsert(request("newHeads"), U256::from(1)).unwrap().local_id();
+        let logs = subs.upsert(request("logs"), U256::from(2)).unwrap().local_id();
+
+        let all = subs.get_all_subscriptions();
+        assert_eq!(all.len(), subs.len());
+        let mut local_ids: Vec<_> = all.iter().map(RawSubscription::local_id).collect();
+        local_ids.sort();
+        let mut expected = vec![heads, logs];
+        expected.sort();
+        assert_eq!(local_ids, expected);
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut subs = SubscriptionManager::default();
+        let heads = subs.upsert(request("newHeads"), U256::from(1)).unwrap().local_id();
+        let logs = subs.upsert(request("logs"), U256::from(2)).unwrap().local_id();
+        subs.clear_server_ids_only();
+        subs.upsert(request("logs"), U256::from(3)).unwrap();
+
+        let mut items: Vec<_> = subs
+            .into_iter()
+            .map(|(local_id, server_id, req)| (local_id, server_id, req.params_hash()))
+            .collect();
+        items.sort();
+        let mut expected = vec![(heads, None, heads), (logs, Some(U256::from(3)), logs)];
+        expected.sort();
+        assert_eq!(items, expected);
     }
 }
@@ -0,0 +1,148 @@
+use crate::{Subscription, SubscriptionItem};
+use serde::de::DeserializeOwned;
+use std::{fmt, marker::PhantomData};
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+impl<T> Subscription<T> {
+    /// Convert the channel errors of the subscription using `f`, e.g. into an
+    /// application-specific error type.
+    pub fn map_err<E, F>(self, f: F) -> MappedErrSubscription<T, E, F>
+    where
+        F: Fn(RecvError) -> E,
+    {
+        MappedErrSubscription { inner: self, f, _err: PhantomData }
+    }
+}
+
+/// A [`Subscription`] whose channel errors are converted with a function,
+/// created by [`Subscription::map_err`].
+pub struct MappedErrSubscription<T, E, F> {
+    inner: Subscription<T>,
+    f: F,
+    _err: PhantomData<fn() -> E>,
+}
+
+impl<T, E, F> fmt::Debug for MappedErrSubscription<T, E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedErrSubscription")
+            .field("local_id", &self.inner.local_id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E, F> MappedErrSubscription<T, E, F>
+where
+    F: Fn(RecvError) -> E,
+{
+    /// Get a reference to the inner subscription.
+    pub const fn inner(&self) -> &Subscription<T> {
+        &self.inner
+    }
+
+    /// Remove the error conversion, returning the inner [`Subscription`].
+    #[allow(clippy::missing_const_for_fn)] // erroneous lint
+    pub fn into_inner(self) -> Subscription<T> {
+        self.inner
+    }
+
+    /// Wrapper for [`Subscription::resubscribe`]. Create a new subscription
+    /// with the same error conversion, starting from the current tail element.
+    pub fn resubscribe(&self) -> Self
+    where
+        F: Clone,
+    {
+        self.inner.resubscribe().map_err(self.f.clone())
+    }
+}
+
+impl<T: DeserializeOwned, E, F> MappedErrSubscription<T, E, F>
+where
+    F: Fn(RecvError) -> E,
+{
+    /// Wrapper for [`Subscription::blocking_recv_any`].
+    pub fn blocking_recv_any(&mut self) -> Result<SubscriptionItem<T>, E> {
+        self.inner.blocking_recv_any().map_err(&self.f)
+    }
+
+    /// Wrapper for [`Subscription::recv_any`].
+    pub async fn recv_any(&mut self) -> Result<SubscriptionItem<T>, E> {
+        self.inner.recv_any().await.map_err(&self.f)
+    }
+
+    /// Wrapper for [`Subscription::try_recv_any`]. Returns `Ok(None)` if no
+    /// message is available.
+    pub fn try_recv_any(&mut self) -> Result<Option<SubscriptionItem<T>>, E> {
+        convert_try_recv(self.inner.try_recv_any(), &self.f)
+    }
+
+    /// Wrapper for [`Subscription::blocking_recv`].
+    pub fn blocking_recv(&mut self) -> Result<T, E> {
+        self.inner.blocking_recv().map_err(&self.f)
+    }
+
+    /// Wrapper for [`Subscription::recv`].
+    pub async fn recv(&mut self) -> Result<T, E> {
+        self.inner.recv().await.map_err(&self.f)
+    }
+
+    /// Wrapper for [`Subscription::try_recv`]. Returns `Ok(None)` if no
+    /// message of the expected type is available.
+    pub fn try_recv(&mut self) -> Result<Option<T>, E> {
+        convert_try_recv(self.inner.try_recv(), &self.f)
+    }
+}
+
+/// Convert a [`TryRecvError`] with a function taking [`RecvError`], mapping
+/// an empty channel to `Ok(None)`.
+fn convert_try_recv<T, E>(
+    res: Result<T, TryRecvError>,
+    f: impl Fn(RecvError) -> E,
+) -> Result<Option<T>, E> {
+    match res {
+        Ok(item) => Ok(Some(item)),
+        Err(TryRecvError::Empty) => Ok(None),
+        Err(TryRecvError::Closed) => Err(f(RecvError::Closed)),
+        Err(TryRecvError::Lagged(count)) => Err(f(RecvError::Lagged(count))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RawSubscription, Subscription};
+    use alloy_primitives::B256;
+    use std::collections::VecDeque;
+    use tokio::sync::broadcast::{self, error::RecvError};
+
+    #[derive(Debug, PartialEq)]
+    enum AppError {
+        Lagged(u64),
+        Closed,
+    }
+
+    fn to_app_error(err: RecvError) -> AppError {
+        match err {
+            RecvError::Lagged(count) => AppError::Lagged(count),
+            RecvError::Closed => AppError::Closed,
+        }
+    }
+
+    #[tokio::test]
+    async fn map_err() {
+        let (tx, rx) = broadcast::channel(2);
+        let sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        let mut sub = sub.map_err(to_app_error);
+        assert_eq!(sub.try_recv(), Ok(None));
+
+        for i in 0..3u64 {
+            tx.send(serde_json::value::to_raw_value(&i).unwrap()).unwrap();
+        }
+        assert_eq!(sub.try_recv(), Err(AppError::Lagged(1)));
+        assert_eq!(sub.recv().await, Ok(1));
+        assert_eq!(sub.try_recv(), Ok(Some(2)));
+
+        drop(tx);
+        assert_eq!(sub.recv().await, Err(AppError::Closed));
+        assert_eq!(sub.try_recv(), Err(AppError::Closed));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::Subscription;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+impl<T> Subscription<T> {
+    /// Merge this subscription with a `fallback` subscription of the same
+    /// type, preferring items from this one.
+    ///
+    /// See [`PriorityMergedSubscription`] for details.
+    pub const fn merge_with_priority(
+        self,
+        fallback: Subscription<T>,
+        primary_timeout: Duration,
+    ) -> PriorityMergedSubscription<T> {
+        PriorityMergedSubscription {
+            primary: self,
+            fallback,
+            primary_timeout,
+            primary_closed: false,
+        }
+    }
+}
+
+/// Two subscriptions to the same feed, one of which is preferred over the
+/// other. Created by [`Subscription::merge_with_priority`].
+///
+/// Each call to [`recv`] waits up to `primary_timeout` for an item from the
+/// primary subscription. If one arrives, it is yielded and any items already
+/// buffered on the fallback are discarded as duplicates. Otherwise, the next
+/// item from either subscription is yielded, preferring the primary. Yielding
+/// a fallback item logs a warning.
+///
+/// Once the primary subscription is closed, items are received from the
+/// fallback only.
+///
+/// [`recv`]: PriorityMergedSubscription::recv
+#[derive(Debug)]
+pub struct PriorityMergedSubscription<T> {
+    primary: Subscription<T>,
+    fallback: Subscription<T>,
+    primary_timeout: Duration,
+    primary_closed: bool,
+}
+
+impl<T> PriorityMergedSubscription<T> {
+    /// Get a reference to the primary subscription.
+    pub const fn primary(&self) -> &Subscription<T> {
+        &self.primary
+    }
+
+    /// Get a reference to the fallback subscription.
+    pub const fn fallback(&self) -> &Subscription<T> {
+        &self.fallback
+    }
+
+    /// Get the time to wait for the primary subscription before falling back.
+    pub const fn primary_timeout(&self) -> Duration {
+        self.primary_timeout
+    }
+
+    /// Split the merged subscription into its primary and fallback
+    /// subscriptions.
+    #[allow(clippy::missing_const_for_fn)] // erroneous lint
+    pub fn into_inner(self) -> (Subscription<T>, Subscription<T>) {
+        (self.primary, self.fallback)
+    }
+}
+
+impl<T: DeserializeOwned> PriorityMergedSubscription<T> {
+    /// Await an item of the expected type from either subscription, preferring
+    /// the primary.
+    ///
+    /// Returns [`RecvError::Closed`] once both subscriptions are closed. A lag
+    /// on the primary subscription is treated as a missed item, while a lag
+    /// on the fallback is returned as [`RecvError::Lagged`].
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        if !self.primary_closed {
+            match tokio::time::timeout(self.primary_timeout, self.primary.recv()).await {
+                Ok(Ok(item)) => return Ok(self.skip_fallback(item)),
+                Ok(Err(RecvError::Closed)) => self.primary_closed = true,
+                Ok(Err(RecvError::Lagged(_))) | Err(_) => {}
+            }
+        }
+
+        loop {
+            let item = if self.primary_closed {
+                self.fallback.recv().await
+            } else {
+                tokio::select! {
+                    biased;
+                    item = self.primary.recv() => match item {
+                        Ok(item) => return Ok(self.skip_fallback(item)),
+                        Err(RecvError::Closed) => {
+                            self.primary_closed = true;
+                            continue;
+                        }
+                        Err(RecvError::Lagged(_)) => continue,
+                    },
+                    item = self.fallback.recv() => item,
+                }
+            };
+
+            if item.is_ok() {
+                warn!(
+                    primary = %self.primary.local_id(),
+                    fallback = %self.fallback.local_id(),
+                    "Primary subscription timed out, yielding item from fallback."
+                );
+            }
+            return item;
+        }
+    }
+
+    /// Discard the items currently buffered on the fallback subscription.
+    fn skip_fallback(&mut self, item: T) -> T {
+        loop {
+            match self.fallback.inner_mut().try_recv() {
+                Ok(_) | Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => return item,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RawSubscription;
+    use alloy_primitives::B256;
+    use serde_json::value::{to_raw_value, RawValue};
+    use tokio::sync::broadcast;
+
+    fn channel(id: u8) -> (broadcast::Sender<Box<RawValue>>, Subscription<u64>) {
+        let (tx, rx) = broadcast::channel(16);
+        (
+            tx,
+            RawSubscription { rx, local_id: B256::with_last_byte(id), replay: Default::default() }
+                .into(),
+        )
+    }
+
+    fn send(tx: &broadcast::Sender<Box<RawValue>>, value: u64) {
+        tx.send(to_raw_value(&value).unwrap()).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn prefers_primary() {
+        let (primary_tx, primary) = channel(1);
+        let (fallback_tx, fallback) = channel(2);
+        let mut merged = primary.merge_with_priority(fallback, Duration::from_millis(100));
+
+        // Both arrive within the window, only the primary item is yielded.
+        send(&fallback_tx, 2);
+        send(&primary_tx, 1);
+        assert_eq!(merged.recv().await.unwrap(), 1);
+        assert!(merged.fallback().is_empty());
+
+        // The primary item arrives late, but within the window.
+        send(&fallback_tx, 2);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            send(&primary_tx, 3);
+        });
+        assert_eq!(merged.recv().await.unwrap(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn falls_back_after_timeout() {
+        let (primary_tx, primary) = channel(1);
+        let (fallback_tx, fallback) = channel(2);
+        let mut merged = primary.merge_with_priority(fallback, Duration::from_millis(100));
+
+        send(&fallback_tx, 2);
+        assert_eq!(merged.recv().await.unwrap(), 2);
+
+        // Only the fallback remains once the primary is closed.
+        drop(primary_tx);
+        send(&fallback_tx, 3);
+        assert_eq!(merged.recv().await.unwrap(), 3);
+
+        drop(fallback_tx);
+        assert_eq!(merged.recv().await.unwrap_err(), RecvError::Closed);
+    }
+}
@@ -0,0 +1,72 @@
+use alloy_json_rpc::EthNotification;
+use serde::{de::Error, Deserialize};
+use serde_json::value::RawValue;
+
+/// The JSON-RPC notification frame wrapping an [`EthNotification`].
+#[derive(Deserialize)]
+struct NotificationFrame<'a> {
+    #[serde(borrow)]
+    method: &'a str,
+    params: EthNotification,
+}
+
+/// Parse a raw `eth_subscription` JSON-RPC notification frame into the
+/// [`EthNotification`] it carries.
+///
+/// The frame is expected to be of the form
+/// `{"jsonrpc":"2.0","method":"eth_subscription","params":{"subscription":...,"result":...}}`.
+/// Frames with a different method, or without `params.subscription` and
+/// `params.result`, are rejected.
+///
+/// This is useful for custom transports that need to turn incoming frames into
+/// notifications without going through [`PubSubItem`].
+///
+/// [`PubSubItem`]: alloy_json_rpc::PubSubItem
+pub fn parse_notification(raw: &RawValue) -> Result<EthNotification, serde_json::Error> {
+    let frame: NotificationFrame<'_> = serde_json::from_str(raw.get())?;
+    if frame.method != "eth_subscription" {
+        return Err(serde_json::Error::custom(format!(
+            "unexpected notification method `{}`, expected `eth_subscription`",
+            frame.method
+        )));
+    }
+    Ok(frame.params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+
+    fn raw(s: &str) -> Box<RawValue> {
+        RawValue::from_string(s.to_owned()).unwrap()
+    }
+
+    #[test]
+    fn parses_eth_subscription() {
+        let frame = raw(
+            r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"subscription":"0xcd0c3e8af590364c09d0fa6a1210faf5","result":{"number":"0x1"}}}"#,
+        );
+        let notification = parse_notification(&frame).unwrap();
+        assert_eq!(
+            notification.subscription,
+            "0xcd0c3e8af590364c09d0fa6a1210faf5".parse::<U256>().unwrap()
+        );
+        assert_eq!(notification.result.get(), r#"{"number":"0x1"}"#);
+    }
+
+    #[test]
+    fn rejects_other_frames() {
+        let wrong_method = raw(
+            r#"{"jsonrpc":"2.0","method":"eth_foo","params":{"subscription":"0x1","result":null}}"#,
+        );
+        assert!(parse_notification(&wrong_method).unwrap_err().to_string().contains("eth_foo"));
+
+        let missing_result =
+            raw(r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"subscription":"0x1"}}"#);
+        assert!(parse_notification(&missing_result).is_err());
+
+        let response = raw(r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#);
+        assert!(parse_notification(&response).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{Subscription, SubscriptionItem};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+impl<T> Subscription<T> {
+    /// Call `callback` once the subscription is closed, e.g. to release
+    /// resources associated with it.
+    ///
+    /// The callback runs the first time receiving returns a `Closed` error,
+    /// before the error is returned. It does not run if the subscription is
+    /// dropped before that.
+    pub const fn on_close<F>(self, callback: F) -> OnCloseSubscription<T, F>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        OnCloseSubscription { inner: self, callback: Some(callback) }
+    }
+}
+
+/// A [`Subscription`] that calls a function when it is closed, created by
+/// [`Subscription::on_close`].
+pub struct OnCloseSubscription<T, F> {
+    inner: Subscription<T>,
+    callback: Option<F>,
+}
+
+impl<T, F> fmt::Debug for OnCloseSubscription<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnCloseSubscription")
+            .field("local_id", &self.inner.local_id())
+            .field("called", &self.callback.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, F> OnCloseSubscription<T, F>
+where
+    F: FnOnce() + Send + 'static,
+{
+    /// Get a reference to the inner subscription.
+    pub const fn inner(&self) -> &Subscription<T> {
+        &self.inner
+    }
+
+    /// Remove the callback, returning the inner [`Subscription`]. The callback
+    /// is dropped without being called.
+    #[allow(clippy::missing_const_for_fn)] // erroneous lint
+    pub fn into_inner(self) -> Subscription<T> {
+        self.inner
+    }
+
+    /// Call the callback if `closed` and it has not been called yet.
+    fn check_closed(&mut self, closed: bool) {
+        if closed {
+            if let Some(callback) = self.callback.take() {
+                callback();
+            }
+        }
+    }
+
+    fn check_recv<U>(&mut self, res: Result<U, RecvError>) -> Result<U, RecvError> {
+        self.check_closed(matches!(res, Err(RecvError::Closed)));
+        res
+    }
+
+    fn check_try_recv<U>(&mut self, res: Result<U, TryRecvError>) -> Result<U, TryRecvError> {
+        self.check_closed(matches!(res, Err(TryRecvError::Closed)));
+        res
+    }
+}
+
+impl<T: DeserializeOwned, F> OnCloseSubscription<T, F>
+where
+    F: FnOnce() + Send + 'static,
+{
+    /// Wrapper for [`Subscription::blocking_recv_any`].
+    pub fn blocking_recv_any(&mut self) -> Result<SubscriptionItem<T>, RecvError> {
+        let res = self.inner.blocking_recv_any();
+        self.check_recv(res)
+    }
+
+    /// Wrapper for [`Subscription::recv_any`].
+    pub async fn recv_any(&mut self) -> Result<SubscriptionItem<T>, RecvError> {
+        let res = self.inner.recv_any().await;
+        self.check_recv(res)
+    }
+
+    /// Wrapper for [`Subscription::try_recv_any`].
+    pub fn try_recv_any(&mut self) -> Result<SubscriptionItem<T>, TryRecvError> {
+        let res = self.inner.try_recv_any();
+        self.check_try_recv(res)
+    }
+
+    /// Wrapper for [`Subscription::blocking_recv`].
+    pub fn blocking_recv(&mut self) -> Result<T, RecvError> {
+        let res = self.inner.blocking_recv();
+        self.check_recv(res)
+    }
+
+    /// Wrapper for [`Subscription::recv`].
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        let res = self.inner.recv().await;
+        self.check_recv(res)
+    }
+
+    /// Wrapper for [`Subscription::try_recv`].
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let res = self.inner.try_recv();
+        self.check_try_recv(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RawSubscription, Subscription};
+    use alloy_primitives::B256;
+    use std::{
+        collections::VecDeque,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+    use tokio::sync::broadcast::{self, error::RecvError};
+
+    fn sub() -> (broadcast::Sender<Box<serde_json::value::RawValue>>, Subscription<u64>) {
+        let (tx, rx) = broadcast::channel(4);
+        (tx, RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into())
+    }
+
+    fn counter() -> (Arc<AtomicUsize>, impl FnOnce() + Send + 'static) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let callback = {
+            let calls = calls.clone();
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+            }
+        };
+        (calls, callback)
+    }
+
+    #[tokio::test]
+    async fn on_close() {
+        let (tx, sub) = sub();
+        let (calls, callback) = counter();
+        let mut sub = sub.on_close(callback);
+
+        tx.send(serde_json::value::to_raw_value(&1).unwrap()).unwrap();
+        drop(tx);
+        // The channel is closed, but an item is still available.
+        assert_eq!(sub.recv().await, Ok(1));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        assert_eq!(sub.recv().await, Err(RecvError::Closed));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(sub.recv().await, Err(RecvError::Closed));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn dropped_before_close() {
+        let (tx, sub) = sub();
+        let (calls, callback) = counter();
+        drop(sub.on_close(callback));
+        drop(tx);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+}
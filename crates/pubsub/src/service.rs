@@ -45,9 +45,10 @@ where
         let (tx, reqs) = mpsc::unbounded_channel();
         let this = Self {
             handle,
+            subs: SubscriptionManager::default()
+                .with_max_subscriptions(connector.max_subscriptions()),
             connector,
             reqs,
-            subs: Default::default(),
             in_flights: Default::default(),
         };
         this.spawn();
@@ -89,7 +90,7 @@ where
         debug!(count = self.subs.len(), "Re-starting active subscriptions");
 
         // Drop all server IDs. We'll re-insert them as we get responses.
-        self.subs.drop_server_ids();
+        self.subs.clear_server_ids_only();
 
         // Dispatch all subscription requests.
         for (_, sub) in self.subs.iter() {
@@ -121,36 +122,57 @@ where
 
     /// Service a GetSub instruction.
     ///
-    /// If the subscription exists, the waiter is sent a broadcast receiver. If
-    /// the subscription does not exist, the waiter is sent nothing, and the
-    /// `tx` is dropped. This notifies the waiter that the subscription does
-    /// not exist.
+    /// If the subscription exists, the waiter is sent a broadcast receiver,
+    /// pre-seeded with up to `history` retained notifications. If the
+    /// subscription does not exist, the waiter is sent nothing, and the `tx`
+    /// is dropped. This notifies the waiter that the subscription does not
+    /// exist.
     fn service_get_sub(
         &mut self,
         local_id: U256,
+        history: usize,
         tx: oneshot::Sender<RawSubscription>,
     ) -> TransportResult<()> {
         let local_id = local_id.into();
 
-        if let Some(rx) = self.subs.get_subscription(local_id) {
+        if let Some(rx) = self.subs.get_subscription_with_history(local_id, history) {
             let _ = tx.send(rx);
         }
 
         Ok(())
     }
 
+    /// Service a SetHistory instruction.
+    fn service_set_history(&mut self, local_id: U256, capacity: usize) -> TransportResult<()> {
+        self.subs.set_history_capacity(local_id.into(), capacity);
+        Ok(())
+    }
+
     /// Service an unsubscribe instruction.
+    ///
+    /// The server only knows the subscription by its current server ID. If it
+    /// has none, e.g. while re-subscribing after a reconnect, it is only
+    /// removed locally.
     fn service_unsubscribe(&mut self, local_id: U256) -> TransportResult<()> {
         let local_id = local_id.into();
+        match self.subs.server_id_for(local_id) {
+            Some(server_id) => {
+                self.dispatch_unsubscribe(server_id)?;
+                self.subs.remove_sub_by_server_id(server_id);
+            }
+            None => self.subs.remove_sub(local_id),
+        }
+        Ok(())
+    }
+
+    /// Cancel a subscription on the server.
+    fn dispatch_unsubscribe(&mut self, server_id: U256) -> TransportResult<()> {
         let req = Request {
             meta: RequestMeta { id: Id::None, method: "eth_unsubscribe" },
-            params: [local_id],
+            params: [server_id],
         };
         let brv = req.serialize().expect("no ser error").take_request();
-
-        self.dispatch_request(brv)?;
-        self.subs.remove_sub(local_id);
-        Ok(())
+        self.dispatch_request(brv)
     }
 
     /// Service an instruction
@@ -158,8 +180,21 @@ where
         trace!(?ix, "servicing instruction");
         match ix {
             PubSubInstruction::Request(in_flight) => self.service_request(in_flight),
-            PubSubInstruction::GetSub(alias, tx) => self.service_get_sub(alias, tx),
+            PubSubInstruction::GetSub(alias, history, tx) => {
+                self.service_get_sub(alias, history, tx)
+            }
+            PubSubInstruction::GetAllSubs(tx) => {
+                let _ = tx.send(self.subs.get_all_subscriptions());
+                Ok(())
+            }
+            PubSubInstruction::SetHistory(alias, capacity) => {
+                self.service_set_history(alias, capacity)
+            }
             PubSubInstruction::Unsubscribe(alias) => self.service_unsubscribe(alias),
+            PubSubInstruction::SubscribeAll(tx) => {
+                let _ = tx.send(self.subs.subscribe_all());
+                Ok(())
+            }
         }
     }
 
@@ -182,7 +217,12 @@ where
         let request = in_flight.request;
         let id = request.id().clone();
 
-        self.subs.upsert(request, server_id);
+        if let Err(err) = self.subs.upsert(request, server_id) {
+            warn!(%err, %server_id, "Rejecting subscription");
+            self.dispatch_unsubscribe(server_id)?;
+            let _ = in_flight.tx.send(Err(TransportErrorKind::custom(err)));
+            return Ok(());
+        }
 
         // lie to the client about the sub id.
         let local_id = self.subs.local_id_for(server_id).unwrap();
@@ -240,6 +280,16 @@ where
             if let Err(err) = result {
                 error!(%err, "pubsub service reconnection error");
             }
+
+            // Best-effort unsubscribe from the server, which fails if the
+            // backend is already gone.
+            for (local_id, server_id, _) in std::mem::take(&mut self.subs) {
+                let Some(server_id) = server_id else { continue };
+                if let Err(err) = self.dispatch_unsubscribe(server_id) {
+                    debug!(%err, %local_id, "could not unsubscribe on shutdown");
+                    break;
+                }
+            }
         };
         fut.spawn_task();
     }
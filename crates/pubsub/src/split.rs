@@ -0,0 +1,245 @@
+use crate::{
+    managers::CHANNEL_CAPACITY, sub::senders_dropped, RawSubscription, Subscription,
+    SubscriptionItem,
+};
+use alloy_primitives::B256;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::broadcast::{
+    self,
+    error::{RecvError, TryRecvError},
+};
+
+impl<T> Subscription<T> {
+    /// Split the subscription into a [`SubscriptionReader`] that receives the
+    /// notifications, and a cheaply cloneable [`SubscriptionHandle`] to query
+    /// the subscription's metadata from other tasks.
+    pub fn into_split(self) -> (SubscriptionReader<T>, SubscriptionHandle) {
+        let inner = self.into_raw();
+        let consumed = Arc::new(AtomicU64::new(0));
+        let handle = SubscriptionHandle {
+            local_id: inner.local_id,
+            tail: inner.rx.resubscribe(),
+            base: inner.len() as u64,
+            replay: inner.replay.len() as u64,
+            consumed: consumed.clone(),
+        };
+        (SubscriptionReader { inner, consumed, _pd: PhantomData }, handle)
+    }
+}
+
+/// The receiving half of a [`Subscription`], created by
+/// [`Subscription::into_split`].
+///
+/// Notifications of unexpected types are discarded, as by
+/// [`Subscription::recv`].
+#[derive(Debug)]
+pub struct SubscriptionReader<T> {
+    inner: RawSubscription,
+    /// The number of notifications consumed from the channel, including those
+    /// skipped due to lag.
+    consumed: Arc<AtomicU64>,
+    _pd: PhantomData<fn() -> T>,
+}
+
+impl<T> SubscriptionReader<T> {
+    /// Get the local ID of the subscription.
+    pub const fn local_id(&self) -> B256 {
+        self.inner.local_id()
+    }
+
+    fn track<V, E>(
+        &self,
+        result: Result<V, E>,
+        lagged: impl Fn(&E) -> Option<u64>,
+    ) -> Result<V, E> {
+        let consumed = match &result {
+            Ok(_) => 1,
+            Err(err) => lagged(err).unwrap_or(0),
+        };
+        self.consumed.fetch_add(consumed, Ordering::Relaxed);
+        result
+    }
+}
+
+impl<T: DeserializeOwned> SubscriptionReader<T> {
+    /// Wrapper for [`recv`]. Await an item of the expected type from the
+    /// channel.
+    ///
+    /// [`recv`]: broadcast::Receiver::recv
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        loop {
+            match self.recv_any().await? {
+                SubscriptionItem::Item(item) => return Ok(item),
+                SubscriptionItem::Other(_) => continue,
+            }
+        }
+    }
+
+    /// Wrapper for [`recv`], may produce unexpected values. Await an item from
+    /// the channel.
+    ///
+    /// [`recv`]: broadcast::Receiver::recv
+    pub async fn recv_any(&mut self) -> Result<SubscriptionItem<T>, RecvError> {
+        let result = self.inner.recv().await;
+        self.track(result, |err| match err {
+            RecvError::Lagged(missed) => Some(*missed),
+            RecvError::Closed => None,
+        })
+        .map(Into::into)
+    }
+
+    /// Wrapper for [`try_recv`]. Attempt to receive a message of the expected
+    /// type from the channel without awaiting.
+    ///
+    /// [`try_recv`]: broadcast::Receiver::try_recv
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        loop {
+            let result = self.inner.try_recv();
+            let value = self.track(result, |err| match err {
+                TryRecvError::Lagged(missed) => Some(*missed),
+                _ => None,
+            })?;
+            if let SubscriptionItem::Item(item) = value.into() {
+                return Ok(item);
+            }
+        }
+    }
+}
+
+/// A handle to query the metadata of a [`Subscription`] whose notifications
+/// are received by a [`SubscriptionReader`], created by
+/// [`Subscription::into_split`].
+///
+/// NB: The server ID of the subscription is not available. Subscriptions are
+/// identified by their local ID on the client side, and the server ID is only
+/// known to the pubsub service, which assigns a new one whenever it
+/// resubscribes after a reconnection.
+#[derive(Debug)]
+pub struct SubscriptionHandle {
+    local_id: B256,
+    /// A receiver that never receives, to count the notifications sent since
+    /// the handle was created.
+    tail: broadcast::Receiver<Box<RawValue>>,
+    /// The number of notifications left to receive when the handle was
+    /// created.
+    base: u64,
+    /// The number of replayed notifications, which the reader receives first.
+    replay: u64,
+    consumed: Arc<AtomicU64>,
+}
+
+impl SubscriptionHandle {
+    /// Get the local ID of the subscription.
+    pub const fn local_id(&self) -> B256 {
+        self.local_id
+    }
+
+    /// Returns the number of notifications the reader has yet to receive.
+    ///
+    /// Notifications that were already overwritten because the reader lagged
+    /// behind are not counted, so this is at most the channel's capacity, plus
+    /// any replayed notifications not yet received.
+    ///
+    /// NB: This count may include messages of unexpected types that will be
+    /// discarded upon receipt.
+    pub fn len(&self) -> usize {
+        let total = self.base + self.tail.len() as u64;
+        let consumed = self.consumed.load(Ordering::Relaxed);
+        let receivable = self.replay.saturating_sub(consumed) + CHANNEL_CAPACITY as u64;
+        total.saturating_sub(consumed).min(receivable) as usize
+    }
+
+    /// Returns `true` if the reader currently has no notifications to receive.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if no further notifications can be received, i.e. the
+    /// sender has been dropped and the reader has drained the channel.
+    pub fn is_closed(&self) -> bool {
+        senders_dropped(&self.tail) && self.is_empty()
+    }
+}
+
+impl Clone for SubscriptionHandle {
+    fn clone(&self) -> Self {
+        Self {
+            local_id: self.local_id,
+            tail: self.tail.resubscribe(),
+            base: self.base + self.tail.len() as u64,
+            replay: self.replay,
+            consumed: self.consumed.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::value::to_raw_value;
+    use std::collections::VecDeque;
+
+    #[tokio::test]
+    async fn into_split() {
+        let (tx, rx) = broadcast::channel(CHANNEL_CAPACITY);
+        let sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::with_last_byte(1), replay: VecDeque::new() }
+                .into();
+        tx.send(to_raw_value(&1).unwrap()).unwrap();
+
+        let (mut reader, handle) = sub.into_split();
+        assert_eq!(handle.local_id(), B256::with_last_byte(1));
+        assert_eq!(handle.len(), 1);
+        tx.send(to_raw_value(&2).unwrap()).unwrap();
+        assert_eq!(handle.len(), 2);
+
+        let cloned = handle.clone();
+        assert_eq!(reader.recv().await.unwrap(), 1);
+        assert_eq!((handle.len(), cloned.len()), (1, 1));
+
+        // Overwritten notifications are not pending.
+        for value in 3..=20u64 {
+            tx.send(to_raw_value(&value).unwrap()).unwrap();
+        }
+        assert_eq!((handle.len(), cloned.len()), (CHANNEL_CAPACITY, CHANNEL_CAPACITY));
+        assert_eq!(reader.try_recv().unwrap_err(), TryRecvError::Lagged(3));
+        assert_eq!(handle.len(), CHANNEL_CAPACITY);
+        assert_eq!(reader.try_recv().unwrap(), 5);
+        assert_eq!(handle.len(), CHANNEL_CAPACITY - 1);
+
+        drop(tx);
+        for value in 6..=19 {
+            assert_eq!(reader.recv().await.unwrap(), value);
+        }
+        assert!(!cloned.is_closed());
+        assert_eq!(reader.recv().await.unwrap(), 20);
+        assert!(cloned.is_closed());
+        assert_eq!(reader.recv().await.unwrap_err(), RecvError::Closed);
+    }
+
+    #[tokio::test]
+    async fn len_with_replay() {
+        let (tx, rx) = broadcast::channel(CHANNEL_CAPACITY);
+        let replay = (0..2u64).map(|value| to_raw_value(&value).unwrap()).collect();
+        let sub: Subscription<u64> = RawSubscription { rx, local_id: B256::ZERO, replay }.into();
+        let (mut reader, handle) = sub.into_split();
+        for value in 0..100u64 {
+            tx.send(to_raw_value(&value).unwrap()).unwrap();
+        }
+
+        // Replayed notifications are received before the channel's.
+        assert_eq!(handle.len(), CHANNEL_CAPACITY + 2);
+        assert_eq!(reader.recv().await.unwrap(), 0);
+        assert_eq!(handle.len(), CHANNEL_CAPACITY + 1);
+        assert_eq!(reader.recv().await.unwrap(), 1);
+        assert_eq!(handle.len(), CHANNEL_CAPACITY);
+    }
+}
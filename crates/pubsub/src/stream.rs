@@ -0,0 +1,756 @@
+use crate::{RawSubscription, Subscription, SubscriptionItem};
+use alloy_primitives::{Bytes, B256};
+use futures::{future, stream, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::broadcast,
+    time::{Instant, Sleep},
+};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+
+impl RawSubscription {
+    /// Convert the subscription into a [`Stream`] of the raw bytes of each
+    /// notification's JSON.
+    ///
+    /// Notifications missed because the subscription lagged behind are skipped
+    /// with a warning. The stream ends once the subscription is closed.
+    pub fn into_bytes_stream(self) -> impl Stream<Item = Bytes> {
+        self.into_raw_stream().map(|value| Bytes::copy_from_slice(value.get().as_bytes()))
+    }
+
+    /// Convert the subscription into a [`Stream`] of each notification parsed
+    /// as a [`serde_json::Value`].
+    ///
+    /// Lag and closing are handled as in
+    /// [`into_bytes_stream`](Self::into_bytes_stream).
+    pub fn into_json_stream(self) -> impl Stream<Item = serde_json::Value> {
+        self.into_raw_stream().filter_map(|value| {
+            // The value is valid JSON, but may exceed `serde_json`'s recursion limit.
+            future::ready(serde_json::from_str(value.get()).ok())
+        })
+    }
+
+    fn into_raw_stream(self) -> impl Stream<Item = Box<RawValue>> {
+        let local_id = self.local_id;
+        stream::iter(self.replay).chain(BroadcastStream::new(self.rx).filter_map(move |value| {
+            future::ready(match value {
+                Ok(value) => Some(value),
+                Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                    warn!(%local_id, missed, "Subscription stream lagged.");
+                    None
+                }
+            })
+        }))
+    }
+}
+
+/// An error ending a [`Stream`] of the items of a [`Subscription`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptionError {
+    /// The subscription lagged behind more than the given number of times in
+    /// a row, see [`Subscription::retry_on_lag`].
+    MaxLagsExceeded(u32),
+}
+
+impl fmt::Display for SubscriptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxLagsExceeded(max) => {
+                write!(f, "subscription lagged behind more than {max} times in a row")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubscriptionError {}
+
+impl<T> Subscription<T> {
+    /// Convert the subscription into a [`Stream`] of items of the expected
+    /// type.
+    ///
+    /// See [`SubscriptionStream`] for details.
+    pub fn into_stream(self) -> SubscriptionStream<T> {
+        let inner = self.into_raw();
+        SubscriptionStream {
+            local_id: inner.local_id,
+            replay: inner.replay,
+            rx: BroadcastStream::new(inner.rx),
+            _pd: PhantomData,
+        }
+    }
+
+    /// Convert the subscription into a [`Stream`] that expands each item into
+    /// the items of the iterator returned by `f`.
+    ///
+    /// See [`FlatMapSubscription`] for details.
+    pub fn flat_map<U, I, F>(self, f: F) -> FlatMapSubscription<T, U, I, F>
+    where
+        F: Fn(T) -> I,
+        I: IntoIterator<Item = U>,
+    {
+        FlatMapSubscription { stream: self.into_stream(), f, current: None }
+    }
+
+    /// Convert the subscription into a [`Stream`] that discards the first `n`
+    /// items of the expected type, e.g. an initial snapshot.
+    ///
+    /// See [`SkipSubscription`] for details.
+    pub fn skip(self, n: usize) -> SkipSubscription<T> {
+        SkipSubscription { stream: self.into_stream(), n, received: 0 }
+    }
+
+    /// Convert the subscription into a [`Stream`] that yields at most one item
+    /// per `interval`, dropping all but the most recent of the items received
+    /// in between.
+    ///
+    /// See [`ThrottleSubscription`] for details.
+    pub fn throttle(self, interval: Duration) -> ThrottleSubscription<T> {
+        ThrottleSubscription {
+            stream: self.into_stream(),
+            interval,
+            latest: None,
+            done: false,
+            sleep: Box::pin(tokio::time::sleep(Duration::ZERO)),
+        }
+    }
+
+    /// Convert the subscription into a [`Stream`] of chunks of up to `size`
+    /// items, e.g. for batch processing.
+    ///
+    /// See [`ChunkSubscription`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn chunks(self, size: usize, max_delay: Duration) -> ChunkSubscription<T> {
+        assert_ne!(size, 0, "chunk size must be non-zero");
+        ChunkSubscription {
+            stream: self.into_stream(),
+            size,
+            max_delay,
+            buffer: Vec::with_capacity(size),
+            done: false,
+            sleep: Box::pin(tokio::time::sleep(max_delay)),
+        }
+    }
+
+    /// Convert the subscription into a [`Stream`] of items of the expected
+    /// type that [resubscribes](Self::resubscribe) whenever the subscription
+    /// lags behind, skipping all buffered notifications.
+    ///
+    /// See [`RetryOnLagSubscription`] for details.
+    pub fn retry_on_lag(self, max_retries: u32) -> RetryOnLagSubscription<T> {
+        let inner = self.into_raw();
+        RetryOnLagSubscription {
+            local_id: inner.local_id,
+            replay: inner.replay,
+            tail: inner.rx.resubscribe(),
+            rx: BroadcastStream::new(inner.rx),
+            max_retries,
+            lags: 0,
+            resubscribes: 0,
+            done: false,
+            _pd: PhantomData,
+        }
+    }
+}
+
+/// A [`Stream`] of the items of a [`Subscription`], created by
+/// [`Subscription::into_stream`].
+///
+/// Like [`Subscription::recv`], notifications of unexpected types are
+/// discarded. Notifications missed because the subscription lagged behind are
+/// skipped with a warning. The stream ends once the subscription is closed.
+pub struct SubscriptionStream<T> {
+    local_id: B256,
+    replay: VecDeque<Box<RawValue>>,
+    rx: BroadcastStream<Box<RawValue>>,
+    _pd: PhantomData<fn() -> T>,
+}
+
+impl<T> SubscriptionStream<T> {
+    /// Get the local ID of the subscription.
+    pub const fn local_id(&self) -> B256 {
+        self.local_id
+    }
+}
+
+impl<T> fmt::Debug for SubscriptionStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubscriptionStream")
+            .field("local_id", &self.local_id)
+            .field("replay", &self.replay.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        loop {
+            let value = match this.replay.pop_front() {
+                Some(value) => value,
+                None => match Pin::new(&mut this.rx).poll_next(cx) {
+                    Poll::Ready(Some(Ok(value))) => value,
+                    Poll::Ready(Some(Err(BroadcastStreamRecvError::Lagged(missed)))) => {
+                        warn!(local_id = %this.local_id, missed, "Subscription stream lagged.");
+                        continue;
+                    }
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+            if let SubscriptionItem::Item(item) = value.into() {
+                return Poll::Ready(Some(item));
+            }
+        }
+    }
+}
+
+/// A [`Stream`] of the items of a [`Subscription`] that resubscribes whenever
+/// the subscription lags behind, created by [`Subscription::retry_on_lag`].
+///
+/// Like [`Subscription::recv`], notifications of unexpected types are
+/// discarded. On lag, the stream resubscribes from the current tail of the
+/// channel, skipping all buffered notifications. The new receiver starts with
+/// no lag of its own, but the number of consecutive [`lags`](Self::lags) is
+/// only reset by receiving an item. So at most `max_retries` resubscriptions
+/// happen in a row: on the next lag, the stream yields
+/// [`SubscriptionError::MaxLagsExceeded`] and ends. It also ends once the
+/// subscription is closed.
+///
+/// NB: To resubscribe, the stream holds a receiver that never receives, which
+/// keeps up to the channel's capacity of notifications alive.
+pub struct RetryOnLagSubscription<T> {
+    local_id: B256,
+    replay: VecDeque<Box<RawValue>>,
+    /// A receiver that never receives, to resubscribe from the tail.
+    tail: broadcast::Receiver<Box<RawValue>>,
+    rx: BroadcastStream<Box<RawValue>>,
+    max_retries: u32,
+    lags: u32,
+    resubscribes: u32,
+    done: bool,
+    _pd: PhantomData<fn() -> T>,
+}
+
+impl<T> RetryOnLagSubscription<T> {
+    /// Get the local ID of the subscription.
+    pub const fn local_id(&self) -> B256 {
+        self.local_id
+    }
+
+    /// Get the maximum number of consecutive lags to resubscribe after.
+    pub const fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Get the number of lags since the last item was received.
+    pub const fn lags(&self) -> u32 {
+        self.lags
+    }
+
+    /// Get the total number of times the stream resubscribed.
+    pub const fn resubscribes(&self) -> u32 {
+        self.resubscribes
+    }
+}
+
+impl<T> fmt::Debug for RetryOnLagSubscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryOnLagSubscription")
+            .field("local_id", &self.local_id)
+            .field("replay", &self.replay.len())
+            .field("max_retries", &self.max_retries)
+            .field("lags", &self.lags)
+            .field("resubscribes", &self.resubscribes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned> Stream for RetryOnLagSubscription<T> {
+    type Item = Result<T, SubscriptionError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.done {
+            let value = match this.replay.pop_front() {
+                Some(value) => value,
+                None => match Pin::new(&mut this.rx).poll_next(cx) {
+                    Poll::Ready(Some(Ok(value))) => value,
+                    Poll::Ready(Some(Err(BroadcastStreamRecvError::Lagged(missed)))) => {
+                        if this.lags >= this.max_retries {
+                            this.done = true;
+                            let err = SubscriptionError::MaxLagsExceeded(this.max_retries);
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                        this.lags += 1;
+                        this.resubscribes += 1;
+                        warn!(local_id = %this.local_id, missed, lags = this.lags, "Subscription lagged, resubscribing.");
+                        this.rx = BroadcastStream::new(this.tail.resubscribe());
+                        continue;
+                    }
+                    Poll::Ready(None) => break,
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+            if let SubscriptionItem::Item(item) = value.into() {
+                this.lags = 0;
+                return Poll::Ready(Some(Ok(item)));
+            }
+        }
+        this.done = true;
+        Poll::Ready(None)
+    }
+}
+
+/// A [`Stream`] that expands each item of a [`Subscription`] into multiple
+/// items, created by [`Subscription::flat_map`].
+///
+/// The items of the iterator returned for one notification are buffered and
+/// yielded before the next notification is received.
+pub struct FlatMapSubscription<T, U, I, F>
+where
+    I: IntoIterator<Item = U>,
+{
+    stream: SubscriptionStream<T>,
+    f: F,
+    current: Option<I::IntoIter>,
+}
+
+impl<T, U, I, F> FlatMapSubscription<T, U, I, F>
+where
+    I: IntoIterator<Item = U>,
+{
+    /// Get the local ID of the subscription.
+    pub const fn local_id(&self) -> B256 {
+        self.stream.local_id()
+    }
+}
+
+impl<T, U, I, F> fmt::Debug for FlatMapSubscription<T, U, I, F>
+where
+    I: IntoIterator<Item = U>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlatMapSubscription").field("stream", &self.stream).finish_non_exhaustive()
+    }
+}
+
+impl<T, U, I, F> Stream for FlatMapSubscription<T, U, I, F>
+where
+    T: DeserializeOwned,
+    F: Fn(T) -> I + Unpin,
+    I: IntoIterator<Item = U>,
+    I::IntoIter: Unpin,
+{
+    type Item = U;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<U>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.current.as_mut().and_then(Iterator::next) {
+                return Poll::Ready(Some(item));
+            }
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => this.current = Some((this.f)(item).into_iter()),
+                Poll::Ready(None) => {
+                    this.current = None;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A [`Stream`] of the items of a [`Subscription`] that discards the first
+/// items, created by [`Subscription::skip`].
+///
+/// Only items of the expected type count towards the number of skipped items,
+/// notifications of unexpected types are discarded as usual. The skipped items
+/// still count as [`received`](Self::received).
+#[derive(Debug)]
+pub struct SkipSubscription<T> {
+    stream: SubscriptionStream<T>,
+    n: usize,
+    received: usize,
+}
+
+impl<T> SkipSubscription<T> {
+    /// Get the local ID of the subscription.
+    pub const fn local_id(&self) -> B256 {
+        self.stream.local_id()
+    }
+
+    /// Get the number of items that remain to be skipped.
+    pub const fn remaining(&self) -> usize {
+        self.n.saturating_sub(self.received)
+    }
+
+    /// Get the number of items skipped so far.
+    pub const fn skipped(&self) -> usize {
+        self.n - self.remaining()
+    }
+
+    /// Get the number of items received so far, including the skipped ones.
+    pub const fn received(&self) -> usize {
+        self.received
+    }
+}
+
+impl<T: DeserializeOwned> Stream for SkipSubscription<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        loop {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.received += 1;
+                    if this.received > this.n {
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                poll => return poll,
+            }
+        }
+    }
+}
+
+/// A [`Stream`] of the items of a [`Subscription`] that yields at most one
+/// item per interval, created by [`Subscription::throttle`].
+///
+/// The first item is yielded as soon as it is received. Afterwards, the most
+/// recent item received since the last yielded one is yielded once the
+/// interval has elapsed, and older items are dropped. When the subscription is
+/// closed, the last buffered item is still yielded at the next tick.
+pub struct ThrottleSubscription<T> {
+    stream: SubscriptionStream<T>,
+    interval: Duration,
+    latest: Option<T>,
+    done: bool,
+    sleep: Pin<Box<Sleep>>,
+}
+
+impl<T> ThrottleSubscription<T> {
+    /// Get the local ID of the subscription.
+    pub const fn local_id(&self) -> B256 {
+        self.stream.local_id()
+    }
+
+    /// Get the minimum interval between two yielded items.
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+// The buffered item is never pinned.
+impl<T> Unpin for ThrottleSubscription<T> {}
+
+impl<T> fmt::Debug for ThrottleSubscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThrottleSubscription")
+            .field("stream", &self.stream)
+            .field("interval", &self.interval)
+            .field("buffered", &self.latest.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned> Stream for ThrottleSubscription<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        while !this.done {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => this.latest = Some(item),
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if this.latest.is_none() {
+            return if this.done { Poll::Ready(None) } else { Poll::Pending };
+        }
+        if this.sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        this.sleep.as_mut().reset(Instant::now() + this.interval);
+        Poll::Ready(this.latest.take())
+    }
+}
+
+/// A [`Stream`] of chunks of the items of a [`Subscription`], created by
+/// [`Subscription::chunks`].
+///
+/// A chunk is yielded as soon as it is full, or once the maximum delay elapsed
+/// since its first item was received. When the subscription is closed, the
+/// last partial chunk is yielded right away. Chunks are never empty.
+pub struct ChunkSubscription<T> {
+    stream: SubscriptionStream<T>,
+    size: usize,
+    max_delay: Duration,
+    buffer: Vec<T>,
+    done: bool,
+    sleep: Pin<Box<Sleep>>,
+}
+
+impl<T> ChunkSubscription<T> {
+    /// Get the local ID of the subscription.
+    pub const fn local_id(&self) -> B256 {
+        self.stream.local_id()
+    }
+
+    /// Get the maximum number of items in a chunk.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Get the maximum delay between receiving the first item of a chunk and
+    /// yielding it.
+    pub const fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    fn take_chunk(&mut self) -> Vec<T> {
+        std::mem::replace(&mut self.buffer, Vec::with_capacity(self.size))
+    }
+}
+
+// The buffered items are never pinned.
+impl<T> Unpin for ChunkSubscription<T> {}
+
+impl<T> fmt::Debug for ChunkSubscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkSubscription")
+            .field("stream", &self.stream)
+            .field("size", &self.size)
+            .field("max_delay", &self.max_delay)
+            .field("buffered", &self.buffer.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned> Stream for ChunkSubscription<T> {
+    type Item = Vec<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Vec<T>>> {
+        let this = self.get_mut();
+        while !this.done {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if this.buffer.is_empty() {
+                        this.sleep.as_mut().reset(Instant::now() + this.max_delay);
+                    }
+                    this.buffer.push(item);
+                    if this.buffer.len() >= this.size {
+                        return Poll::Ready(Some(this.take_chunk()));
+                    }
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if this.buffer.is_empty() {
+            return if this.done { Poll::Ready(None) } else { Poll::Pending };
+        }
+        if this.done || this.sleep.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Some(this.take_chunk()));
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RawSubscription;
+    use futures::StreamExt;
+    use serde_json::value::to_raw_value;
+    use tokio::sync::broadcast;
+
+    fn channel<T>() -> (broadcast::Sender<Box<RawValue>>, Subscription<T>) {
+        let (tx, rx) = broadcast::channel(16);
+        (tx, RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into())
+    }
+
+    #[tokio::test]
+    async fn into_stream() {
+        let (tx, sub) = channel::<u64>();
+        tx.send(to_raw_value(&1).unwrap()).unwrap();
+        tx.send(to_raw_value("unexpected").unwrap()).unwrap();
+        tx.send(to_raw_value(&2).unwrap()).unwrap();
+        drop(tx);
+        assert_eq!(sub.into_stream().collect::<Vec<_>>().await, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn flat_map() {
+        let (tx, sub) = channel::<Vec<u64>>();
+        tx.send(to_raw_value(&[1, 2, 3]).unwrap()).unwrap();
+        tx.send(to_raw_value(&[0u64; 0]).unwrap()).unwrap();
+        tx.send(to_raw_value(&[4]).unwrap()).unwrap();
+        drop(tx);
+
+        let items = sub.flat_map(|items| items.into_iter().map(|item| item * 10));
+        assert_eq!(items.collect::<Vec<_>>().await, [10, 20, 30, 40]);
+    }
+
+    #[tokio::test]
+    async fn skip() {
+        let (tx, sub) = channel::<u64>();
+        for value in 1..=4 {
+            tx.send(to_raw_value(&value).unwrap()).unwrap();
+        }
+        tx.send(to_raw_value("unexpected").unwrap()).unwrap();
+        drop(tx);
+
+        let mut items = sub.skip(2);
+        assert_eq!((items.remaining(), items.skipped(), items.received()), (2, 0, 0));
+        assert_eq!(items.next().await, Some(3));
+        assert_eq!((items.remaining(), items.skipped(), items.received()), (0, 2, 3));
+        assert_eq!(items.next().await, Some(4));
+        assert_eq!(items.next().await, None);
+        assert_eq!((items.skipped(), items.received()), (2, 4));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn chunks() {
+        let max_delay = Duration::from_secs(1);
+        let (tx, sub) = channel::<u64>();
+        let mut chunks = sub.chunks(3, max_delay);
+        let start = Instant::now();
+
+        for value in 1..=4 {
+            tx.send(to_raw_value(&value).unwrap()).unwrap();
+        }
+        assert_eq!(chunks.next().await, Some(vec![1, 2, 3]));
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // A partial chunk is flushed once the delay elapsed since its first item.
+        assert_eq!(chunks.next().await, Some(vec![4]));
+        assert_eq!(start.elapsed(), max_delay);
+
+        tokio::time::sleep(max_delay * 5).await;
+        tx.send(to_raw_value(&5).unwrap()).unwrap();
+        assert_eq!(chunks.next().await, Some(vec![5]));
+        assert_eq!(start.elapsed(), max_delay * 7);
+
+        // Closing flushes the last partial chunk right away.
+        tx.send(to_raw_value(&6).unwrap()).unwrap();
+        drop(tx);
+        assert_eq!(chunks.next().await, Some(vec![6]));
+        assert_eq!(start.elapsed(), max_delay * 7);
+        assert_eq!(chunks.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn retry_on_lag() {
+        use futures::FutureExt;
+
+        let (tx, rx) = broadcast::channel(1);
+        let sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        let overflow = || {
+            tx.send(to_raw_value(&0).unwrap()).unwrap();
+            tx.send(to_raw_value(&0).unwrap()).unwrap();
+        };
+        let mut items = sub.retry_on_lag(2);
+
+        // After a lag, the buffered notification is skipped by resubscribing.
+        for lags in 1..=2 {
+            overflow();
+            assert!(items.next().now_or_never().is_none());
+            assert_eq!((items.lags(), items.resubscribes()), (lags, lags));
+        }
+        // Receiving an item resets the lags.
+        tx.send(to_raw_value(&1).unwrap()).unwrap();
+        assert_eq!(items.next().await, Some(Ok(1)));
+        assert_eq!((items.lags(), items.resubscribes()), (0, 2));
+
+        for _ in 0..2 {
+            overflow();
+            assert!(items.next().now_or_never().is_none());
+        }
+        assert_eq!(items.resubscribes(), 4);
+        // The third lag in a row ends the stream without resubscribing.
+        overflow();
+        assert_eq!(items.next().await, Some(Err(SubscriptionError::MaxLagsExceeded(2))));
+        assert_eq!(items.resubscribes(), 4);
+        assert_eq!(items.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn retry_on_lag_closed() {
+        let (tx, sub) = channel::<u64>();
+        tx.send(to_raw_value(&1).unwrap()).unwrap();
+        drop(tx);
+        assert_eq!(sub.retry_on_lag(0).collect::<Vec<_>>().await, [Ok(1)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throttle() {
+        let interval = Duration::from_secs(1);
+        let (tx, sub) = channel::<u64>();
+        let mut items = sub.throttle(interval);
+        let start = Instant::now();
+
+        tx.send(to_raw_value(&1).unwrap()).unwrap();
+        assert_eq!(items.next().await, Some(1));
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // Only the most recent item is yielded, once the interval elapsed.
+        for value in 2..=4 {
+            tx.send(to_raw_value(&value).unwrap()).unwrap();
+        }
+        assert_eq!(items.next().await, Some(4));
+        assert_eq!(start.elapsed(), interval);
+
+        // An item received after the interval elapsed is yielded right away.
+        tokio::time::sleep(interval * 3).await;
+        tx.send(to_raw_value(&5).unwrap()).unwrap();
+        assert_eq!(items.next().await, Some(5));
+        assert_eq!(start.elapsed(), interval * 4);
+
+        // The last item is still yielded after the subscription closed.
+        tx.send(to_raw_value(&6).unwrap()).unwrap();
+        drop(tx);
+        assert_eq!(items.next().await, Some(6));
+        assert_eq!(start.elapsed(), interval * 5);
+        assert_eq!(items.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn into_bytes_and_json_streams() {
+        let (tx, rx) = broadcast::channel(16);
+        let sub = RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() };
+        let json = sub.resubscribe().into_json_stream();
+        tx.send(to_raw_value(&1).unwrap()).unwrap();
+        tx.send(to_raw_value(&serde_json::json!({ "a": [true] })).unwrap()).unwrap();
+        drop(tx);
+
+        assert_eq!(
+            sub.into_bytes_stream().collect::<Vec<_>>().await,
+            [Bytes::from_static(b"1"), Bytes::from_static(br#"{"a":[true]}"#)]
+        );
+        assert_eq!(
+            json.collect::<Vec<_>>().await,
+            [serde_json::json!(1), serde_json::json!({ "a": [true] })]
+        );
+    }
+}
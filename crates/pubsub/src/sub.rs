@@ -1,7 +1,17 @@
 use alloy_primitives::B256;
 use serde::de::DeserializeOwned;
 use serde_json::value::RawValue;
-use tokio::sync::broadcast;
+use std::{collections::VecDeque, time::Duration};
+use tokio::{sync::broadcast, time::Instant};
+
+/// Returns `true` if all senders of the broadcast channel have been dropped.
+///
+/// A fresh receiver starts at the tail of the channel, so its `try_recv` only
+/// reports whether the channel is closed, without consuming anything from
+/// `rx`.
+pub(crate) fn senders_dropped<T: Clone>(rx: &broadcast::Receiver<T>) -> bool {
+    matches!(rx.resubscribe().try_recv(), Err(broadcast::error::TryRecvError::Closed))
+}
 
 /// A Subscription is a feed of notifications from the server, identified by a
 /// local ID.
@@ -14,6 +24,8 @@ pub struct RawSubscription {
     pub(crate) rx: broadcast::Receiver<Box<RawValue>>,
     /// The local ID of the subscription.
     pub(crate) local_id: B256,
+    /// Retained notifications to yield before any received from the channel.
+    pub(crate) replay: VecDeque<Box<RawValue>>,
 }
 
 impl RawSubscription {
@@ -27,25 +39,54 @@ impl RawSubscription {
     ///
     /// [`blocking_recv`]: broadcast::Receiver::blocking_recv
     pub fn blocking_recv(&mut self) -> Result<Box<RawValue>, broadcast::error::RecvError> {
+        if let Some(item) = self.replay.pop_front() {
+            return Ok(item);
+        }
         self.rx.blocking_recv()
     }
 
     /// Returns `true` if the broadcast channel is empty (i.e. there are
     /// currently no notifications to receive).
     pub fn is_empty(&self) -> bool {
-        self.rx.is_empty()
+        self.replay.is_empty() && self.rx.is_empty()
     }
 
     /// Returns the number of messages in the broadcast channel that this
     /// receiver has yet to receive.
     pub fn len(&self) -> usize {
-        self.rx.len()
+        self.replay.len() + self.rx.len()
+    }
+
+    /// Returns `true` if no further notifications can be received, i.e. the
+    /// sender has been dropped and the broadcast channel is drained.
+    ///
+    /// Unlike [`is_empty`](Self::is_empty), this does not return `true` while
+    /// the subscription is merely waiting for the next notification.
+    pub fn is_closed(&self) -> bool {
+        senders_dropped(&self.rx) && self.is_empty()
+    }
+
+    /// Wait until the broadcast channel is closed, i.e. the server ended the
+    /// subscription or the connection was dropped, without receiving any
+    /// notifications from this subscription.
+    ///
+    /// Notifications that were sent before the channel closed may still be
+    /// received afterwards, see [`is_closed`](Self::is_closed).
+    ///
+    /// This awaits a [`resubscribe`](Self::resubscribe)d receiver, which
+    /// clones each notification sent while waiting.
+    pub async fn closed(&self) {
+        let mut rx = self.rx.resubscribe();
+        while !matches!(rx.recv().await, Err(broadcast::error::RecvError::Closed)) {}
     }
 
     /// Wrapper for [`recv`]. Await an item from the channel.
     ///
     /// [`recv`]: broadcast::Receiver::recv
     pub async fn recv(&mut self) -> Result<Box<RawValue>, broadcast::error::RecvError> {
+        if let Some(item) = self.replay.pop_front() {
+            return Ok(item);
+        }
         self.rx.recv().await
     }
 
@@ -54,7 +95,7 @@ impl RawSubscription {
     ///
     /// [`resubscribe`]: broadcast::Receiver::resubscribe
     pub fn resubscribe(&self) -> Self {
-        Self { rx: self.rx.resubscribe(), local_id: self.local_id }
+        Self { rx: self.rx.resubscribe(), local_id: self.local_id, replay: VecDeque::new() }
     }
 
     /// Wrapper for [`same_channel`]. Returns `true` if the two subscriptions
@@ -70,6 +111,9 @@ impl RawSubscription {
     ///
     /// [`try_recv`]: broadcast::Receiver::try_recv
     pub fn try_recv(&mut self) -> Result<Box<RawValue>, broadcast::error::TryRecvError> {
+        if let Some(item) = self.replay.pop_front() {
+            return Ok(item);
+        }
         self.rx.try_recv()
     }
 }
@@ -84,6 +128,44 @@ pub enum SubscriptionItem<T> {
     Other(Box<RawValue>),
 }
 
+impl<T> SubscriptionItem<T> {
+    /// Returns `true` if this is the expected item.
+    pub const fn is_item(&self) -> bool {
+        matches!(self, Self::Item(_))
+    }
+
+    /// Returns `true` if this is some other value.
+    pub const fn is_other(&self) -> bool {
+        matches!(self, Self::Other(_))
+    }
+
+    /// Converts into the expected item, if it is one.
+    #[allow(clippy::missing_const_for_fn)] // erroneous lint
+    pub fn item(self) -> Option<T> {
+        match self {
+            Self::Item(item) => Some(item),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Converts into the other value, if it is one.
+    #[allow(clippy::missing_const_for_fn)] // erroneous lint
+    pub fn other(self) -> Option<Box<RawValue>> {
+        match self {
+            Self::Item(_) => None,
+            Self::Other(value) => Some(value),
+        }
+    }
+
+    /// Maps the expected item with `f`, leaving other values untouched.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> SubscriptionItem<U> {
+        match self {
+            Self::Item(item) => SubscriptionItem::Item(f(item)),
+            Self::Other(value) => SubscriptionItem::Other(value),
+        }
+    }
+}
+
 impl<T: DeserializeOwned> From<Box<RawValue>> for SubscriptionItem<T> {
     fn from(value: Box<RawValue>) -> Self {
         if let Ok(item) = serde_json::from_str(value.get()) {
@@ -95,6 +177,43 @@ impl<T: DeserializeOwned> From<Box<RawValue>> for SubscriptionItem<T> {
     }
 }
 
+/// A type that notifications of one of several known shapes can be classified
+/// into, used by [`Subscription::recv_enum`].
+///
+/// This is typically implemented for an enum with one variant per known type,
+/// by attempting to deserialize the notification into each variant's type in
+/// order.
+pub trait SubscriptionEnum: Sized {
+    /// Attempt to classify the notification, returning `None` if it matches
+    /// none of the known types.
+    fn try_from_raw(value: &RawValue) -> Option<Self>;
+}
+
+/// An event in a typed [`Subscription`], as returned by
+/// [`Subscription::recv_event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionEvent<T> {
+    /// The expected item.
+    Item(T),
+    /// The subscription lagged behind, and this many notifications were
+    /// skipped.
+    Lagged(u64),
+}
+
+/// Why [`Subscription::collect_n_timeout`] stopped collecting items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectOutcome {
+    /// The requested number of items was collected.
+    Full,
+    /// The timeout elapsed first.
+    TimedOut,
+    /// The subscription was closed first.
+    Closed,
+    /// The subscription lagged behind, and this many notifications were
+    /// skipped.
+    Lagged(u64),
+}
+
 /// A Subscription is a feed of notifications from the server of a specific
 /// type `T`, identified by a local ID.
 ///
@@ -108,12 +227,67 @@ impl<T: DeserializeOwned> From<Box<RawValue>> for SubscriptionItem<T> {
 #[derive(Debug)]
 pub struct Subscription<T> {
     pub(crate) inner: RawSubscription,
-    _pd: std::marker::PhantomData<T>,
+    /// The item last yielded by [`Subscription::recv_changed`].
+    last_changed: Option<T>,
+    /// The rate at which receiving lagged behind.
+    lags: LagRate,
+    /// Whether [`Subscription::recv_or_default`] returned the default for
+    /// the closed channel.
+    closed_default: bool,
 }
 
 impl<T> From<RawSubscription> for Subscription<T> {
     fn from(inner: RawSubscription) -> Self {
-        Self { inner, _pd: std::marker::PhantomData }
+        Self { inner, last_changed: None, lags: LagRate::default(), closed_default: false }
+    }
+}
+
+/// An exponentially decaying estimate of how often a receiver lags behind,
+/// in lags per second.
+#[derive(Debug, Default)]
+struct LagRate {
+    /// The estimated rate as of `updated`.
+    rate: f64,
+    /// When the rate was last updated, if ever.
+    updated: Option<Instant>,
+}
+
+impl LagRate {
+    /// The time constant of the decay. Lags older than this weigh less than
+    /// `1/e` of recent ones.
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    /// Returns the rate decayed from `updated` to `now`.
+    fn at(&self, now: Instant) -> f64 {
+        self.updated.map_or(0.0, |updated| {
+            let elapsed = now.saturating_duration_since(updated).as_secs_f64();
+            self.rate * (-elapsed / Self::WINDOW.as_secs_f64()).exp()
+        })
+    }
+
+    /// Records a lag at the current time.
+    fn record(&mut self) {
+        let now = Instant::now();
+        self.rate = self.at(now) + 1.0 / Self::WINDOW.as_secs_f64();
+        self.updated = Some(now);
+    }
+}
+
+/// A broadcast receive error that may report lag.
+trait LagError {
+    /// Returns `true` if the receiver lagged behind.
+    fn is_lagged(&self) -> bool;
+}
+
+impl LagError for broadcast::error::RecvError {
+    fn is_lagged(&self) -> bool {
+        matches!(self, Self::Lagged(_))
+    }
+}
+
+impl LagError for broadcast::error::TryRecvError {
+    fn is_lagged(&self) -> bool {
+        matches!(self, Self::Lagged(_))
     }
 }
 
@@ -154,6 +328,21 @@ impl<T> Subscription<T> {
         self.inner.len()
     }
 
+    /// Returns `true` if no further notifications can be received, i.e. the
+    /// sender has been dropped and the broadcast channel is drained.
+    ///
+    /// Unlike [`is_empty`](Self::is_empty), this does not return `true` while
+    /// the subscription is merely waiting for the next notification.
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// Wait until the broadcast channel is closed, without receiving any
+    /// notifications from this subscription. See [`RawSubscription::closed`].
+    pub async fn closed(&self) {
+        self.inner.closed().await
+    }
+
     /// Wrapper for [`resubscribe`]. Create a new [`RawSubscription`], starting
     /// from the current tail element.
     ///
@@ -170,6 +359,24 @@ impl<T> Subscription<T> {
         self.inner.resubscribe().into()
     }
 
+    /// Returns how often receiving from this subscription lagged behind
+    /// recently, in lags per second.
+    ///
+    /// Each lag counts once, regardless of the number of notifications it
+    /// skipped. The rate decays exponentially with a time constant of one
+    /// minute, so it approximates the average rate over the last minute.
+    pub fn lag_rate(&self) -> f64 {
+        self.lags.at(Instant::now())
+    }
+
+    /// Records a lag if `res` reports one, returning `res` unchanged.
+    fn track_lag<U, E: LagError>(&mut self, res: Result<U, E>) -> Result<U, E> {
+        if res.as_ref().err().map_or(false, LagError::is_lagged) {
+            self.lags.record();
+        }
+        res
+    }
+
     /// Wrapper for [`same_channel`]. Returns `true` if the two subscriptions
     /// share the same broadcast channel.
     ///
@@ -177,6 +384,28 @@ impl<T> Subscription<T> {
     pub fn same_channel<U>(&self, other: &Subscription<U>) -> bool {
         self.inner.same_channel(&other.inner)
     }
+
+    /// Wrapper for [`recv`]. Await an item from the channel, classifying it
+    /// into one of several known types via [`SubscriptionEnum`].
+    ///
+    /// Unlike [`Subscription::recv_any`], this ignores `T`. Notifications that
+    /// match none of the known types are yielded as
+    /// [`SubscriptionItem::Other`].
+    ///
+    /// [`recv`]: broadcast::Receiver::recv
+    pub async fn recv_enum<E: SubscriptionEnum>(
+        &mut self,
+    ) -> Result<SubscriptionItem<E>, broadcast::error::RecvError> {
+        let res = self.inner.recv().await;
+        let value = self.track_lag(res)?;
+        Ok(match E::try_from_raw(&value) {
+            Some(item) => SubscriptionItem::Item(item),
+            None => {
+                trace!(value = value.get(), "Received unexpected value in subscription.");
+                SubscriptionItem::Other(value)
+            }
+        })
+    }
 }
 
 impl<T: DeserializeOwned> Subscription<T> {
@@ -187,7 +416,8 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn blocking_recv_any(
         &mut self,
     ) -> Result<SubscriptionItem<T>, broadcast::error::RecvError> {
-        self.inner.blocking_recv().map(Into::into)
+        let res = self.inner.blocking_recv();
+        self.track_lag(res).map(Into::into)
     }
 
     /// Wrapper for [`recv`], may produce unexpected values. Await an item from
@@ -195,7 +425,8 @@ impl<T: DeserializeOwned> Subscription<T> {
     ///
     /// [`recv`]: broadcast::Receiver::recv
     pub async fn recv_any(&mut self) -> Result<SubscriptionItem<T>, broadcast::error::RecvError> {
-        self.inner.recv().await.map(Into::into)
+        let res = self.inner.recv().await;
+        self.track_lag(res).map(Into::into)
     }
 
     /// Wrapper for [`try_recv`]. Attempt to receive a message from the channel
@@ -203,7 +434,8 @@ impl<T: DeserializeOwned> Subscription<T> {
     ///
     /// [`try_recv`]: broadcast::Receiver::try_recv
     pub fn try_recv_any(&mut self) -> Result<SubscriptionItem<T>, broadcast::error::TryRecvError> {
-        self.inner.try_recv().map(Into::into)
+        let res = self.inner.try_recv();
+        self.track_lag(res).map(Into::into)
     }
 
     /// Wrapper for [`blocking_recv`]. Block the current thread until a message
@@ -232,6 +464,87 @@ impl<T: DeserializeOwned> Subscription<T> {
         }
     }
 
+    /// Await an item of the expected type from the channel, returning
+    /// `T::default()` on lag and, once, on closure instead of an error.
+    ///
+    /// This is sugar for simple consumers that would ignore errors anyway. The
+    /// default value is indistinguishable from a genuine notification of the
+    /// same value, so lag goes unnoticed unless checked separately:
+    ///
+    /// - On lag, `Ok(T::default())` is returned, the lag is counted in
+    ///   [`lag_rate`](Self::lag_rate), and the following calls continue from the oldest
+    ///   notification still buffered in the channel.
+    /// - Once the channel is closed, `Ok(T::default())` is returned exactly once. The subscription
+    ///   has ended at this point: all further calls return [`RecvError::Closed`], so a receiving
+    ///   loop can stop on the first error.
+    ///
+    /// Use [`recv`](Self::recv) or [`recv_event`](Self::recv_event) to handle
+    /// these cases explicitly.
+    ///
+    /// [`RecvError::Closed`]: broadcast::error::RecvError::Closed
+    pub async fn recv_or_default(&mut self) -> Result<T, broadcast::error::RecvError>
+    where
+        T: Default,
+    {
+        if self.closed_default {
+            return Err(broadcast::error::RecvError::Closed);
+        }
+        match self.recv().await {
+            Ok(item) => Ok(item),
+            Err(broadcast::error::RecvError::Lagged(_)) => Ok(T::default()),
+            Err(broadcast::error::RecvError::Closed) => {
+                self.closed_default = true;
+                Ok(T::default())
+            }
+        }
+    }
+
+    /// Wrapper for [`recv`]. Await an item of the expected type from the
+    /// channel, reporting lag as [`SubscriptionEvent::Lagged`] instead of an
+    /// error.
+    ///
+    /// After a lag, the notifications still buffered in the channel are
+    /// skipped as well, and the following calls continue from the next
+    /// notification sent to the channel.
+    ///
+    /// [`recv`]: broadcast::Receiver::recv
+    pub async fn recv_event(
+        &mut self,
+    ) -> Result<SubscriptionEvent<T>, broadcast::error::RecvError> {
+        loop {
+            match self.recv_any().await {
+                Ok(SubscriptionItem::Item(item)) => return Ok(SubscriptionEvent::Item(item)),
+                Ok(SubscriptionItem::Other(_)) => continue,
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    // Skip the stale backlog by moving to the tail of the channel.
+                    self.inner.rx = self.inner.rx.resubscribe();
+                    return Ok(SubscriptionEvent::Lagged(count));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Wrapper for [`recv`]. Await an item of the expected type from the
+    /// channel that differs from the item last returned by this method.
+    ///
+    /// Useful for state feeds that repeat the same value. The first item is
+    /// always returned.
+    ///
+    /// [`recv`]: broadcast::Receiver::recv
+    pub async fn recv_changed(&mut self) -> Result<T, broadcast::error::RecvError>
+    where
+        T: PartialEq + Clone,
+    {
+        loop {
+            let item = self.recv().await?;
+            if self.last_changed.as_ref() != Some(&item) {
+                self.last_changed = Some(item.clone());
+                return Ok(item);
+            }
+        }
+    }
+
     /// Wrapper for [`try_recv`]. Attempt to receive a message of the expected
     /// type from the channel without awaiting.
     ///
@@ -245,6 +558,56 @@ impl<T: DeserializeOwned> Subscription<T> {
         }
     }
 
+    /// Receive all items of the expected type that are currently buffered,
+    /// without awaiting. Notifications of unexpected types are discarded.
+    ///
+    /// Returns [`TryRecvError::Lagged`] or [`TryRecvError::Closed`] if the
+    /// subscription lagged behind or closed before it was drained, in which
+    /// case the items received so far are dropped.
+    ///
+    /// [`TryRecvError::Lagged`]: broadcast::error::TryRecvError::Lagged
+    /// [`TryRecvError::Closed`]: broadcast::error::TryRecvError::Closed
+    pub fn drain(&mut self) -> Result<Vec<T>, broadcast::error::TryRecvError> {
+        let mut items = Vec::with_capacity(self.len());
+        loop {
+            match self.try_recv() {
+                Ok(item) => items.push(item),
+                Err(broadcast::error::TryRecvError::Empty) => return Ok(items),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Await up to `n` items of the expected type, for at most `timeout`.
+    /// Notifications of unexpected types are discarded.
+    ///
+    /// Stops early if the timeout elapses, or the subscription lags behind or
+    /// is closed, returning the items received so far along with the reason.
+    /// After a lag, the following calls continue from the oldest notification
+    /// still buffered in the channel.
+    pub async fn collect_n_timeout(
+        &mut self,
+        n: usize,
+        timeout: Duration,
+    ) -> (Vec<T>, CollectOutcome) {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut items = Vec::with_capacity(n);
+        let outcome = loop {
+            if items.len() >= n {
+                break CollectOutcome::Full;
+            }
+            match tokio::time::timeout_at(deadline, self.recv()).await {
+                Ok(Ok(item)) => items.push(item),
+                Ok(Err(broadcast::error::RecvError::Closed)) => break CollectOutcome::Closed,
+                Ok(Err(broadcast::error::RecvError::Lagged(count))) => {
+                    break CollectOutcome::Lagged(count)
+                }
+                Err(_) => break CollectOutcome::TimedOut,
+            }
+        };
+        (items, outcome)
+    }
+
     /// Wrapper for [`blocking_recv`]. Block the current thread until a message
     /// is available, deserializing the message and returning the result.
     ///
@@ -252,7 +615,8 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn blocking_recv_result(
         &mut self,
     ) -> Result<Result<T, serde_json::Error>, broadcast::error::RecvError> {
-        self.inner.blocking_recv().map(|value| serde_json::from_str(value.get()))
+        let res = self.inner.blocking_recv();
+        self.track_lag(res).map(|value| serde_json::from_str(value.get()))
     }
 
     /// Wrapper for [`recv`]. Await an item from the channel, deserializing the
@@ -262,7 +626,8 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub async fn recv_result(
         &mut self,
     ) -> Result<Result<T, serde_json::Error>, broadcast::error::RecvError> {
-        self.inner.recv().await.map(|value| serde_json::from_str(value.get()))
+        let res = self.inner.recv().await;
+        self.track_lag(res).map(|value| serde_json::from_str(value.get()))
     }
 
     /// Wrapper for [`try_recv`]. Attempt to receive a message from the channel
@@ -272,6 +637,229 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn try_recv_result(
         &mut self,
     ) -> Result<Result<T, serde_json::Error>, broadcast::error::TryRecvError> {
-        self.inner.try_recv().map(|value| serde_json::from_str(value.get()))
+        let res = self.inner.try_recv();
+        self.track_lag(res).map(|value| serde_json::from_str(value.get()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_item() {
+        let item = SubscriptionItem::<u64>::from(serde_json::value::to_raw_value(&1).unwrap());
+        assert!(item.is_item() && !item.is_other());
+        assert_eq!(item.map(|n| n + 1).item(), Some(2));
+
+        let other = SubscriptionItem::<u64>::from(serde_json::value::to_raw_value("a").unwrap());
+        assert!(other.is_other() && !other.is_item());
+        let other = other.map(|n| n + 1);
+        assert_eq!(other.other().unwrap().get(), r#""a""#);
+    }
+
+    #[tokio::test]
+    async fn recv_or_default() {
+        let (tx, rx) = broadcast::channel(2);
+        let mut sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+
+        // Three notifications overflow the channel by one.
+        for value in 1..=3u64 {
+            tx.send(serde_json::value::to_raw_value(&value).unwrap()).unwrap();
+        }
+        assert_eq!(sub.recv_or_default().await, Ok(0));
+        assert!(sub.lag_rate() > 0.0);
+        assert_eq!(sub.recv_or_default().await, Ok(2));
+        assert_eq!(sub.recv_or_default().await, Ok(3));
+
+        // The default is returned exactly once after closing.
+        drop(tx);
+        assert_eq!(sub.recv_or_default().await, Ok(0));
+        assert!(sub.is_closed());
+        assert_eq!(sub.recv_or_default().await, Err(broadcast::error::RecvError::Closed));
+        assert_eq!(sub.recv_or_default().await, Err(broadcast::error::RecvError::Closed));
+    }
+
+    #[test]
+    fn is_closed() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        assert!(sub.is_empty() && !sub.is_closed());
+
+        tx.send(serde_json::value::to_raw_value(&1).unwrap()).unwrap();
+        drop(tx);
+        assert!(!sub.is_closed());
+
+        assert_eq!(sub.try_recv().unwrap(), 1);
+        assert!(sub.is_closed());
+    }
+
+    #[tokio::test]
+    async fn closed() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        {
+            let closed = sub.closed();
+            tokio::pin!(closed);
+
+            tx.send(serde_json::value::to_raw_value(&1).unwrap()).unwrap();
+            assert!(futures::poll!(closed.as_mut()).is_pending());
+
+            drop(tx);
+            closed.await;
+        }
+        assert_eq!(sub.recv().await.unwrap(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn collect_n_timeout() {
+        let timeout = Duration::from_secs(2);
+        let (tx, rx) = broadcast::channel(4);
+        let mut sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        let send = |value: u64| tx.send(serde_json::value::to_raw_value(&value).unwrap()).unwrap();
+
+        for value in 1..=3 {
+            send(value);
+        }
+        assert_eq!(sub.collect_n_timeout(2, timeout).await, (vec![1, 2], CollectOutcome::Full));
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(sub.collect_n_timeout(2, timeout).await, (vec![3], CollectOutcome::TimedOut));
+        assert_eq!(start.elapsed(), timeout);
+
+        for value in 4..=9 {
+            send(value);
+        }
+        assert_eq!(sub.collect_n_timeout(2, timeout).await, (vec![], CollectOutcome::Lagged(2)));
+
+        drop(tx);
+        assert_eq!(
+            sub.collect_n_timeout(10, timeout).await,
+            (vec![6, 7, 8, 9], CollectOutcome::Closed)
+        );
+    }
+
+    #[test]
+    fn drain() {
+        let (tx, rx) = broadcast::channel(2);
+        let mut sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        let send = |value: serde_json::Value| {
+            tx.send(serde_json::value::to_raw_value(&value).unwrap()).unwrap();
+        };
+
+        assert!(sub.drain().unwrap().is_empty());
+        send(1.into());
+        send("unexpected".into());
+        assert_eq!(sub.drain().unwrap(), [1]);
+
+        // Three items overflow the channel.
+        send(2.into());
+        send(3.into());
+        send(4.into());
+        assert_eq!(sub.drain().unwrap_err(), broadcast::error::TryRecvError::Lagged(1));
+        assert_eq!(sub.drain().unwrap(), [3, 4]);
+
+        drop(tx);
+        assert_eq!(sub.drain().unwrap_err(), broadcast::error::TryRecvError::Closed);
+    }
+
+    #[tokio::test]
+    async fn recv_event() {
+        let (tx, rx) = broadcast::channel(2);
+        let mut sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+
+        // Five notifications overflow the channel by three.
+        for value in 1..=5u64 {
+            tx.send(serde_json::value::to_raw_value(&value).unwrap()).unwrap();
+        }
+        assert_eq!(sub.recv_event().await.unwrap(), SubscriptionEvent::Lagged(3));
+        // The stale notifications 4 and 5 are skipped.
+        assert!(sub.is_empty());
+        tx.send(serde_json::value::to_raw_value(&6u64).unwrap()).unwrap();
+        assert_eq!(sub.recv_event().await.unwrap(), SubscriptionEvent::Item(6));
+
+        drop(tx);
+        assert_eq!(sub.recv_event().await.unwrap_err(), broadcast::error::RecvError::Closed);
+    }
+
+    #[tokio::test]
+    async fn recv_enum() {
+        #[derive(Debug, PartialEq)]
+        enum Feed {
+            Number(u64),
+            Text(String),
+        }
+
+        impl SubscriptionEnum for Feed {
+            fn try_from_raw(value: &RawValue) -> Option<Self> {
+                serde_json::from_str(value.get())
+                    .map(Self::Number)
+                    .or_else(|_| serde_json::from_str(value.get()).map(Self::Text))
+                    .ok()
+            }
+        }
+
+        let (tx, rx) = broadcast::channel(16);
+        let mut sub: Subscription<()> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        for value in [serde_json::json!(1), "text".into(), true.into()] {
+            tx.send(serde_json::value::to_raw_value(&value).unwrap()).unwrap();
+        }
+
+        assert!(matches!(sub.recv_enum().await, Ok(SubscriptionItem::Item(Feed::Number(1)))));
+        assert!(
+            matches!(sub.recv_enum().await, Ok(SubscriptionItem::Item(Feed::Text(text))) if text == "text")
+        );
+        match sub.recv_enum::<Feed>().await.unwrap() {
+            SubscriptionItem::Other(value) => assert_eq!(value.get(), "true"),
+            item => panic!("unexpected item: {item:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn recv_changed() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        for value in [1u64, 1, 2, 2, 2, 1] {
+            tx.send(serde_json::value::to_raw_value(&value).unwrap()).unwrap();
+        }
+        drop(tx);
+
+        assert_eq!(sub.recv_changed().await.unwrap(), 1);
+        assert_eq!(sub.recv_changed().await.unwrap(), 2);
+        assert_eq!(sub.recv_changed().await.unwrap(), 1);
+        assert_eq!(sub.recv_changed().await.unwrap_err(), broadcast::error::RecvError::Closed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lag_rate() {
+        let (tx, rx) = broadcast::channel(1);
+        let mut sub: Subscription<u64> =
+            RawSubscription { rx, local_id: B256::ZERO, replay: VecDeque::new() }.into();
+        let send = |value: u64| tx.send(serde_json::value::to_raw_value(&value).unwrap()).unwrap();
+        assert_eq!(sub.lag_rate(), 0.0);
+
+        // Lag once per second.
+        for value in 0..600 {
+            send(value);
+            send(value);
+            assert!(matches!(sub.try_recv(), Err(broadcast::error::TryRecvError::Lagged(1))));
+            assert_eq!(sub.recv().await.unwrap(), value);
+            tokio::time::advance(Duration::from_secs(1)).await;
+        }
+        assert!((sub.lag_rate() - 1.0).abs() < 0.01, "{}", sub.lag_rate());
+
+        // Without further lags, the rate decays.
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert!((sub.lag_rate() - (-1.0f64).exp()).abs() < 0.01, "{}", sub.lag_rate());
+        tokio::time::advance(Duration::from_secs(3600)).await;
+        assert!(sub.lag_rate() < 1e-9);
     }
 }
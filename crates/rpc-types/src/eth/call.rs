@@ -1,4 +1,4 @@
-use crate::{AccessList, BlockId, BlockOverrides};
+use crate::{AccessList, BlockId, BlockOverrides, SignedAuthorization};
 use alloy_primitives::{Address, Bytes, B256, U256, U64, U8};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -133,6 +133,9 @@ pub struct CallRequest {
     /// EIP-2718 type
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub transaction_type: Option<U8>,
+    /// Authorization list for EIP-7702 transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_list: Option<Vec<SignedAuthorization>>,
 }
 
 impl CallRequest {
@@ -0,0 +1,63 @@
+use alloy_primitives::{Address, U256, U64, U8};
+use serde::{Deserialize, Serialize};
+
+/// An unsigned EIP-7702 authorization, allowing the code at `address` to be set on the account of
+/// its signer.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Authorization {
+    /// The chain ID the authorization is valid on, or `0` for all chains.
+    pub chain_id: U256,
+    /// The address of the code to delegate to.
+    pub address: Address,
+    /// The nonce of the signing account at which the authorization is valid.
+    pub nonce: U64,
+}
+
+/// A signed EIP-7702 authorization, as included in the authorization list of a type 4
+/// transaction.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAuthorization {
+    /// The authorization that was signed.
+    #[serde(flatten)]
+    pub inner: Authorization,
+    /// The parity of the signature's `y` coordinate.
+    pub y_parity: U8,
+    /// The `r` value of the signature.
+    pub r: U256,
+    /// The `s` value of the signature.
+    pub s: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_authorization_serde() {
+        let auth = SignedAuthorization {
+            inner: Authorization {
+                chain_id: U256::from(1),
+                address: Address::with_last_byte(1),
+                nonce: U64::from(2),
+            },
+            y_parity: U8::from(1),
+            r: U256::from(3),
+            s: U256::from(4),
+        };
+        let json = serde_json::to_value(auth).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "chainId": "0x1",
+                "address": "0x0000000000000000000000000000000000000001",
+                "nonce": "0x2",
+                "yParity": "0x1",
+                "r": "0x3",
+                "s": "0x4",
+            })
+        );
+        assert_eq!(serde_json::from_value::<SignedAuthorization>(json).unwrap(), auth);
+    }
+}
@@ -14,3 +14,84 @@ pub struct BlobTransactionSidecar {
     /// The blob proofs.
     pub proofs: Vec<Bytes48>,
 }
+
+impl BlobTransactionSidecar {
+    /// Returns the JSON representation of the sidecar, as returned by JSON-RPC APIs like
+    /// `eth_getTransactionByHash`: each blob, commitment and proof is a `0x`-prefixed hex string.
+    pub fn to_json_representation(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("sidecar serialization is infallible")
+    }
+
+    /// Parses a sidecar from its [JSON representation](Self::to_json_representation).
+    ///
+    /// Fails if a field is missing, if a blob, commitment or proof is not a hex string of the
+    /// expected length, or if the numbers of blobs, commitments and proofs differ.
+    pub fn from_json_representation(value: &serde_json::Value) -> Result<Self, BlobJsonError> {
+        let sidecar = Self::deserialize(value)?;
+        if sidecar.commitments.len() != sidecar.blobs.len()
+            || sidecar.proofs.len() != sidecar.blobs.len()
+        {
+            return Err(BlobJsonError::LengthMismatch {
+                blobs: sidecar.blobs.len(),
+                commitments: sidecar.commitments.len(),
+                proofs: sidecar.proofs.len(),
+            });
+        }
+        Ok(sidecar)
+    }
+}
+
+/// Error that can occur when parsing a [`BlobTransactionSidecar`]
+#[derive(Debug, thiserror::Error)]
+pub enum BlobJsonError {
+    /// The sidecar JSON is malformed
+    #[error("failed to parse blob sidecar: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The sidecar has different numbers of blobs, commitments and proofs
+    #[error("blob sidecar has {blobs} blobs, {commitments} commitments and {proofs} proofs")]
+    LengthMismatch {
+        /// The number of blobs.
+        blobs: usize,
+        /// The number of commitments.
+        commitments: usize,
+        /// The number of proofs.
+        proofs: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::BYTES_PER_BLOB;
+
+    #[test]
+    fn json_representation_roundtrip() {
+        // Blobs are too large to build on the stack in debug builds, so start from JSON.
+        let hex = |byte: &str, len: usize| format!("0x{}", byte.repeat(len));
+        let json = serde_json::json!({
+            "blobs": [hex("01", BYTES_PER_BLOB), hex("02", BYTES_PER_BLOB)],
+            "commitments": [hex("03", 48), hex("04", 48)],
+            "proofs": [hex("05", 48), hex("06", 48)],
+        });
+
+        let sidecar = BlobTransactionSidecar::from_json_representation(&json).unwrap();
+        assert_eq!(sidecar.blobs.len(), 2);
+        assert!(sidecar.blobs[1].iter().all(|&byte| byte == 0x02));
+        assert_eq!(sidecar.commitments[0], Bytes48::repeat_byte(0x03));
+        assert_eq!(sidecar.to_json_representation(), json);
+
+        let mut invalid = json.clone();
+        invalid["proofs"][0] = "0x05".into();
+        assert!(matches!(
+            BlobTransactionSidecar::from_json_representation(&invalid),
+            Err(BlobJsonError::Json(_))
+        ));
+
+        let mut mismatched = json;
+        mismatched["commitments"].as_array_mut().unwrap().pop();
+        assert!(matches!(
+            BlobTransactionSidecar::from_json_representation(&mismatched),
+            Err(BlobJsonError::LengthMismatch { blobs: 2, commitments: 1, proofs: 2 })
+        ));
+    }
+}
@@ -3,7 +3,8 @@
 use crate::eth::other::OtherFields;
 pub use access_list::{AccessList, AccessListItem, AccessListWithGasUsed};
 use alloy_primitives::{Address, Bytes, B256, U128, U256, U64};
-pub use blob::BlobTransactionSidecar;
+pub use authorization::{Authorization, SignedAuthorization};
+pub use blob::{BlobJsonError, BlobTransactionSidecar};
 pub use common::TransactionInfo;
 pub use optimism::OptimismTransactionReceiptFields;
 pub use receipt::TransactionReceipt;
@@ -11,6 +12,7 @@ use serde::{Deserialize, Serialize};
 pub use signature::{Parity, Signature};
 
 mod access_list;
+mod authorization;
 mod common;
 pub mod kzg;
 pub mod optimism;